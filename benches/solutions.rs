@@ -0,0 +1,20 @@
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+use aoc2023::input::daily_input;
+use aoc2023::puzzle::{solvers, Part};
+
+fn solutions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("solutions");
+    group.sample_size(10);
+    for solver in solvers() {
+        let input = daily_input(solver.year, solver.day).unwrap();
+        for part in Part::both() {
+            let id = BenchmarkId::new(format!("{}/day{:0>2}", solver.year, solver.day), format!("part{}", part));
+            group.bench_with_input(id, &input, |b, input| b.iter(|| (solver.solve)(part, input.clone())));
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, solutions);
+criterion_main!(benches);
@@ -0,0 +1,30 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn puzzle_day(file_name: &str) -> Option<u32> {
+    file_name.strip_prefix("day")?.strip_suffix(".txt")?.parse().ok()
+}
+
+fn embedded_inputs() -> String {
+    let inputs = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/inputs");
+    println!("cargo:rerun-if-changed={}", inputs.display());
+    let mut entries = Vec::new();
+    for year in fs::read_dir(&inputs).into_iter().flatten().flatten() {
+        let Some(year_number) = year.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else { continue };
+        println!("cargo:rerun-if-changed={}", year.path().display());
+        for file in fs::read_dir(year.path()).into_iter().flatten().flatten() {
+            if let Some(day) = file.file_name().to_str().and_then(puzzle_day) {
+                entries.push(format!("    ({}, {}, include_str!({:?})),\n", year_number, day, file.path().display().to_string()));
+            }
+        }
+    }
+    entries.sort();
+    entries.concat()
+}
+
+fn main() {
+    let entries = if env::var_os("CARGO_FEATURE_EMBEDDED_INPUTS").is_some() { embedded_inputs() } else { String::new() };
+    let table = format!("pub static INPUTS: &[(u32, u32, &str)] = &[\n{}];\n", entries);
+    fs::write(Path::new(&env::var("OUT_DIR").unwrap()).join("embedded_inputs.rs"), table).unwrap();
+}
@@ -0,0 +1,8 @@
+#![no_main]
+
+use aoc2023::puzzle::{solver, split_lines};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = (solver(2023, 4).unwrap().parse)(split_lines(input));
+});
@@ -0,0 +1,17 @@
+#ifndef AOC2023_H
+#define AOC2023_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+/* Solves a part of a day on `len` bytes of UTF-8 input. Returns the answer, to release with aoc_free,
+   or NULL on failure, in which case aoc_last_error() describes the failure. */
+char *aoc_solve(uint32_t day, uint8_t part, const uint8_t *input, size_t len);
+
+/* Message of the last failure of aoc_solve on the calling thread, or NULL. Owned by the library. */
+const char *aoc_last_error(void);
+
+/* Releases an answer returned by aoc_solve. */
+void aoc_free(char *answer);
+
+#endif
@@ -0,0 +1,46 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::meta::ParseNestedMeta;
+use syn::{parse_macro_input, parse_quote, ItemImpl, LitInt};
+
+#[derive(Default)]
+struct Registration {
+    year: Option<LitInt>,
+    day: Option<LitInt>,
+}
+
+impl Registration {
+    fn parse(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("day") {
+            self.day = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("year") {
+            self.year = Some(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error("expected `day` or `year`"));
+        }
+        Ok(())
+    }
+}
+
+/// Sets the day (and optionally the year) of a `Puzzle` implementation and registers its solver, e.g.
+/// `#[aoc(day = 9)] impl Puzzle for Day09 { ... }`.
+#[proc_macro_attribute]
+pub fn aoc(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut registration = Registration::default();
+    let parser = syn::meta::parser(|meta| registration.parse(meta));
+    parse_macro_input!(args with parser);
+    let mut puzzle = parse_macro_input!(item as ItemImpl);
+    let Some(day) = registration.day else {
+        return syn::Error::new_spanned(&puzzle.self_ty, "missing `day = N`").to_compile_error().into();
+    };
+    puzzle.items.insert(0, parse_quote! { const DAY: u32 = #day; });
+    if let Some(year) = registration.year {
+        puzzle.items.insert(0, parse_quote! { const YEAR: u32 = #year; });
+    }
+    let ty = &puzzle.self_ty;
+    quote! {
+        #puzzle
+
+        ::inventory::submit! { crate::puzzle::Solver::of::<#ty>() }
+    }.into()
+}
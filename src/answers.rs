@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use std::fs::read_to_string;
+
+use toml::Table;
+
+use crate::config::config;
+use crate::error::{AocError, Result};
+use crate::puzzle::Part;
+
+const ANSWERS: &str = "answers.toml";
+
+fn example_answers_path(year: u32, day: u32) -> String {
+    format!("{}/{}/day{:0>2}.toml", config().examples, year, day)
+}
+
+pub struct Answers {
+    answers: HashMap<(u32, u32, Part), String>,
+}
+
+impl Answers {
+    pub fn load() -> Result<Answers> {
+        Answers::load_from(ANSWERS)
+    }
+
+    pub fn load_from(path: &str) -> Result<Answers> {
+        Answers::parse(&read_to_string(path).unwrap_or_default())
+    }
+
+    /// Table of a registry, a malformed one being reported at the line the TOML parser gave up on.
+    fn parse_table(registry: &str) -> Result<Table> {
+        registry.parse().map_err(|error: toml::de::Error| {
+            let line = registry[..error.span().map_or(0, |span| span.start)].matches('\n').count();
+            AocError::parse(line + 1, registry.lines().nth(line).unwrap_or_default(), error.message().replace('\n', ", "))
+        })
+    }
+
+    fn parse(registry: &str) -> Result<Answers> {
+        let table = Self::parse_table(registry)?;
+        let answers = table.iter()
+            .filter_map(|(year, days)| Some((year.parse().ok()?, days.as_table()?)))
+            .flat_map(|(year, days)| days.iter()
+                .filter_map(move |(day, parts)| Some((year, day.strip_prefix("day")?.parse().ok()?, parts.as_table()?))))
+            .flat_map(|(year, day, parts)| Part::both().into_iter()
+                .filter_map(move |part| Some(((year, day, part), Self::answer_in(parts, part)?))))
+            .collect();
+        Ok(Answers { answers })
+    }
+
+    pub fn load_example(year: u32, day: u32) -> Result<Answers> {
+        Answers::parse_example(year, day, &read_to_string(example_answers_path(year, day)).unwrap_or_default())
+    }
+
+    fn parse_example(year: u32, day: u32, registry: &str) -> Result<Answers> {
+        let parts = Self::parse_table(registry)?;
+        let answers = Part::both().into_iter()
+            .filter_map(|part| Some(((year, day, part), Self::answer_in(&parts, part)?)))
+            .collect();
+        Ok(Answers { answers })
+    }
+
+    fn answer_in(parts: &Table, part: Part) -> Option<String> {
+        match parts.get(&format!("part{}", part))? {
+            toml::Value::String(answer) => Some(answer.to_owned()),
+            toml::Value::Integer(answer) => Some(answer.to_string()),
+            _ => None,
+        }
+    }
+
+    pub fn get(&self, year: u32, day: u32, part: Part) -> Option<&str> {
+        self.answers.get(&(year, day, part)).map(String::as_str)
+    }
+}
+
+pub fn expected_answer(year: u32, day: u32, part: Part) -> String {
+    Answers::load().unwrap().get(year, day, part).unwrap().to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_answers_by_day_and_part() {
+        let answers = Answers::parse("[2023.day07]\npart1 = \"241344943\"\npart2 = 243101568\n").unwrap();
+        assert_eq!(answers.get(2023, 7, Part::One), Some("241344943"));
+        assert_eq!(answers.get(2023, 7, Part::Two), Some("243101568"));
+        assert_eq!(answers.get(2023, 8, Part::One), None);
+        assert_eq!(answers.get(2022, 7, Part::One), None);
+    }
+
+    #[test]
+    fn reads_example_answers_of_a_day() {
+        let answers = Answers::parse_example(2023, 8, "part1 = \"6\"\n").unwrap();
+        assert_eq!(answers.get(2023, 8, Part::One), Some("6"));
+        assert_eq!(answers.get(2023, 8, Part::Two), None);
+    }
+
+    #[test]
+    fn reports_line_of_malformed_registry() {
+        let error = Answers::parse("[2023.day07]\npart1 = 241344943\npart2 = \n").err().unwrap();
+        assert!(error.to_string().starts_with("line 3: "), "{}", error);
+        assert!(error.to_string().ends_with(r#" in "part2 = ""#), "{}", error);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::grid::Grid;
+use crate::vec2::{Direction, Vec2};
+
+/// Cells of a grid crossed by beams, with the headings they entered each cell in.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Beams {
+    headings: Grid<u8>,
+}
+
+impl Beams {
+    pub fn is_lit(&self, position: (usize, usize)) -> bool {
+        self.headings[position] != 0
+    }
+
+    /// Headings beams entered the cell at `position` in, clockwise from upwards.
+    pub fn headings(&self, position: (usize, usize)) -> impl Iterator<Item=Direction> + '_ {
+        Direction::ALL.into_iter().filter(move |&heading| self.headings[position] & bit(heading) != 0)
+    }
+
+    /// Number of cells crossed by at least one beam.
+    pub fn lit_count(&self) -> usize {
+        self.headings.cells().filter(|&(_, &headings)| headings != 0).count()
+    }
+}
+
+fn bit(heading: Direction) -> u8 {
+    1 << heading as u8
+}
+
+/// Traces a beam entering the cell at `start` heading `heading`. In each cell it enters, `redirect` tells from the cell
+/// and the heading of the beam which headings it leaves in: none when the cell blocks it, a single one when the cell
+/// lets it through or turns it, several when the cell splits it. Beams leaving the grid are lost, and beams entering a
+/// cell in a heading a beam already entered it in are not traced any further, so that tracing ends on loops.
+pub fn trace_beams<T, I>(grid: &Grid<T>, start: Vec2, heading: Direction, redirect: impl Fn(&T, Direction) -> I) -> Beams
+where I: IntoIterator<Item=Direction> {
+    let mut headings = grid.map(|_| 0_u8);
+    let mut beams = vec![(start, heading)];
+    while let Some((position, heading)) = beams.pop() {
+        let Some(cell) = grid.get(position.x, position.y) else { continue };
+        let entered = &mut headings[(position.x as usize, position.y as usize)];
+        if *entered & bit(heading) != 0 {
+            continue;
+        }
+        *entered |= bit(heading);
+        beams.extend(redirect(cell, heading).into_iter().map(|next| (position + next.offset(), next)));
+    }
+    Beams { headings }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::vec2::Direction::{Down, Right, Up};
+
+    use super::*;
+
+    /// Cells blocking beams (`#`), turning them right (`R`), splitting them to their left and right (`+`) or letting
+    /// them through.
+    fn grid(lines: &[&str]) -> Grid<char> {
+        Grid::of_chars(&lines.iter().map(|line| line.to_string()).collect::<Vec<_>>()).unwrap()
+    }
+
+    fn redirect(cell: &char, heading: Direction) -> Vec<Direction> {
+        match cell {
+            '#' => vec![],
+            'R' => vec![heading.turn_right()],
+            '+' => vec![heading.turn_left(), heading.turn_right()],
+            _ => vec![heading],
+        }
+    }
+
+    #[test]
+    fn lights_cells_crossed_by_turning_beams() {
+        let beams = trace_beams(&grid(&["..R", "...", "R.R"]), Vec2::ZERO, Right, redirect);
+        assert_eq!(beams.lit_count(), 8);
+        assert!(beams.is_lit((0, 1)) && !beams.is_lit((1, 1)));
+        assert_eq!(beams.headings((0, 0)).collect::<Vec<_>>(), vec![Up, Right]);
+        assert_eq!(beams.headings((2, 2)).collect::<Vec<_>>(), vec![Down]);
+    }
+
+    #[test]
+    fn follows_every_beam_of_a_split() {
+        let beams = trace_beams(&grid(&[".....", "..+..", "....."]), Vec2::new(0, 1), Right, redirect);
+        assert_eq!(beams.lit_count(), 5);
+        assert!(beams.is_lit((2, 0)) && beams.is_lit((2, 2)) && !beams.is_lit((3, 1)));
+    }
+
+    #[test]
+    fn ends_on_loops() {
+        let beams = trace_beams(&grid(&["RR", "RR"]), Vec2::new(1, 0), Right, redirect);
+        assert_eq!(beams.lit_count(), 4);
+        assert_eq!(beams.headings((0, 0)).collect::<Vec<_>>(), vec![Up]);
+        assert_eq!(beams.headings((1, 0)).collect::<Vec<_>>(), vec![Right]);
+    }
+
+    #[test]
+    fn stops_at_blocking_cells() {
+        let beams = trace_beams(&grid(&["..#.."]), Vec2::ZERO, Right, redirect);
+        assert_eq!(beams.lit_count(), 3);
+        assert!(!beams.is_lit((3, 0)));
+    }
+}
@@ -0,0 +1,109 @@
+use std::time::Duration;
+
+use crate::error::Result;
+use crate::input::daily_input;
+use crate::puzzle::{Part, Solution, Solver};
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Timings {
+    samples: Vec<Duration>,
+}
+
+impl Timings {
+    pub fn new(mut samples: Vec<Duration>) -> Timings {
+        samples.sort();
+        Timings { samples }
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples[0]
+    }
+
+    pub fn median(&self) -> Duration {
+        self.samples[self.samples.len() / 2]
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples[self.samples.len() - 1]
+    }
+
+    pub fn std_dev(&self) -> Duration {
+        let count = self.samples.len() as f64;
+        let mean = self.samples.iter().map(Duration::as_secs_f64).sum::<f64>() / count;
+        let variance = self.samples.iter().map(|sample| (sample.as_secs_f64() - mean).powi(2)).sum::<f64>() / count;
+        Duration::from_secs_f64(variance.sqrt())
+    }
+
+    pub fn count(&self) -> usize {
+        self.samples.len()
+    }
+}
+
+pub struct Benchmark {
+    pub day: u32,
+    pub parse: Timings,
+    pub part_one: Timings,
+    pub part_two: Timings,
+}
+
+fn sample(solver: &Solver, part: Part, input: &[String], warmup: usize, iterations: usize) -> Result<Vec<Solution>> {
+    for _ in 0..warmup {
+        (solver.solve)(part, input.to_vec())?;
+    }
+    (0..iterations).map(|_| (solver.solve)(part, input.to_vec())).collect()
+}
+
+pub fn bench(solver: &Solver, warmup: usize, iterations: usize) -> Result<Benchmark> {
+    let input = daily_input(solver.year, solver.day)?;
+    let part_one = sample(solver, Part::One, &input, warmup, iterations)?;
+    let part_two = sample(solver, Part::Two, &input, warmup, iterations)?;
+    Ok(Benchmark {
+        day: solver.day,
+        parse: Timings::new(part_one.iter().chain(&part_two).map(|s| s.parse_time).collect()),
+        part_one: Timings::new(part_one.iter().map(|s| s.solve_time).collect()),
+        part_two: Timings::new(part_two.iter().map(|s| s.solve_time).collect()),
+    })
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    match nanos {
+        0..=999 => format!("{}ns", nanos),
+        1_000..=999_999 => format!("{:.1}µs", nanos as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.1}ms", nanos as f64 / 1e6),
+        _ => format!("{:.2}s", nanos as f64 / 1e9),
+    }
+}
+
+pub fn format_table(benchmarks: &[Benchmark]) -> String {
+    let mut table = format!("{:<5} {:<6} {:>10} {:>10} {:>10} {:>10}\n", "day", "step", "min", "median", "std dev", "max");
+    for benchmark in benchmarks {
+        for (step, timings) in [("parse", &benchmark.parse), ("part 1", &benchmark.part_one), ("part 2", &benchmark.part_two)] {
+            table += &format!("{:<5} {:<6} {:>10} {:>10} {:>10} {:>10}\n", format!("{:0>2}", benchmark.day), step,
+                              format_duration(timings.min()), format_duration(timings.median()), format_duration(timings.std_dev()),
+                              format_duration(timings.max()));
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarizes_timings() {
+        let timings = Timings::new(vec![Duration::from_millis(3), Duration::from_millis(1), Duration::from_millis(2)]);
+        assert_eq!(timings.min(), Duration::from_millis(1));
+        assert_eq!(timings.median(), Duration::from_millis(2));
+        assert_eq!(timings.max(), Duration::from_millis(3));
+        assert_eq!(timings.std_dev().as_micros(), 816);
+    }
+
+    #[test]
+    fn formats_durations_in_readable_units() {
+        assert_eq!(format_duration(Duration::from_nanos(420)), "420ns");
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.5ms");
+        assert_eq!(format_duration(Duration::from_millis(2345)), "2.35s");
+    }
+}
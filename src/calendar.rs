@@ -0,0 +1,101 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+/// Puzzles unlock at midnight EST, which is 5 hours behind UTC.
+const UNLOCK_OFFSET: u64 = 5 * 60 * 60;
+const LAST_DAY: u32 = 25;
+
+fn days_from_civil(year: u32, month: u32, day: u32) -> u64 {
+    let (year, month) = if month <= 2 { (year as u64 - 1, month as u64 + 9) } else { (year as u64, month as u64 - 3) };
+    let era = year / 400;
+    let year_of_era = year % 400;
+    let day_of_year = (153 * month + 2) / 5 + day as u64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+fn civil_from_days(days: u64) -> (u32, u32, u32) {
+    let days = days + 719468;
+    let era = days / 146097;
+    let day_of_era = days % 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month + 2) / 5 + 1) as u32;
+    let month = if month < 10 { month + 3 } else { month - 9 } as u32;
+    let year = (year_of_era + era * 400) as u32 + u32::from(month <= 2);
+    (year, month, day)
+}
+
+fn seconds_since_epoch(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs())
+}
+
+pub fn unlock_time(year: u32, day: u32) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(days_from_civil(year, 12, day) * SECONDS_PER_DAY + UNLOCK_OFFSET)
+}
+
+/// Puzzle of the current day in EST, if `now` falls during an Advent of Code.
+pub fn puzzle_of_the_day(now: SystemTime) -> Option<(u32, u32)> {
+    let (year, month, day) = civil_from_days(seconds_since_epoch(now).saturating_sub(UNLOCK_OFFSET) / SECONDS_PER_DAY);
+    (month == 12 && day <= LAST_DAY).then_some((year, day))
+}
+
+/// Next puzzle to unlock after `now`.
+pub fn next_puzzle(now: SystemTime) -> (u32, u32) {
+    let (year, _, _) = civil_from_days(seconds_since_epoch(now).saturating_sub(UNLOCK_OFFSET) / SECONDS_PER_DAY);
+    (year..=year + 1)
+        .flat_map(|year| (1..=LAST_DAY).map(move |day| (year, day)))
+        .find(|&(year, day)| unlock_time(year, day) > now)
+        .expect("a puzzle unlocks within a year")
+}
+
+pub fn format_countdown(remaining: Duration) -> String {
+    let seconds = remaining.as_secs();
+    match (seconds / SECONDS_PER_DAY, seconds % SECONDS_PER_DAY / 3600, seconds % 3600 / 60, seconds % 60) {
+        (0, 0, minutes, seconds) => format!("{}m{:0>2}s", minutes, seconds),
+        (0, hours, minutes, seconds) => format!("{}h{:0>2}m{:0>2}s", hours, minutes, seconds),
+        (days, hours, minutes, _) => format!("{}d{:0>2}h{:0>2}m", days, hours, minutes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(year: u32, month: u32, day: u32, hour: u64, minute: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(days_from_civil(year, month, day) * SECONDS_PER_DAY + hour * 3600 + minute * 60)
+    }
+
+    #[test]
+    fn converts_dates_to_days_since_epoch_and_back() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2023, 12, 1), 19692);
+        assert_eq!(civil_from_days(19692), (2023, 12, 1));
+        assert_eq!(civil_from_days(days_from_civil(2024, 2, 29)), (2024, 2, 29));
+    }
+
+    #[test]
+    fn finds_puzzle_of_the_day_in_eastern_time() {
+        assert_eq!(puzzle_of_the_day(utc(2023, 12, 7, 4, 59)), Some((2023, 6)));
+        assert_eq!(puzzle_of_the_day(utc(2023, 12, 7, 5, 0)), Some((2023, 7)));
+        assert_eq!(puzzle_of_the_day(utc(2023, 12, 1, 4, 0)), None);
+        assert_eq!(puzzle_of_the_day(utc(2023, 12, 26, 12, 0)), None);
+    }
+
+    #[test]
+    fn finds_next_puzzle_to_unlock() {
+        assert_eq!(next_puzzle(utc(2023, 11, 30, 20, 0)), (2023, 1));
+        assert_eq!(next_puzzle(utc(2023, 12, 7, 4, 59)), (2023, 7));
+        assert_eq!(next_puzzle(utc(2023, 12, 7, 5, 0)), (2023, 8));
+        assert_eq!(next_puzzle(utc(2023, 12, 25, 6, 0)), (2024, 1));
+        assert_eq!(unlock_time(2023, 7), utc(2023, 12, 7, 5, 0));
+    }
+
+    #[test]
+    fn formats_time_left_before_unlock() {
+        assert_eq!(format_countdown(Duration::from_secs(95)), "1m35s");
+        assert_eq!(format_countdown(Duration::from_secs(3 * 3600 + 65)), "3h01m05s");
+        assert_eq!(format_countdown(Duration::from_secs(2 * SECONDS_PER_DAY + 7200)), "2d02h00m");
+    }
+}
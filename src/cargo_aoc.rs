@@ -0,0 +1,52 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::error::AocError;
+use crate::puzzle::{split_lines, Puzzle};
+use crate::validate::validate;
+
+pub fn generate<P: Puzzle>(input: &str) -> Result<P::Model, AocError> {
+    let input = split_lines(input);
+    validate(&input, P::RULES)?;
+    P::parse(input)
+}
+
+macro_rules! adapt {
+    ($puzzle:ty, $day:ident, $generator:ident, $part_one:ident, $part_two:ident) => {
+        #[aoc_generator($day)]
+        fn $generator(input: &str) -> Result<<$puzzle as Puzzle>::Model, AocError> {
+            generate::<$puzzle>(input)
+        }
+
+        #[aoc($day, part1)]
+        fn $part_one(model: &<$puzzle as Puzzle>::Model) -> Result<String, AocError> {
+            Ok(<$puzzle as Puzzle>::part_one(model)?.to_string())
+        }
+
+        #[aoc($day, part2)]
+        fn $part_two(model: &<$puzzle as Puzzle>::Model) -> Result<String, AocError> {
+            Ok(<$puzzle as Puzzle>::part_two(model)?.to_string())
+        }
+    };
+}
+
+adapt!(crate::day01::Day01, day1, generate_day01, day01_part_one, day01_part_two);
+adapt!(crate::day02::Day02, day2, generate_day02, day02_part_one, day02_part_two);
+adapt!(crate::day03::Day03, day3, generate_day03, day03_part_one, day03_part_two);
+adapt!(crate::day04::Day04, day4, generate_day04, day04_part_one, day04_part_two);
+adapt!(crate::day05::Day05, day5, generate_day05, day05_part_one, day05_part_two);
+adapt!(crate::day06::Day06, day6, generate_day06, day06_part_one, day06_part_two);
+adapt!(crate::day07::Day07, day7, generate_day07, day07_part_one, day07_part_two);
+adapt!(crate::day08::Day08, day8, generate_day08, day08_part_one, day08_part_two);
+
+#[cfg(test)]
+mod tests {
+    use aoc_runner::ArcStr;
+
+    use crate::{Day7Part2, Factory};
+
+    #[test]
+    fn runs_day_as_cargo_aoc_would() {
+        let runner = Factory::day7_part2(ArcStr::from(include_str!("examples/2023/day07.txt"))).unwrap();
+        assert_eq!(runner.try_run().unwrap().to_string(), "5905");
+    }
+}
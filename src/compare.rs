@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use crate::bench::format_duration;
+use crate::puzzle::Part;
+
+pub struct Measurement {
+    pub name: &'static str,
+    pub answer: String,
+    pub time: Duration,
+}
+
+impl Measurement {
+    pub fn of(name: &'static str, (answer, time): (String, Duration)) -> Measurement {
+        Measurement { name, answer, time }
+    }
+}
+
+pub struct Comparison {
+    pub day: u32,
+    pub part: Part,
+    pub measurements: Vec<Measurement>,
+}
+
+impl Comparison {
+    pub fn agrees(&self) -> bool {
+        self.measurements.windows(2).all(|pair| pair[0].answer == pair[1].answer)
+    }
+
+    pub fn to_text(&self) -> String {
+        let reference = self.measurements[0].time.as_secs_f64();
+        let mut text = format!("Day {:0>2} part {}{}\n", self.day, self.part, if self.agrees() { "" } else { ": DISAGREE" });
+        for measurement in &self.measurements {
+            let speedup = reference / measurement.time.as_secs_f64().max(f64::MIN_POSITIVE);
+            text += &format!("  {:<16} {:>16} {:>10} {:>10.1}x\n", measurement.name, measurement.answer,
+                             format_duration(measurement.time), speedup);
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(name: &'static str, answer: &str, millis: u64) -> Measurement {
+        Measurement { name, answer: answer.to_owned(), time: Duration::from_millis(millis) }
+    }
+
+    #[test]
+    fn reports_speed_relative_to_default_implementation() {
+        let comparison = Comparison { day: 6, part: Part::Two, measurements: vec![measurement("default", "71503", 40), measurement("quadratic", "71503", 2)] };
+        assert!(comparison.agrees());
+        assert_eq!(comparison.to_text(), "\
+Day 06 part 2
+  default                     71503     40.0ms        1.0x
+  quadratic                   71503      2.0ms       20.0x
+");
+    }
+
+    #[test]
+    fn flags_variants_giving_another_answer() {
+        let comparison = Comparison { day: 5, part: Part::Two, measurements: vec![measurement("default", "46", 40), measurement("range mapping", "45", 1)] };
+        assert!(!comparison.agrees());
+        assert!(comparison.to_text().starts_with("Day 05 part 2: DISAGREE\n"));
+    }
+}
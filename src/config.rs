@@ -0,0 +1,87 @@
+use std::env;
+use std::fs::read_to_string;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use crate::puzzle::DEFAULT_YEAR;
+
+const CONFIG: &str = "aoc.toml";
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct Settings {
+    year: Option<u32>,
+    session_file: Option<String>,
+    inputs: Option<String>,
+    examples: Option<String>,
+    threads: Option<usize>,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Config {
+    pub year: u32,
+    pub session_file: Option<String>,
+    pub inputs: String,
+    pub examples: String,
+    pub threads: Option<usize>,
+}
+
+impl Config {
+    pub fn load() -> Result<Config, String> {
+        Config::merge(&read_to_string(CONFIG).unwrap_or_default(), |name| env::var(name).ok())
+    }
+
+    fn merge(settings: &str, var: impl Fn(&str) -> Option<String>) -> Result<Config, String> {
+        let settings: Settings = toml::from_str(settings).map_err(|e| format!("invalid {}: {}", CONFIG, e))?;
+        Ok(Config {
+            year: var("AOC_YEAR").and_then(|year| year.parse().ok()).or(settings.year).unwrap_or(DEFAULT_YEAR),
+            session_file: var("AOC_SESSION_FILE").or(settings.session_file),
+            inputs: var("AOC_INPUTS").or(settings.inputs).unwrap_or_else(|| "src/inputs".to_owned()),
+            examples: var("AOC_EXAMPLES").or(settings.examples).unwrap_or_else(|| "src/examples".to_owned()),
+            threads: var("AOC_THREADS").and_then(|threads| threads.parse().ok()).or(settings.threads),
+        })
+    }
+}
+
+static ACTIVE: OnceLock<Config> = OnceLock::new();
+
+pub fn set_config(config: Config) {
+    let _ = ACTIVE.set(config);
+}
+
+/// Configuration set by the command line, or else loaded on first use, when a malformed aoc.toml has no better place
+/// to be reported than a panic.
+pub fn config() -> &'static Config {
+    ACTIVE.get_or_init(|| Config::load().unwrap_or_else(|error| panic!("{}", error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_to_source_tree_layout() {
+        assert_eq!(Config::merge("", |_| None).unwrap(), Config {
+            year: DEFAULT_YEAR,
+            session_file: None,
+            inputs: "src/inputs".to_owned(),
+            examples: "src/examples".to_owned(),
+            threads: None,
+        });
+    }
+
+    #[test]
+    fn overrides_settings_with_environment() {
+        let settings = "year = 2022\ninputs = \"puzzles\"\nthreads = 4\n";
+        let config = Config::merge(settings, |name| (name == "AOC_THREADS").then(|| "2".to_owned())).unwrap();
+        assert_eq!((config.year, config.inputs.as_str(), config.threads), (2022, "puzzles", Some(2)));
+    }
+
+    #[test]
+    fn reports_malformed_settings() {
+        let error = Config::merge("year = \"soon\"\n", |_| None).unwrap_err();
+        assert!(error.starts_with("invalid aoc.toml: "), "{}", error);
+        assert!(Config::merge("colour = true\n", |_| None).is_err());
+    }
+}
@@ -0,0 +1,84 @@
+use crate::graph::dijkstra;
+use crate::grid::Grid;
+use crate::vec2::{Direction, Vec2};
+
+/// How many cells in a row a walker must, then may, move straight before turning left or right. It never turns back.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct RunLimits {
+    pub min: usize,
+    pub max: usize,
+}
+
+/// A walker on a grid, with how many cells in a row it moved in its direction so far.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash)]
+struct Walker {
+    position: Vec2,
+    heading: Option<Direction>,
+    run: usize,
+}
+
+impl Walker {
+    fn moves(self, limits: RunLimits) -> impl Iterator<Item=(Direction, usize)> {
+        let moves = match self.heading {
+            None => Direction::ALL.map(|direction| Some((direction, 1))),
+            Some(heading) if self.run < limits.min => [Some((heading, self.run + 1)), None, None, None],
+            Some(heading) => [
+                (self.run < limits.max).then_some((heading, self.run + 1)),
+                Some((heading.turn_left(), 1)),
+                Some((heading.turn_right(), 1)),
+                None,
+            ],
+        };
+        moves.into_iter().flatten()
+    }
+}
+
+/// Cheapest path from `start` to `goal` on `grid`, entering each cell costing `cost` of it, for a walker bound by
+/// `limits` which must also have moved straight at least the minimum when it stops. Returns the cells walked through,
+/// `start` first, with the total cost.
+pub fn cheapest_constrained_path<T>(grid: &Grid<T>, start: Vec2, goal: Vec2, limits: RunLimits, cost: impl Fn(&T) -> usize) -> Option<(Vec<Vec2>, usize)> {
+    let successors = |walker: &Walker| {
+        walker.moves(limits)
+            .filter_map(|(heading, run)| {
+                let position = walker.position + heading.offset();
+                let step = cost(grid.get(position.x, position.y)?);
+                Some((Walker { position, heading: Some(heading), run }, step))
+            })
+            .collect::<Vec<_>>()
+    };
+    let is_goal = |walker: &Walker| walker.position == goal && walker.run >= limits.min;
+    let (walkers, total) = dijkstra(Walker { position: start, heading: None, run: 0 }, successors, is_goal)?;
+    Some((walkers.into_iter().map(|walker| walker.position).collect(), total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn city(blocks: &[&str]) -> Grid<usize> {
+        let lines: Vec<String> = blocks.iter().map(|line| line.to_string()).collect();
+        Grid::parse_with(&lines, |c| c.to_digit(10).map(|digit| digit as usize).ok_or(format!("{} is not a digit", c))).unwrap()
+    }
+
+    fn cheapest(city: &Grid<usize>, min: usize, max: usize) -> Option<usize> {
+        let goal = Vec2::new(city.width() as isize - 1, city.height() as isize - 1);
+        cheapest_constrained_path(city, Vec2::ZERO, goal, RunLimits { min, max }, |&loss| loss).map(|(_, total)| total)
+    }
+
+    #[test]
+    fn limits_runs_in_a_straight_line() {
+        let city = city(&["2413432311323", "3215453535623", "3255245654254", "3446585845452", "4546657867536",
+            "1438598798454", "4457876987766", "3637877979653", "4654967986887", "4564679986453", "1224686865563",
+            "2546548887735", "4322674655533"]);
+        assert_eq!(cheapest(&city, 0, 3), Some(102));
+        assert_eq!(cheapest(&city, 4, 10), Some(94));
+    }
+
+    #[test]
+    fn runs_at_least_the_minimum_before_stopping() {
+        let city = city(&["111111111111", "999999999991", "999999999991", "999999999991", "999999999991"]);
+        assert_eq!(cheapest(&city, 4, 10), Some(71));
+        let (path, _) = cheapest_constrained_path(&city, Vec2::ZERO, Vec2::new(2, 0), RunLimits { min: 0, max: 3 }, |&loss| loss).unwrap();
+        assert_eq!(path, vec![Vec2::new(0, 0), Vec2::new(1, 0), Vec2::new(2, 0)]);
+    }
+}
@@ -0,0 +1,130 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::{Add, Sub};
+
+/// Number of occurrences of each distinct item, a multiset.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Counter<T: Eq + Hash> {
+    counts: HashMap<T, usize>,
+}
+
+impl<T: Eq + Hash> Counter<T> {
+    pub fn new() -> Counter<T> {
+        Counter { counts: HashMap::new() }
+    }
+
+    pub fn add(&mut self, item: T) {
+        self.add_n(item, 1);
+    }
+
+    pub fn add_n(&mut self, item: T, count: usize) {
+        if count > 0 {
+            *self.counts.entry(item).or_default() += count;
+        }
+    }
+
+    /// Number of occurrences of `item`, 0 if it never occurred.
+    pub fn get(&self, item: &T) -> usize {
+        self.counts.get(item).copied().unwrap_or(0)
+    }
+
+    /// Number of distinct items.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Number of occurrences of all items.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Distinct items with their number of occurrences, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item=(&T, usize)> {
+        self.counts.iter().map(|(item, &count)| (item, count))
+    }
+
+    /// Numbers of occurrences of the distinct items, largest first, such as `[3, 2]` for a full house.
+    pub fn counts_descending(&self) -> Vec<usize> {
+        let mut counts: Vec<usize> = self.counts.values().copied().collect();
+        counts.sort_unstable_by_key(|&count| Reverse(count));
+        counts
+    }
+
+    /// Distinct items with their number of occurrences, most common first, ties in the order of the items.
+    pub fn most_common(&self) -> Vec<(&T, usize)> where T: Ord {
+        let mut items: Vec<(&T, usize)> = self.iter().collect();
+        items.sort_unstable_by_key(|&(item, count)| (Reverse(count), item));
+        items
+    }
+}
+
+impl<T: Eq + Hash> Default for Counter<T> {
+    fn default() -> Counter<T> {
+        Counter::new()
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item=T>>(items: I) -> Counter<T> {
+        let mut counter = Counter::new();
+        counter.extend(items);
+        counter
+    }
+}
+
+impl<T: Eq + Hash> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item=T>>(&mut self, items: I) {
+        items.into_iter().for_each(|item| self.add(item));
+    }
+}
+
+impl<T: Eq + Hash> Add for Counter<T> {
+    type Output = Counter<T>;
+
+    /// Occurrences of both counters summed.
+    fn add(mut self, other: Counter<T>) -> Counter<T> {
+        other.counts.into_iter().for_each(|(item, count)| self.add_n(item, count));
+        self
+    }
+}
+
+impl<T: Eq + Hash> Sub for Counter<T> {
+    type Output = Counter<T>;
+
+    /// Occurrences left once those of `other` are taken away, items running out of occurrences being dropped.
+    fn sub(mut self, other: Counter<T>) -> Counter<T> {
+        for (item, count) in other.counts {
+            if let Some(left) = self.counts.get_mut(&item) {
+                *left = left.saturating_sub(count);
+            }
+        }
+        self.counts.retain(|_, &mut count| count > 0);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_occurrences() {
+        let letters: Counter<char> = "QQQJA".chars().collect();
+        assert_eq!((letters.get(&'Q'), letters.get(&'K')), (3, 0));
+        assert_eq!((letters.len(), letters.total()), (3, 5));
+        assert_eq!(letters.counts_descending(), vec![3, 1, 1]);
+        assert_eq!(letters.most_common(), vec![(&'Q', 3), (&'A', 1), (&'J', 1)]);
+    }
+
+    #[test]
+    fn adds_and_subtracts_counters() {
+        let counter = |s: &str| s.chars().collect::<Counter<char>>();
+        assert_eq!(counter("aab") + counter("bc"), counter("aabbc"));
+        assert_eq!(counter("aabbc") - counter("abbbd"), counter("ac"));
+    }
+}
@@ -0,0 +1,58 @@
+use std::env;
+use std::io;
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use pbkdf2::pbkdf2_hmac;
+use sha2::Sha256;
+
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+const ROUNDS: u32 = 100_000;
+
+pub fn passphrase() -> Option<String> {
+    env::var("AOC_INPUT_KEY").ok().filter(|key| !key.is_empty())
+}
+
+fn cipher(passphrase: &str, salt: &[u8]) -> Aes256Gcm {
+    let mut key = Key::<Aes256Gcm>::default();
+    pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, ROUNDS, &mut key);
+    Aes256Gcm::new(&key)
+}
+
+pub fn encrypt(plain: &str, passphrase: &str) -> Vec<u8> {
+    let mut salt = [0; SALT_LENGTH];
+    OsRng.fill_bytes(&mut salt);
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let encrypted = cipher(passphrase, &salt).encrypt(&nonce, plain.as_bytes()).expect("plain text fits in memory");
+    [salt.as_slice(), nonce.as_slice(), &encrypted].concat()
+}
+
+pub fn decrypt(data: &[u8], passphrase: &str) -> io::Result<String> {
+    let invalid = |reason: &str| io::Error::new(io::ErrorKind::InvalidData, reason.to_owned());
+    if data.len() < SALT_LENGTH + NONCE_LENGTH {
+        return Err(invalid("truncated encrypted input"));
+    }
+    let (salt, rest) = data.split_at(SALT_LENGTH);
+    let (nonce, encrypted) = rest.split_at(NONCE_LENGTH);
+    let plain = cipher(passphrase, salt).decrypt(Nonce::from_slice(nonce), encrypted).map_err(|_| invalid("wrong key or corrupted encrypted input"))?;
+    String::from_utf8(plain).map_err(|_| invalid("encrypted input is not text"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decrypts_what_it_encrypted() {
+        let encrypted = encrypt("Time:      7  15   30\nDistance:  9  40  200\n", "secret");
+        assert_eq!(decrypt(&encrypted, "secret").unwrap(), "Time:      7  15   30\nDistance:  9  40  200\n");
+    }
+
+    #[test]
+    fn rejects_wrong_key() {
+        let encrypted = encrypt("467..114..", "secret");
+        assert_eq!(decrypt(&encrypted, "guess").unwrap_err().to_string(), "wrong key or corrupted encrypted input");
+    }
+}
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// States repeated by iterating a function: after `prefix` steps, every `length` steps bring back the same state.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct Cycle {
+    pub prefix: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Earliest step reaching the same state as step `n`.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.prefix { n } else { self.prefix + (n - self.prefix) % self.length }
+    }
+}
+
+/// Cycle reached by iterating `step` from `initial`, remembering every state met by its hash, along with those states
+/// in order until the cycle closes.
+pub fn find_cycle<S: Eq + Hash + Clone>(initial: S, mut step: impl FnMut(&S) -> S) -> (Cycle, Vec<S>) {
+    let mut seen = HashMap::from([(initial.clone(), 0)]);
+    let mut states = vec![initial];
+    loop {
+        let next = step(states.last().unwrap());
+        if let Some(&prefix) = seen.get(&next) {
+            return (Cycle { prefix, length: states.len() - prefix }, states);
+        }
+        seen.insert(next.clone(), states.len());
+        states.push(next);
+    }
+}
+
+/// Cycle reached by iterating `step` from `initial` with Brent's algorithm, which only keeps a couple of states at a
+/// time but iterates `step` a few times more than [`find_cycle`].
+pub fn brent<S: Eq + Clone>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let (mut power, mut length) = (1, 1);
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+    let (mut tortoise, mut hare) = (initial.clone(), initial);
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+    Cycle { prefix, length }
+}
+
+/// State after iterating `step` `n` times from `initial`, skipping over whole cycles.
+pub fn nth_state<S: Eq + Hash + Clone>(initial: S, step: impl FnMut(&S) -> S, n: usize) -> S {
+    let (cycle, mut states) = find_cycle(initial, step);
+    states.swap_remove(cycle.equivalent_step(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(n: &u64) -> u64 {
+        (n * n + 1) % 255
+    }
+
+    #[test]
+    fn finds_prefix_and_length_of_cycle() {
+        let (cycle, states) = find_cycle(3, step);
+        assert_eq!(states, [3, 10, 101, 2, 5, 26, 167, 95]);
+        assert_eq!(cycle, Cycle { prefix: 2, length: 6 });
+        assert_eq!(brent(3, step), cycle);
+    }
+
+    #[test]
+    fn skips_whole_cycles() {
+        assert_eq!(Cycle { prefix: 2, length: 6 }.equivalent_step(1_000_000_000), 4);
+        let mut state = 3;
+        (0..1000).for_each(|_| state = step(&state));
+        assert_eq!(nth_state(3, step, 1000), state);
+        assert_eq!(nth_state(3, step, 1), 10);
+    }
+}
@@ -0,0 +1,162 @@
+use std::io;
+use std::sync::mpsc;
+use std::sync::mpsc::{Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::ExecutableCommand;
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::Constraint;
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Block, Borders, Row, Table, TableState};
+use ratatui::{Frame, Terminal};
+
+use crate::bench::format_duration;
+use crate::error::Result;
+use crate::input::InputSource;
+use crate::puzzle::{Part, Solver};
+use crate::results::{answer_of, runtime_of};
+use crate::runner::{run, PartRun};
+
+const REFRESH: Duration = Duration::from_millis(100);
+
+enum Status {
+    Queued,
+    Running,
+    Solved(Vec<PartRun>),
+    Failed(String),
+}
+
+enum Update {
+    Started(usize),
+    Finished(usize, Result<Vec<PartRun>>),
+}
+
+pub struct Dashboard {
+    solvers: Vec<Solver>,
+    statuses: Vec<Status>,
+    selected: TableState,
+}
+
+impl Dashboard {
+    pub fn new(solvers: Vec<Solver>) -> Dashboard {
+        let statuses = solvers.iter().map(|_| Status::Queued).collect();
+        Dashboard { solvers, statuses, selected: TableState::default().with_selected(Some(0)) }
+    }
+
+    fn cells(&self, index: usize) -> [String; 5] {
+        let day = format!("{:0>2}", self.solvers[index].day);
+        match &self.statuses[index] {
+            Status::Queued => [day, String::new(), String::new(), String::new(), "queued".to_owned()],
+            Status::Running => [day, String::new(), String::new(), String::new(), "running".to_owned()],
+            Status::Solved(runs) => [day, answer_of(runs, Part::One), answer_of(runs, Part::Two),
+                                     format_duration(runtime_of(runs)), "done".to_owned()],
+            Status::Failed(error) => [day, String::new(), String::new(), String::new(), error.to_owned()],
+        }
+    }
+
+    fn update(&mut self, update: Update) {
+        match update {
+            Update::Started(index) => self.statuses[index] = Status::Running,
+            Update::Finished(index, Ok(runs)) => self.statuses[index] = Status::Solved(runs),
+            Update::Finished(index, Err(error)) => self.statuses[index] = Status::Failed(error.to_string()),
+        }
+    }
+
+    fn select(&mut self, offset: isize) {
+        let selected = self.selected.selected().unwrap_or(0).saturating_add_signed(offset);
+        self.selected.select(Some(selected.min(self.solvers.len().saturating_sub(1))));
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let rows: Vec<_> = (0..self.solvers.len()).map(|index| Row::new(self.cells(index))).collect();
+        let widths = [Constraint::Length(4), Constraint::Length(16), Constraint::Length(16), Constraint::Length(10), Constraint::Fill(1)];
+        let table = Table::new(rows, widths)
+            .header(Row::new(["Day", "Part 1", "Part 2", "Runtime", "Status"]).style(Style::new().add_modifier(Modifier::BOLD)))
+            .block(Block::default().borders(Borders::ALL).title(" Advent of Code — ↑↓ select, r re-run, q quit "))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(table, frame.size(), &mut self.selected);
+    }
+}
+
+fn solve_days(solvers: Vec<Solver>, budget: Duration, jobs: Receiver<usize>, updates: Sender<Update>) {
+    for index in jobs {
+        let solver = &solvers[index];
+        let _ = updates.send(Update::Started(index));
+        let runs = InputSource::Daily.read(solver.year, solver.day).map(|input| run(solver, &input, budget));
+        let _ = updates.send(Update::Finished(index, runs));
+    }
+}
+
+fn event_loop(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, dashboard: &mut Dashboard, budget: Duration) -> io::Result<()> {
+    let (jobs, queue) = mpsc::channel();
+    let (updates, progress) = mpsc::channel();
+    let solvers = dashboard.solvers.clone();
+    thread::spawn(move || solve_days(solvers, budget, queue, updates));
+    (0..dashboard.solvers.len()).for_each(|index| { let _ = jobs.send(index); });
+
+    loop {
+        while let Ok(update) = progress.try_recv() {
+            dashboard.update(update);
+        }
+        terminal.draw(|frame| dashboard.draw(frame))?;
+        if !event::poll(REFRESH)? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else { continue };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Up | KeyCode::Char('k') => dashboard.select(-1),
+            KeyCode::Down | KeyCode::Char('j') => dashboard.select(1),
+            KeyCode::Char('r') => if let Some(index) = dashboard.selected.selected() {
+                dashboard.statuses[index] = Status::Queued;
+                let _ = jobs.send(index);
+            },
+            _ => {}
+        }
+    }
+}
+
+pub fn dashboard(solvers: Vec<Solver>, budget: Duration) -> io::Result<()> {
+    let mut dashboard = Dashboard::new(solvers);
+    enable_raw_mode()?;
+    io::stdout().execute(EnterAlternateScreen)?;
+    let outcome = Terminal::new(CrosstermBackend::new(io::stdout()))
+        .and_then(|mut terminal| event_loop(&mut terminal, &mut dashboard, budget));
+    io::stdout().execute(LeaveAlternateScreen)?;
+    disable_raw_mode()?;
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AocError;
+    use crate::puzzle::{Solution, DEFAULT_YEAR};
+
+    use super::*;
+
+    fn parse_nothing(_: Vec<String>) -> Result<()> {
+        Ok(())
+    }
+
+    fn solve_nothing(_: Part, _: Vec<String>) -> Result<Solution> {
+        Err(AocError::unsolvable("nothing to solve"))
+    }
+
+    #[test]
+    fn shows_days_as_they_are_solved() {
+        let solver = Solver::new(DEFAULT_YEAR, 7, parse_nothing, solve_nothing);
+        let mut dashboard = Dashboard::new(vec![solver]);
+        assert_eq!(dashboard.cells(0)[4], "queued");
+        dashboard.update(Update::Started(0));
+        assert_eq!(dashboard.cells(0)[4], "running");
+        let solution = Solution { answer: "6440".to_owned(), parse_time: Duration::ZERO, solve_time: Duration::from_millis(2) };
+        dashboard.update(Update::Finished(0, Ok(vec![PartRun { day: 7, part: Part::One, solution: Ok(solution), allocations: None, timings: None }])));
+        assert_eq!(dashboard.cells(0), ["07", "6440", "", "2.0ms", "done"].map(str::to_owned));
+    }
+}
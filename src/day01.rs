@@ -1,12 +1,21 @@
 use std::collections::HashMap;
-use crate::input::daily_input;
+use std::fmt::Display;
 
-fn calibration(input: &String) -> u32 {
+use aoc2023_macros::aoc;
+
+use crate::error::{parse_line, parse_lines, query_argument, AocError, Result};
+use crate::puzzle::Puzzle;
+use crate::validate::Rule;
+
+fn calibration(input: &str) -> std::result::Result<u32, String> {
     let digits = input.chars().filter_map(|c| c.to_digit(10)).collect::<Vec<u32>>();
-    return digits[0] * 10 + digits[digits.len() - 1];
+    match (digits.first(), digits.last()) {
+        (Some(first), Some(last)) => Ok(first * 10 + last),
+        _ => Err("no digit".to_owned()),
+    }
 }
 
-fn decode_spellings(input: &String) -> String {
+fn decode_spellings(input: &str) -> String {
     let digits_spelled_out = HashMap::from([
         ("one", "o1e"),
         ("two", "t2o"),
@@ -23,26 +32,80 @@ fn decode_spellings(input: &String) -> String {
         .fold(input.to_string(), |result, (spelling, code)| { result.replace(spelling, code) })
 }
 
-fn total_calibration(input: Vec<String>, account_for_spelled_outs: bool) -> u32 {
-    input.iter()
-        .map(|input| match account_for_spelled_outs { false => calibration(input), true => calibration(&decode_spellings(input)) })
-        .sum()
+fn calibrate(line: &str, account_for_spelled_outs: bool) -> std::result::Result<u32, String> {
+    match account_for_spelled_outs {
+        false => calibration(line),
+        true => calibration(&decode_spellings(line)),
+    }
+}
+
+fn total_calibration(input: &[String], account_for_spelled_outs: bool) -> Result<u32> {
+    let calibrations = parse_lines(input, 0, |line| calibrate(line, account_for_spelled_outs))?;
+    Ok(calibrations.iter().sum())
+}
+
+fn line_calibration(input: &[String], line: usize, account_for_spelled_outs: bool) -> Result<u32> {
+    let index = line.checked_sub(1).ok_or_else(|| AocError::parse(line, "", "lines are numbered from 1"))?;
+    parse_line(input, index, |line| calibrate(line, account_for_spelled_outs))
+}
+
+pub struct Day01;
+
+#[aoc(day = 1)]
+impl Puzzle for Day01 {
+    const RULES: &'static [Rule] = &[Rule::MinLines(1), Rule::Charset("abcdefghijklmnopqrstuvwxyz0123456789"), Rule::NoTrailingBlankLines];
+
+    const QUERIES: &'static [&'static str] = &["calibration <line>", "decoded <line>"];
+
+    type Model = Vec<String>;
+
+    fn parse(input: Vec<String>) -> Result<Self::Model> {
+        Ok(input)
+    }
+
+    fn part_one(model: &Self::Model) -> Result<impl Display> {
+        total_calibration(model, false)
+    }
+
+    fn part_two(model: &Self::Model) -> Result<impl Display> {
+        total_calibration(model, true)
+    }
+
+    fn query(model: &Self::Model, query: &[&str]) -> Result<String> {
+        match query {
+            ["calibration", line] => Ok(line_calibration(model, query_argument(line)?, false)?.to_string()),
+            ["decoded", line] => Ok(line_calibration(model, query_argument(line)?, true)?.to_string()),
+            _ => Err(AocError::unknown_query(query)),
+        }
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
+    use crate::answers::expected_answer;
+    use crate::input::daily_input;
+    use crate::puzzle::Part;
+
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "personal-inputs"), ignore = "requires personal puzzle inputs")]
     fn part_one() {
-        let total = total_calibration(daily_input(1), false);
-        assert_eq!(total, 55447);
+        let total = total_calibration(&daily_input(2023, 1).unwrap(), false).unwrap();
+        assert_eq!(total.to_string(), expected_answer(2023, 1, Part::One));
     }
 
     #[test]
+    #[cfg_attr(not(feature = "personal-inputs"), ignore = "requires personal puzzle inputs")]
     fn part_two() {
-        let total = total_calibration(daily_input(1), true);
-        assert_eq!(total, 54706);
+        let total = total_calibration(&daily_input(2023, 1).unwrap(), true).unwrap();
+        assert_eq!(total.to_string(), expected_answer(2023, 1, Part::Two));
+    }
+
+    #[test]
+    fn reports_lines_without_digits() {
+        let input = vec!["1abc2".to_owned(), "abcdef".to_owned()];
+        assert_eq!(total_calibration(&input, false).unwrap_err().to_string(), r#"line 2: no digit in "abcdef""#);
     }
 }
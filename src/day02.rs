@@ -1,9 +1,13 @@
 use std::collections::HashSet;
+use std::fmt::Display;
 use std::vec;
 
+use aoc2023_macros::aoc;
 use regex::Regex;
+use tracing::debug;
 
-use crate::input::daily_input;
+use crate::error::{parse_lines, query_argument, AocError, Result};
+use crate::puzzle::Puzzle;
 
 type Color = String;
 
@@ -28,14 +32,15 @@ impl Hand {
         Hand { cubes: picks }
     }
 
-    fn parse(hand: &str) -> Hand {
+    fn parse(hand: &str) -> std::result::Result<Hand, String> {
         let re = Regex::new(r"(?<count>\d+) (?<color>(green|blue|red))").unwrap();
 
         let grabs = re.captures_iter(hand).map(|groups| {
-            Grab(groups["color"].to_owned(), groups["count"].parse().unwrap())
-        }).collect();
+            let count = groups["count"].parse().map_err(|_| format!("invalid count {}", &groups["count"]))?;
+            Ok(Grab(groups["color"].to_owned(), count))
+        }).collect::<std::result::Result<_, String>>()?;
 
-        Hand::new(grabs)
+        Ok(Hand::new(grabs))
     }
 
     fn colors(self: &Self) -> HashSet<&Color> {
@@ -64,16 +69,17 @@ impl Hand {
     }
 }
 
-struct Game {
+pub struct Game {
     id: u32,
     grabs: Vec<Hand>
 }
 
 impl Game {
-    fn parse(game: &str) -> Game {
-        let re = Regex::new(r"Game (?<id>\d+):").unwrap();
-        let id = re.captures(game).unwrap()["id"].parse().unwrap();
-        Game { id, grabs: game.split(";").map(Hand::parse).collect() }
+    fn parse(game: &str) -> std::result::Result<Game, String> {
+        let re = Regex::new(r"^Game (?<id>\d+):").unwrap();
+        let captures = re.captures(game).ok_or("expected a game id")?;
+        let id = captures["id"].parse().map_err(|_| format!("invalid game id {}", &captures["id"]))?;
+        Ok(Game { id, grabs: game.split(";").map(Hand::parse).collect::<std::result::Result<_, String>>()? })
     }
 
     fn is_possible_with_hand(self: &Self, hand: &Hand) -> bool {
@@ -85,28 +91,109 @@ impl Game {
     }
 }
 
-fn sum_possible_games(lines: Vec<String>) -> u32 {
-    let available_cubes = Hand::new(vec![Grab::new("green", 13), Grab::new("red", 12), Grab::new("blue", 14)]);
-    lines.iter().map(|game| Game::parse(game)).filter(|game| game.is_possible_with_hand(&available_cubes)).map(|game| game.id).sum()
+fn parse_games(lines: Vec<String>) -> Result<Vec<Game>> {
+    let games = parse_lines(&lines, 0, Game::parse)?;
+    debug!(games = games.len(), "parsed games");
+    Ok(games)
 }
 
-fn sum_power_of_minimal_sets(lines: Vec<String>) -> u32 {
-    lines.iter().map(|game| Game::parse(game)).map(|game| game.hand_required_to_play().power()).sum()
+fn available_cubes() -> Hand {
+    Hand::new(vec![Grab::new("green", 13), Grab::new("red", 12), Grab::new("blue", 14)])
+}
+
+fn sum_possible_games(games: &[Game]) -> u32 {
+    let available_cubes = available_cubes();
+    games.iter().filter(|game| game.is_possible_with_hand(&available_cubes)).map(|game| game.id).sum()
+}
+
+fn describe_game(games: &[Game], id: u32) -> Result<String> {
+    let game = games.iter().find(|game| game.id == id).ok_or_else(|| AocError::Query(format!("no game {}", id)))?;
+    let hand = game.hand_required_to_play();
+    let cubes = ["red", "green", "blue"].map(|color| format!("{} {}", hand.count(&color.to_owned()), color)).join(", ");
+    let verdict = if game.is_possible_with_hand(&available_cubes()) { "possible" } else { "impossible" };
+    Ok(format!("needs {}, power {}, {}", cubes, hand.power(), verdict))
+}
+
+fn sum_power_of_minimal_sets(games: &[Game]) -> u32 {
+    games.iter().map(|game| game.hand_required_to_play().power()).sum()
+}
+
+pub struct Day02;
+
+#[aoc(day = 2)]
+impl Puzzle for Day02 {
+    const QUERIES: &'static [&'static str] = &["game <id>"];
+
+    type Model = Vec<Game>;
+
+    fn parse(input: Vec<String>) -> Result<Self::Model> {
+        parse_games(input)
+    }
+
+    fn part_one(model: &Self::Model) -> Result<impl Display> {
+        Ok(sum_possible_games(model))
+    }
+
+    fn part_two(model: &Self::Model) -> Result<impl Display> {
+        Ok(sum_power_of_minimal_sets(model))
+    }
+
+    fn query(model: &Self::Model, query: &[&str]) -> Result<String> {
+        match query {
+            ["game", id] => describe_game(model, query_argument(id)?),
+            _ => Err(AocError::unknown_query(query)),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
+    use crate::answers::expected_answer;
+    use crate::input::daily_input;
+    use crate::puzzle::Part;
+
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "personal-inputs"), ignore = "requires personal puzzle inputs")]
     fn part_one() {
-        let sum = sum_possible_games(daily_input(2));
-        assert_eq!(sum, 2776);
+        let sum = sum_possible_games(&parse_games(daily_input(2023, 2).unwrap()).unwrap());
+        assert_eq!(sum.to_string(), expected_answer(2023, 2, Part::One));
     }
 
     #[test]
+    #[cfg_attr(not(feature = "personal-inputs"), ignore = "requires personal puzzle inputs")]
     fn part_two() {
-        let sum = sum_power_of_minimal_sets(daily_input(2));
-        assert_eq!(sum, 68638);
+        let sum = sum_power_of_minimal_sets(&parse_games(daily_input(2023, 2).unwrap()).unwrap());
+        assert_eq!(sum.to_string(), expected_answer(2023, 2, Part::Two));
+    }
+
+    #[test]
+    fn reports_games_without_id() {
+        let error = parse_games(vec!["Game 1: 3 blue".to_owned(), "3 blue, 4 red".to_owned()]).err().unwrap();
+        assert_eq!(error.to_string(), r#"line 2: expected a game id in "3 blue, 4 red""#);
+    }
+
+    fn grab() -> impl Strategy<Value=(u32, &'static str)> {
+        (1..20_u32, prop::sample::select(vec!["red", "green", "blue"]))
+    }
+
+    proptest! {
+        #[test]
+        fn parses_any_valid_game(id in 1..1000_u32, hands in prop::collection::vec(prop::collection::vec(grab(), 1..4), 1..6)) {
+            let hand = |grabs: &Vec<(u32, &str)>| grabs.iter().map(|(count, color)| format!("{} {}", count, color)).collect::<Vec<_>>().join(", ");
+            let line = format!("Game {}: {}", id, hands.iter().map(hand).collect::<Vec<_>>().join("; "));
+            let game = Game::parse(&line).unwrap();
+            prop_assert_eq!(game.id, id);
+            prop_assert_eq!(game.grabs.len(), hands.len());
+            for (parsed, grabs) in game.grabs.iter().zip(&hands) {
+                for color in ["red", "green", "blue"] {
+                    let expected: u32 = grabs.iter().filter(|(_, c)| *c == color).map(|(count, _)| count).sum();
+                    prop_assert_eq!(parsed.count(&color.to_owned()), expected);
+                }
+            }
+        }
     }
 }
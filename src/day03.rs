@@ -1,35 +1,27 @@
-use regex::Regex;
-
-use crate::input::daily_input;
-
-#[derive(PartialEq, Debug)]
-struct Pos {
-    x: isize,
-    y: isize,
-}
-
-impl Pos {
-    const AROUND: [(isize, isize); 8] = [
-        (-1, -1), (0, -1), (1, -1),
-        (-1, 0), (1, 0),
-        (-1, 1), (0, 1), (1, 1)
-    ];
-
-    fn vicinity(self: &Self) -> Vec<Pos> {
-        Pos::AROUND.iter()
-            .map(|(dx, dy)| Pos { x: self.x + dx, y: self.y + dy })
-            .collect()
-    }
-}
-
+use std::fmt::Display;
+
+use aoc2023_macros::aoc;
+use itertools::Itertools;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::error::{AocError, Result};
+use crate::grid::{FromChar, Grid};
+use crate::puzzle::Puzzle;
+use crate::render::{render_grid, Color, Style};
+use crate::svg::{Paint, Svg};
+use crate::validate::Rule;
+use crate::vec2::Vec2;
+
+#[derive(Serialize)]
 struct Region {
-    locations: Vec<Pos>,
+    locations: Vec<Vec2>,
     visual: String,
 }
 
 impl Region {
-    fn is_in_vicinity_of(self: &Self, pos: &Pos) -> bool {
-        pos.vicinity().iter().any(|p| self.locations.contains(p))
+    fn is_in_vicinity_of(self: &Self, pos: &Vec2) -> bool {
+        pos.surroundings().any(|p| self.locations.contains(&p))
     }
 
     fn is_adjacent_to(self: &Self, other: &Region) -> bool {
@@ -53,36 +45,73 @@ impl Region {
     }
 }
 
-struct Schematics {
-    width: usize,
-    visual: String,
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+enum Cell {
+    Digit(char),
+    Symbol(char),
+    Empty,
+}
+
+impl Cell {
+    fn visual(self) -> char {
+        match self {
+            Cell::Digit(c) | Cell::Symbol(c) => c,
+            Cell::Empty => '.',
+        }
+    }
+}
+
+impl FromChar for Cell {
+    fn from_char(c: char) -> std::result::Result<Cell, String> {
+        Ok(match c {
+            '.' => Cell::Empty,
+            '0'..='9' => Cell::Digit(c),
+            _ => Cell::Symbol(c),
+        })
+    }
+}
+
+pub struct Schematics {
+    grid: Grid<Cell>,
 }
 
 struct Gear(Region, u32);
 
 impl Schematics {
-    fn parse(lines: Vec<String>) -> Schematics {
-        let visual = lines.join("");
-        let width = visual.len() / lines.len();
-        Schematics { width, visual }
-    }
-
-    fn regions_matching(self: &Self, re: Regex) -> Vec<Region> {
-        re.captures_iter(&self.visual)
-            .filter_map(|captures| captures.name("region"))
-            .map(|m| Region {
-                locations: (m.start()..m.end()).map(|offset| self.to_pos(offset)).collect(),
-                visual: m.as_str().to_owned(),
-            })
-            .collect()
+    fn parse(lines: Vec<String>) -> Result<Schematics> {
+        let width = lines.first().map(String::len).ok_or_else(|| AocError::parse(1, "", "empty schematics"))?;
+        if let Some((index, line)) = lines.iter().enumerate().find(|(_, line)| line.len() != width) {
+            return Err(AocError::parse(index + 1, line, format!("expected {} characters", width)));
+        }
+        debug!(width, height = lines.len(), "parsed schematics");
+        Ok(Schematics { grid: Grid::parse(&lines)? })
     }
 
     fn numbers(self: &Self) -> Vec<Region> {
-        self.regions_matching(Regex::new(r"(?<region>\d+)").unwrap())
+        let mut numbers: Vec<Region> = Vec::new();
+        let mut previous = None;
+        for ((x, y), &cell) in self.grid.cells() {
+            let pos = Vec2::new(x as isize, y as isize);
+            match (cell, numbers.last_mut()) {
+                (Cell::Digit(digit), Some(number)) if previous == Some(pos - Vec2::new(1, 0)) => {
+                    number.locations.push(pos);
+                    number.visual.push(digit);
+                }
+                (Cell::Digit(digit), _) => numbers.push(Region { locations: vec![pos], visual: digit.to_string() }),
+                _ => {}
+            }
+            previous = matches!(cell, Cell::Digit(_)).then_some(pos);
+        }
+        numbers
     }
 
     fn symbols(self: &Self) -> Vec<Region> {
-        self.regions_matching(Regex::new(r"(?<region>[^.\d])").unwrap())
+        self.grid.cells()
+            .filter_map(|((x, y), &cell)| match cell {
+                Cell::Symbol(symbol) => Some(Region { locations: vec![Vec2::new(x as isize, y as isize)], visual: symbol.to_string() }),
+                _ => None,
+            })
+            .collect()
     }
 
     fn parts(self: &Self) -> Vec<Region> {
@@ -99,6 +128,7 @@ impl Schematics {
 
     fn gears(self: &Self) -> Vec<Gear> {
         let parts = self.parts();
+        debug!(parts = parts.len(), "found parts");
         self.symbols()
             .into_iter()
             .filter_map(|symbol| symbol.to_star_symbol())
@@ -113,69 +143,165 @@ impl Schematics {
         self.gears().into_iter().map(|Gear(_, ratio)| ratio).collect()
     }
 
-    fn to_pos(self: &Self, offset: usize) -> Pos {
-        Pos {
-            x: (offset % self.width) as isize,
-            y: (offset / self.width) as isize,
-        }
+    fn styles(self: &Self) -> Grid<Style> {
+        let mut styles = self.grid.map(|_| Style::PLAIN);
+        let mut paint = |regions: Vec<Region>, style: Style| for pos in regions.iter().flat_map(|region| &region.locations) {
+            styles[(pos.x as usize, pos.y as usize)] = style;
+        };
+        paint(self.numbers(), Style::fg(Color::Red));
+        paint(self.parts(), Style::fg(Color::Green));
+        paint(self.symbols(), Style::fg(Color::Yellow));
+        paint(self.gears().into_iter().map(|Gear(star, _)| star).collect(), Style::fg(Color::Magenta).bold());
+        styles
+    }
+
+    fn render(self: &Self) -> String {
+        let styles = self.styles();
+        let rows: Vec<String> = self.grid.rows().map(|row| row.iter().map(|cell| cell.visual()).collect()).collect();
+        render_grid(&rows, |x, y, _| styles[(x, y)])
+    }
+
+    fn to_svg(self: &Self) -> Svg {
+        let styles = self.styles();
+        let mut svg = Svg::new(10);
+        svg.grid(&self.grid, |position, _| styles[position].foreground.map(|color| Paint::fill(color.name())));
+        svg
     }
 }
 
-fn sum_of_part_numbers(lines: Vec<String>) -> u32 {
-    let schematics = Schematics::parse(lines);
+fn sum_of_part_numbers(schematics: &Schematics) -> u32 {
     schematics.part_numbers().iter().sum()
 }
 
-fn sum_of_gear_ratios(lines: Vec<String>) -> u32 {
-    let schematics = Schematics::parse(lines);
+fn sum_of_gear_ratios(schematics: &Schematics) -> u32 {
     schematics.gear_ratios().iter().sum()
 }
 
+pub struct Day03;
+
+#[aoc(day = 3)]
+impl Puzzle for Day03 {
+    const RULES: &'static [Rule] = &[Rule::MinLines(1), Rule::Rectangular, Rule::NoTrailingBlankLines];
+
+    const QUERIES: &'static [&'static str] = &["numbers", "symbols", "parts", "gears", "render", "svg"];
+
+    type Model = Schematics;
+
+    fn parse(input: Vec<String>) -> Result<Self::Model> {
+        Schematics::parse(input)
+    }
+
+    fn part_one(model: &Self::Model) -> Result<impl Display> {
+        Ok(sum_of_part_numbers(model))
+    }
+
+    fn part_two(model: &Self::Model) -> Result<impl Display> {
+        Ok(sum_of_gear_ratios(model))
+    }
+
+    fn query(model: &Self::Model, query: &[&str]) -> Result<String> {
+        match query {
+            ["numbers"] => Ok(model.numbers().iter().map(|number| &number.visual).join(" ")),
+            ["symbols"] => Ok(model.symbols().iter().map(|symbol| &symbol.visual).join(" ")),
+            ["parts"] => Ok(model.part_numbers().iter().join(" ")),
+            ["gears"] => Ok(model.gears().iter().map(|Gear(star, ratio)| format!("{},{}: {}", star.locations[0].x, star.locations[0].y, ratio)).join("\n")),
+            ["render"] => Ok(model.render().trim_end().to_owned()),
+            ["svg"] => Ok(model.to_svg().to_string().trim_end().to_owned()),
+            _ => Err(AocError::unknown_query(query)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::input::daily_example;
+    use insta::assert_yaml_snapshot;
+
+    use crate::answers::expected_answer;
+    use crate::input::{daily_example, daily_input};
+    use crate::puzzle::Part;
 
     use super::*;
 
+    fn example() -> Schematics {
+        Schematics::parse(daily_example(2023, 3).unwrap()).unwrap()
+    }
+
+    fn input() -> Schematics {
+        Schematics::parse(daily_input(2023, 3).unwrap()).unwrap()
+    }
+
     #[test]
     fn finds_all_numbers() {
-        let schematics = Schematics::parse(daily_example(3));
+        let schematics = example();
         let numbers: Vec<String> = schematics.numbers().into_iter().map(|n| n.visual).collect();
         assert_eq!(numbers, vec!["467", "114", "35", "633", "617", "58", "592", "755", "664", "598"])
     }
 
+    #[test]
+    fn locates_regions() {
+        let schematics = example();
+        assert_yaml_snapshot!("numbers", schematics.numbers());
+        assert_yaml_snapshot!("symbols", schematics.symbols());
+    }
+
     #[test]
     fn finds_parts() {
-        let schematics = Schematics::parse(daily_example(3));
+        let schematics = example();
         let parts: Vec<String> = schematics.parts().into_iter().map(|n| n.visual).collect();
         assert_eq!(parts, vec!["467", "35", "633", "617", "592", "755", "664", "598"])
     }
 
     #[test]
     fn computes_sum_of_part_numbers() {
-        assert_eq!(sum_of_part_numbers(daily_example(3)), 4361)
+        assert_eq!(sum_of_part_numbers(&example()), 4361)
     }
 
     #[test]
+    #[cfg_attr(not(feature = "personal-inputs"), ignore = "requires personal puzzle inputs")]
     fn solves_part_one() {
-        let sum = sum_of_part_numbers(daily_input(3));
-        assert_eq!(sum, 528819);
+        let sum = sum_of_part_numbers(&input());
+        assert_eq!(sum.to_string(), expected_answer(2023, 3, Part::One));
     }
 
     #[test]
     fn finds_gears() {
-        let schematics = Schematics::parse(daily_example(3));
-        let gears_locations: Vec<Pos> = schematics.gears().into_iter().flat_map(|Gear(region, _)| region.locations).collect();
-        assert_eq!(gears_locations, vec![Pos{ x: 3, y: 1}, Pos{ x: 5, y: 8}])
+        let schematics = example();
+        let gears_locations: Vec<Vec2> = schematics.gears().into_iter().flat_map(|Gear(region, _)| region.locations).collect();
+        assert_eq!(gears_locations, vec![Vec2::new(3, 1), Vec2::new(5, 8)])
     }
 
     #[test]
     fn sums_gear_ratios() {
-        assert_eq!(sum_of_gear_ratios(daily_example(3)), 467835)
+        assert_eq!(sum_of_gear_ratios(&example()), 467835)
     }
 
     #[test]
+    #[cfg_attr(not(feature = "personal-inputs"), ignore = "requires personal puzzle inputs")]
     fn solves_part_two() {
-        assert_eq!(sum_of_gear_ratios(daily_input(3)), 80403602)
+        assert_eq!(sum_of_gear_ratios(&input()).to_string(), expected_answer(2023, 3, Part::Two))
+    }
+
+    #[test]
+    fn highlights_parts_symbols_and_gears() {
+        let rendered = example().render();
+        assert_eq!(rendered.lines().take(2).collect::<Vec<_>>(), vec![
+            "\x1b[32m467\x1b[0m..\x1b[31m114\x1b[0m..",
+            "...\x1b[1;35m*\x1b[0m......",
+        ]);
+    }
+
+    #[test]
+    fn draws_schematics_as_svg() {
+        let svg = example().to_svg().to_string();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 10 10\" width=\"100\" height=\"100\">"));
+        assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"1\" height=\"1\" fill=\"green\"/>"));
+        assert!(svg.contains("<rect x=\"3\" y=\"1\" width=\"1\" height=\"1\" fill=\"magenta\"/>"));
+        assert!(!svg.contains("x=\"0\" y=\"1\""));
+    }
+
+    #[test]
+    fn reports_ragged_schematics() {
+        let error = Schematics::parse(vec!["467..".to_owned(), "...*".to_owned()]).err().unwrap();
+        assert_eq!(error.to_string(), r#"line 2: expected 5 characters in "...*""#);
     }
 }
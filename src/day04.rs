@@ -1,24 +1,46 @@
-use regex::Regex;
+use std::fmt::Display;
 
-#[derive(PartialEq)]
+use aoc2023_macros::aoc;
+use tracing::debug;
+
+use crate::error::{parse_lines, query_argument, AocError, Result};
+use crate::parse::{parse_all, Parser};
+use crate::puzzle::{Part, Puzzle, Variant};
+use crate::small_set::SmallSet;
+
+fn numbers(numbers: &[String]) -> impl Iterator<Item=usize> + '_ {
+    numbers.iter().filter_map(|n| n.parse().ok())
+}
+
+#[derive(PartialEq, Clone)]
 struct Card {
     id: u32,
     winning_numbers: Vec<String>,
     own_numbers: Vec<String>,
+    winning: SmallSet,
     is_copy: bool,
 }
 
 impl Card {
-    fn parse<T: AsRef<str>>(card: T) -> Card {
-        let regex = Regex::new(r"Card\s+(?<id>\d+): (?<winners>[\d\s]+) \| (?<own>[\d ]+)").unwrap();
-        let (_, [id, winners, own]) = regex.captures(card.as_ref()).unwrap().extract();
-        let winning_numbers = winners.split_whitespace().map(|n| n.to_string()).collect();
-        let own_numbers = own.split_whitespace().map(|n| n.to_string()).collect();
-        Card { id: id.parse().unwrap(), winning_numbers, own_numbers, is_copy: false }
+    fn parse(card: &str) -> std::result::Result<Card, String> {
+        parse_all(card, |p| {
+            p.tag("Card")?;
+            p.skip_spaces();
+            let id = p.integer()?;
+            p.tag(":")?;
+            p.skip_spaces();
+            let winning_numbers: Vec<String> = p.separated(" ", Parser::word)?.into_iter().map(str::to_owned).collect();
+            p.skip_spaces();
+            p.tag("|")?;
+            p.skip_spaces();
+            let own_numbers = p.separated(" ", Parser::word)?.into_iter().map(str::to_owned).collect();
+            let winning = numbers(&winning_numbers).collect();
+            Ok(Card { id, winning_numbers, own_numbers, winning, is_copy: false })
+        })
     }
 
     fn winners_count(&self) -> u32 {
-        self.own_numbers.iter().filter(|n| self.winning_numbers.contains(n)).count() as u32
+        numbers(&self.own_numbers).filter(|&n| self.winning.contains(n)).count() as u32
     }
 
     fn score(&self) -> u32 {
@@ -31,6 +53,7 @@ impl Card {
             id: self.id,
             own_numbers: self.own_numbers.clone(),
             winning_numbers: self.winning_numbers.clone(),
+            winning: self.winning.clone(),
             is_copy: true,
         }
     }
@@ -40,7 +63,8 @@ impl Card {
     }
 }
 
-struct CardDeck {
+#[derive(Clone)]
+pub struct CardDeck {
     cards: Vec<Card>,
 }
 
@@ -69,60 +93,134 @@ impl CardDeck {
         self.cards.push(card)
     }
 
+    fn describe(&self, id: u32) -> Result<String> {
+        let card = self.cards.iter().find(|c| c.id == id).ok_or_else(|| AocError::Query(format!("no card {}", id)))?;
+        Ok(format!("{} winning numbers, score {}", card.winners_count(), card.score()))
+    }
+
     fn total_score(&self) -> u32 {
         self.cards.iter().map(|c| c.score()).sum()
     }
 }
 
-fn parse_cards<T: AsRef<str>>(cards: Vec<T>) -> Vec<Card> {
-    cards.into_iter().map(|card| Card::parse(card.as_ref())).collect()
+fn parse_cards(cards: &[String]) -> Result<Vec<Card>> {
+    let cards = parse_lines(cards, 0, Card::parse)?;
+    debug!(cards = cards.len(), "parsed cards");
+    Ok(cards)
 }
 
 
 struct GameRules;
 
 impl GameRules {
-    fn claim_prizes(mut deck: CardDeck) -> (CardDeck, CardDeck) {
+    fn count_cards(deck: &CardDeck) -> Result<usize> {
+        let mut copies = vec![1_usize; deck.count()];
+        for (index, card) in deck.cards.iter().enumerate() {
+            for n in 1..=card.winners_count() as usize {
+                if index + n >= copies.len() {
+                    return Err(AocError::unsolvable(format!("card {} wins a copy of missing card {}", card.id, card.id + n as u32)));
+                }
+                copies[index + n] += copies[index];
+            }
+        }
+        let count = copies.iter().sum();
+        debug!(count, "counted cards");
+        Ok(count)
+    }
+
+    fn claim_prizes(mut deck: CardDeck) -> Result<(CardDeck, CardDeck)> {
         let mut originals = CardDeck::empty();
         let mut prizes = CardDeck::empty();
 
         while let Some(card) = deck.draw() {
             for n in 1..=card.winners_count() {
-                deck.put(originals.claim_copy(card.id + n).unwrap())
+                let copy = originals.claim_copy(card.id + n)
+                    .ok_or_else(|| AocError::unsolvable(format!("card {} wins a copy of missing card {}", card.id, card.id + n)))?;
+                deck.put(copy)
             }
             match card.is_original() {
                 true => originals.put(card),
                 false => prizes.put(card),
             }
         }
-        (originals, prizes)
+        debug!(originals = originals.count(), prizes = prizes.count(), "claimed prizes");
+        Ok((originals, prizes))
+    }
+}
+
+pub struct Day04;
+
+#[aoc(day = 4)]
+impl Puzzle for Day04 {
+    const QUERIES: &'static [&'static str] = &["card <id>"];
+
+    type Model = CardDeck;
+
+    fn parse(input: Vec<String>) -> Result<Self::Model> {
+        Ok(CardDeck::new(parse_cards(&input)?))
+    }
+
+    fn part_one(model: &Self::Model) -> Result<impl Display> {
+        Ok(model.total_score())
+    }
+
+    fn part_two(model: &Self::Model) -> Result<impl Display> {
+        GameRules::count_cards(model)
+    }
+
+    fn query(model: &Self::Model, query: &[&str]) -> Result<String> {
+        match query {
+            ["card", id] => model.describe(query_argument(id)?),
+            _ => Err(AocError::unknown_query(query)),
+        }
+    }
+
+    fn variants() -> Vec<Variant<Self::Model>> {
+        vec![Variant { name: "claiming copies", part: Part::Two, solve: count_cards_by_claiming_copies }]
     }
 }
 
+fn count_cards_by_claiming_copies(deck: &CardDeck) -> Result<String> {
+    let (originals, prizes) = GameRules::claim_prizes(deck.clone())?;
+    Ok((originals.count() + prizes.count()).to_string())
+}
+
 
 #[cfg(test)]
 mod test {
+    use proptest::prelude::*;
+
+    use crate::answers::expected_answer;
     use crate::input::{daily_example, daily_input};
+    use crate::puzzle::Part;
 
     use super::*;
 
+    fn example() -> Vec<Card> {
+        parse_cards(&daily_example(2023, 4).unwrap()).unwrap()
+    }
+
+    fn input() -> Vec<Card> {
+        parse_cards(&daily_input(2023, 4).unwrap()).unwrap()
+    }
+
     #[test]
     fn knows_card_winning_numbers() {
-        let cards = parse_cards(daily_example(4));
+        let cards = example();
         let first = cards.first().unwrap();
         assert_eq!(first.winning_numbers, vec!["41", "48", "83", "86", "17"])
     }
 
     #[test]
     fn knows_card_own_numbers() {
-        let cards = parse_cards(daily_example(4));
+        let cards = example();
         let first = cards.first().unwrap();
         assert_eq!(first.own_numbers, vec!["83", "86", "6", "31", "17", "9", "48", "53"])
     }
 
     #[test]
     fn knows_card_winners() {
-        let winning_counts: Vec<u32> = parse_cards(daily_example(4))
+        let winning_counts: Vec<u32> = example()
             .iter()
             .map(Card::winners_count)
             .collect();
@@ -131,29 +229,55 @@ mod test {
 
     #[test]
     fn computes_card_score() {
-        let cards = parse_cards(daily_example(4));
+        let cards = example();
         let card_scores: Vec<u32> = cards.iter().map(|card| card.score()).collect();
         assert_eq!(card_scores, vec![8, 2, 2, 1, 0, 0])
     }
 
     #[test]
+    #[cfg_attr(not(feature = "personal-inputs"), ignore = "requires personal puzzle inputs")]
     fn solves_part_one() {
-        let deck = CardDeck::new(parse_cards(daily_input(4)));
-        assert_eq!(deck.total_score(), 27845)
+        let deck = CardDeck::new(input());
+        assert_eq!(deck.total_score().to_string(), expected_answer(2023, 4, Part::One))
     }
 
     #[test]
     fn gifts_card_copies() {
-        let deck = CardDeck::new(parse_cards(daily_example(4)));
-        let (originals, prizes) = GameRules::claim_prizes(deck);
+        let deck = CardDeck::new(example());
+        let (originals, prizes) = GameRules::claim_prizes(deck).unwrap();
         assert_eq!(originals.count(), 6);
         assert_eq!(prizes.count(), 24);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "personal-inputs"), ignore = "requires personal puzzle inputs")]
     fn solves_part_two() {
-        let deck = CardDeck::new(parse_cards(daily_input(4)));
-        let (originals, prizes) = GameRules::claim_prizes(deck);
-        assert_eq!(originals.count() + prizes.count(), 9496801);
+        let deck = CardDeck::new(input());
+        assert_eq!(GameRules::count_cards(&deck).unwrap().to_string(), expected_answer(2023, 4, Part::Two));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn counts_card_copies_without_claiming_them() {
+        let deck = CardDeck::new(example());
+        assert_eq!(GameRules::count_cards(&deck).unwrap(), 30);
+    }
+
+    #[test]
+    fn cannot_win_copies_of_cards_past_the_table() {
+        let deck = CardDeck::new(parse_cards(&["Card 1: 41 48 | 48 7".to_owned()]).unwrap());
+        assert_eq!(GameRules::count_cards(&deck).err().unwrap().to_string(), "no solution: card 1 wins a copy of missing card 2");
+        assert_eq!(GameRules::claim_prizes(deck).err().unwrap().to_string(), "no solution: card 1 wins a copy of missing card 2");
+    }
+
+    proptest! {
+        #[test]
+        fn parses_any_valid_card(id in 1..300_u32, winners in prop::collection::vec(1..100_u32, 1..10), own in prop::collection::vec(1..100_u32, 1..25)) {
+            let numbers = |numbers: &[u32]| numbers.iter().map(|n| format!("{:>2}", n)).collect::<Vec<_>>().join(" ");
+            let card = Card::parse(&format!("Card {:>3}: {} | {}", id, numbers(&winners), numbers(&own))).unwrap();
+            prop_assert_eq!(card.id, id);
+            prop_assert_eq!(&card.winning_numbers, &winners.iter().map(u32::to_string).collect::<Vec<_>>());
+            prop_assert_eq!(&card.own_numbers, &own.iter().map(u32::to_string).collect::<Vec<_>>());
+            prop_assert_eq!(card.winners_count() as usize, own.iter().filter(|n| winners.contains(n)).count());
+        }
+    }
+}
@@ -1,8 +1,16 @@
+use std::fmt::Display;
 use std::ops::Range;
-use std::str::FromStr;
 
-use regex::Regex;
+use aoc2023_macros::aoc;
+use serde::{Serialize, Serializer};
+use tracing::debug;
 
+use crate::error::{parse_line, parse_lines, query_argument, AocError, Result};
+use crate::parse::{parse_all, Parser};
+use crate::piecewise_map::PiecewiseMap;
+use crate::progress::track;
+use crate::puzzle::{split_blocks, Part, Puzzle, Variant};
+use crate::range_set::RangeSet;
 use Component::{Fertilizer, Humidity, Light, Location, Seed, Soil, Temperature, Water};
 
 #[derive(Eq, PartialEq, Debug, Copy, Clone, Ord, PartialOrd)]
@@ -18,17 +26,17 @@ enum Component {
 }
 
 impl Component {
-    fn parse(name: &str) -> ComponentKind {
+    fn parse(name: &str) -> std::result::Result<ComponentKind, String> {
         match name {
-            "seed" => Seed,
-            "soil" => Soil,
-            "fertilizer" => Fertilizer,
-            "water" => Water,
-            "light" => Light,
-            "temperature" => Temperature,
-            "humidity" => Humidity,
-            "location" => Location,
-            _ => panic!()
+            "seed" => Ok(Seed),
+            "soil" => Ok(Soil),
+            "fertilizer" => Ok(Fertilizer),
+            "water" => Ok(Water),
+            "light" => Ok(Light),
+            "temperature" => Ok(Temperature),
+            "humidity" => Ok(Humidity),
+            "location" => Ok(Location),
+            _ => Err(format!("unknown component {}", name))
         }
     }
 
@@ -42,53 +50,75 @@ impl Component {
     fn is_a(&self, kind: ComponentKind) -> bool {
         *self == kind(self.number())
     }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Seed(_) => "seed",
+            Soil(_) => "soil",
+            Fertilizer(_) => "fertilizer",
+            Water(_) => "water",
+            Light(_) => "light",
+            Temperature(_) => "temperature",
+            Humidity(_) => "humidity",
+            Location(_) => "location",
+        }
+    }
 }
 
 type ComponentKind = fn(isize) -> Component;
 
+fn serialize_kind<S: Serializer>(kind: &ComponentKind, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(kind(0).name())
+}
+
+#[derive(Serialize)]
 struct CorrelationRule {
     range: Range<isize>,
     offset: isize,
 }
 
-impl CorrelationRule {
-    fn apply(&self, number: isize) -> Option<isize> {
-        if self.range.contains(&number) { Some(number + self.offset) } else { None }
-    }
-}
-
+#[derive(Serialize)]
 struct CorrelationTable {
+    #[serde(serialize_with = "serialize_kind")]
     source: ComponentKind,
+    #[serde(serialize_with = "serialize_kind")]
     destination: ComponentKind,
     rules: Vec<CorrelationRule>,
+    #[serde(skip)]
+    map: PiecewiseMap,
 }
 
 impl CorrelationTable {
-    fn parse(table: &str) -> CorrelationTable {
-        let mut lines = table.lines();
-        let (source, destination) = Self::parse_header(lines.next().unwrap());
-        let rules = lines.map(Self::parse_rule).collect();
-        CorrelationTable { source, destination, rules }
+    fn parse(table: &[String], start: usize) -> Result<CorrelationTable> {
+        let (source, destination) = parse_line(table, start, Self::parse_header)?;
+        let rules = parse_lines(table, start + 1, Self::parse_rule)?;
+        let map = rules.iter().map(|rule: &CorrelationRule| (rule.range.clone(), rule.offset)).collect();
+        Ok(CorrelationTable { source, destination, rules, map })
     }
 
-    fn parse_header(header: &str) -> (ComponentKind, ComponentKind) {
-        let re = Regex::new(r"(?<source>\w+)-to-(?<destination>\w+) map:").unwrap();
-        let (_, [source, destination]) = re.captures(header).unwrap().extract();
-        (Component::parse(source), Component::parse(destination))
+    fn parse_header(header: &str) -> std::result::Result<(ComponentKind, ComponentKind), String> {
+        let (source, destination) = parse_all(header, |p| {
+            let source = p.word()?;
+            p.tag("-to-")?;
+            let destination = p.word()?;
+            p.tag(" map:")?;
+            Ok((source, destination))
+        })?;
+        Ok((Component::parse(source)?, Component::parse(destination)?))
     }
 
-    fn parse_rule(rule: &str) -> CorrelationRule {
-        let numbers: Vec<isize> = rule.split_whitespace().map(|n| n.parse().unwrap()).collect();
-        let &[to, from, range_length] = numbers.as_slice() else { panic!() };
-        CorrelationRule { range: from..(from + range_length), offset: to - from }
+    fn parse_rule(rule: &str) -> std::result::Result<CorrelationRule, String> {
+        let numbers: Vec<isize> = parse_all(rule, Parser::integers)?;
+        let &[to, from, range_length] = numbers.as_slice() else { return Err("expected 3 numbers".to_owned()) };
+        match (from.checked_add(range_length), to.checked_sub(from)) {
+            (Some(end), Some(offset)) => Ok(CorrelationRule { range: from..end, offset }),
+            _ => Err("numbers out of range".to_owned()),
+        }
     }
 
     fn lookup(&self, component: Component) -> Option<Component> {
         if !component.is_a(self.source) { return None; };
-        let number = self.rules.iter()
-            .find_map(|rule| rule.apply(component.number()))
-            .unwrap_or(component.number());
-        Some((self.destination)(number))
+        Some((self.destination)(self.map.get(component.number())))
     }
 }
 
@@ -97,8 +127,12 @@ struct SeedBag {
 }
 
 impl SeedBag {
-    fn parse(bag: &str) -> Self {
-        SeedBag { seeds: parse_seed_numbers(bag).iter().map(|&n| Seed(n)).collect() }
+    fn parse(bag: &str) -> std::result::Result<Self, String> {
+        Ok(SeedBag::new(parse_seed_numbers(bag)?))
+    }
+
+    fn new(numbers: Vec<isize>) -> Self {
+        SeedBag { seeds: numbers.iter().map(|&n| Seed(n)).collect() }
     }
 
     fn iter(self) -> impl Iterator<Item=Component> {
@@ -125,31 +159,51 @@ impl SeedFarm {
         self.fields.iter().flat_map(|field| field.iter())
     }
 
-    fn parse(farm: &str) -> Self {
-        SeedFarm::new(parse_seed_numbers(farm))
+    fn parse(farm: &str) -> std::result::Result<Self, String> {
+        SeedFarm::new(parse_seed_numbers(farm)?)
     }
 
-    fn new(numbers: Vec<isize>) -> Self {
-        let fields = numbers.as_slice().chunks_exact(2).map(|chunk| {
-            let &[start, count] = chunk else { panic!() };
-            SeedField { range: start..(start + count) }
-        }).collect();
+    fn new(numbers: Vec<isize>) -> std::result::Result<Self, String> {
+        if numbers.len() % 2 != 0 {
+            return Err(format!("expected pairs of seed numbers, found {} numbers", numbers.len()));
+        }
+        let fields: Vec<SeedField> = numbers.chunks_exact(2)
+            .map(|chunk| SeedField { range: chunk[0]..(chunk[0] + chunk[1]) })
+            .collect();
+
+        debug!(fields = fields.len(), seeds = fields.iter().map(|f| f.range.len()).sum::<usize>(), "planted seed farm");
+        Ok(SeedFarm { fields })
+    }
 
-        SeedFarm { fields }
+    /// Plants the farm from the seed numbers of an already parsed almanac, whose seeds come on the first line.
+    fn plant(numbers: &[isize]) -> Result<Self> {
+        SeedFarm::new(numbers.to_vec()).map_err(|reason| {
+            let seeds = numbers.iter().map(isize::to_string).collect::<Vec<_>>().join(" ");
+            AocError::parse(1, &format!("seeds: {}", seeds), reason)
+        })
     }
 }
 
-struct Almanac {
+#[derive(Serialize)]
+pub struct Almanac {
     tables: Vec<CorrelationTable>,
+    #[serde(skip)]
+    map: PiecewiseMap,
 }
 
 impl Almanac {
-    fn parse(instructions: &[String]) -> Self {
-        let tables = instructions.join("\n")
-            .split("\n\n").map(CorrelationTable::parse)
-            .collect::<Vec<CorrelationTable>>();
+    /// Parses a table from each block, the first block coming after `offset` lines of input.
+    fn parse(blocks: &[Vec<String>], offset: usize) -> Result<Self> {
+        let mut tables = Vec::with_capacity(blocks.len());
+        let mut offset = offset;
+        for block in blocks {
+            tables.push(CorrelationTable::parse(block, 0).map_err(|error| error.offset_by(offset))?);
+            offset += block.len() + 1;
+        }
 
-        Almanac { tables }
+        let map = tables.iter().fold(PiecewiseMap::new(), |map, table: &CorrelationTable| map.then(&table.map));
+        debug!(tables = tables.len(), pieces = map.pieces().len(), "parsed almanac");
+        Ok(Almanac { tables, map })
     }
 
     fn correlate(&self, component: Component) -> Option<Component> {
@@ -157,53 +211,137 @@ impl Almanac {
     }
 
     fn location_for(&self, component: Component) -> Option<Component> {
-        self.tables.iter().fold(Some(component), |result, table| match result {
-            Some(c) => {
-                table.lookup(c)
-            }
-            None => None
-        })
+        match (self.tables.first(), self.tables.last()) {
+            (Some(first), Some(last)) => component.is_a(first.source).then(|| (last.destination)(self.map.get(component.number()))),
+            _ => Some(component),
+        }
+    }
+
+    fn lowest_location_number_of_ranges(&self, ranges: RangeSet) -> Option<isize> {
+        let locations = self.map.image(&ranges);
+        debug!(ranges = locations.ranges().len(), "mapped seed ranges to locations");
+        locations.min()
     }
 
     fn lowest_location_number_of(&self, seeds: impl Iterator<Item=Component> + Sized) -> Option<isize> {
-        seeds.filter_map(|seed| self.location_for(seed))
+        let mut processed = 0_usize;
+        let lowest = seeds.inspect(|_| processed += 1)
+            .filter_map(|seed| self.location_for(seed))
             .map(|c| c.number())
-            .min()
+            .min();
+        debug!(seeds = processed, ?lowest, "processed seeds");
+        lowest
     }
 }
 
-fn parse_seed_numbers(spec: &str) -> Vec<isize> {
-    let re = Regex::new(r"seeds:\s+(?<seeds>[\d\s]+)").unwrap();
-    let (_, [seeds]) = re.captures(spec).unwrap().extract();
-    seeds.split_whitespace()
-        .filter_map(|n| isize::from_str(n).ok())
-        .collect()
+fn describe(component: Option<Component>) -> String {
+    component.map_or_else(|| "unknown".to_owned(), |component| format!("{} {}", component.name(), component.number()))
+}
+
+fn parse_seed_numbers(spec: &str) -> std::result::Result<Vec<isize>, String> {
+    parse_all(spec, |p| {
+        p.section("seeds")?;
+        p.integers()
+    })
+}
+
+fn parse_almanac<T>(blocks: &[Vec<String>], seeds: impl FnOnce(&str) -> std::result::Result<T, String>) -> Result<(T, Almanac)> {
+    let (seeds_block, tables) = blocks.split_first().ok_or_else(|| AocError::parse(1, "", "missing line"))?;
+    Ok((parse_line(seeds_block, 0, seeds)?, Almanac::parse(tables, seeds_block.len() + 1)?))
+}
+
+fn parse_instructions(blocks: Vec<Vec<String>>) -> Result<(SeedBag, Almanac)> {
+    parse_almanac(&blocks, SeedBag::parse)
 }
 
-fn parse_instructions(instructions: Vec<String>) -> (SeedBag, Almanac) {
-    (SeedBag::parse(&instructions[0]), Almanac::parse(&instructions[2..]))
+fn parse_updated_instructions(blocks: Vec<Vec<String>>) -> Result<(SeedFarm, Almanac)> {
+    parse_almanac(&blocks, SeedFarm::parse)
 }
 
-fn parse_updated_instructions(instructions: Vec<String>) -> (SeedFarm, Almanac) {
-    (SeedFarm::parse(&instructions[0]), Almanac::parse(&instructions[2..]))
+fn no_seeds() -> AocError {
+    AocError::unsolvable("no seed to plant")
 }
 
+fn lowest_location_by_brute_force((numbers, almanac): &(Vec<isize>, Almanac)) -> Result<String> {
+    let farm = SeedFarm::plant(numbers)?;
+    farm.fields.iter()
+        .filter_map(|field| {
+            let progress = track(|| format!("seeds {}..{}", field.range.start, field.range.end), field.range.len() as u64);
+            almanac.lowest_location_number_of(field.iter().inspect(|_| progress.tick()))
+        })
+        .min()
+        .map(|n| n.to_string())
+        .ok_or_else(no_seeds)
+}
+
+pub struct Day05;
+
+#[aoc(day = 5)]
+impl Puzzle for Day05 {
+    const QUERIES: &'static [&'static str] = &["seeds", "location_for <seed>", "correlate <component> <number>"];
+
+    type Model = (Vec<isize>, Almanac);
+
+    fn parse(input: Vec<String>) -> Result<Self::Model> {
+        parse_almanac(&split_blocks(&input), parse_seed_numbers)
+    }
+
+    fn part_one((numbers, almanac): &Self::Model) -> Result<impl Display> {
+        let seeds = SeedBag::new(numbers.clone());
+        almanac.lowest_location_number_of(seeds.iter()).ok_or_else(no_seeds)
+    }
+
+    fn part_two((numbers, almanac): &Self::Model) -> Result<impl Display> {
+        let farm = SeedFarm::plant(numbers)?;
+        let ranges = farm.fields.into_iter().map(|field| field.range).collect();
+        almanac.lowest_location_number_of_ranges(ranges).ok_or_else(no_seeds)
+    }
+
+    fn query((numbers, almanac): &Self::Model, query: &[&str]) -> Result<String> {
+        match query {
+            ["seeds"] => Ok(numbers.iter().map(isize::to_string).collect::<Vec<_>>().join(" ")),
+            ["location_for", seed] => Ok(describe(almanac.location_for(Seed(query_argument(seed)?)))),
+            ["correlate", component, number] => {
+                let kind = Component::parse(component).map_err(AocError::Query)?;
+                Ok(describe(almanac.correlate(kind(query_argument(number)?))))
+            }
+            _ => Err(AocError::unknown_query(query)),
+        }
+    }
+
+    fn variants() -> Vec<Variant<Self::Model>> {
+        vec![Variant { name: "brute force", part: Part::Two, solve: lowest_location_by_brute_force }]
+    }
+}
+
+#[cfg(test)]
 mod test {
+    use insta::assert_yaml_snapshot;
+    use proptest::prelude::*;
+
     use Component::{Seed, Soil};
 
-    use crate::input::{daily_example, daily_input};
+    use crate::answers::expected_answer;
+    use crate::input::{daily_example_blocks, daily_input_blocks};
+    use crate::puzzle::Part;
 
     use super::*;
 
     #[test]
     fn knows_seeds_to_be_planted() {
-        let (bag, _) = parse_instructions(daily_example(5));
+        let (bag, _) = parse_instructions(daily_example_blocks(2023, 5).unwrap()).unwrap();
         assert_eq!(bag.seeds, vec![Seed(79), Seed(14), Seed(55), Seed(13)])
     }
 
+    #[test]
+    fn parses_almanac_tables() {
+        let (_, almanac) = parse_instructions(daily_example_blocks(2023, 5).unwrap()).unwrap();
+        assert_yaml_snapshot!(almanac);
+    }
+
     #[test]
     fn knows_correlation_between_components() {
-        let (_, almanac) = parse_instructions(daily_example(5));
+        let (_, almanac) = parse_instructions(daily_example_blocks(2023, 5).unwrap()).unwrap();
         assert_eq!(almanac.correlate(Seed(51)), Some(Soil(53)));
         assert_eq!(almanac.correlate(Seed(25)), Some(Soil(25)));
         assert_eq!(almanac.correlate(Soil(53)), Some(Fertilizer(38)));
@@ -211,7 +349,7 @@ mod test {
 
     #[test]
     fn correlate_seed_to_location() {
-        let (_, almanac) = parse_instructions(daily_example(5));
+        let (_, almanac) = parse_instructions(daily_example_blocks(2023, 5).unwrap()).unwrap();
         assert_eq!(almanac.location_for(Seed(79)), Some(Location(82)));
         assert_eq!(almanac.location_for(Seed(14)), Some(Location(43)));
         assert_eq!(almanac.location_for(Seed(55)), Some(Location(86)));
@@ -220,25 +358,79 @@ mod test {
 
     #[test]
     fn finds_lowest_location_number() {
-        let (seeds, almanac) = parse_instructions(daily_example(5));
+        let (seeds, almanac) = parse_instructions(daily_example_blocks(2023, 5).unwrap()).unwrap();
         assert_eq!(almanac.lowest_location_number_of(seeds.iter()), Some(35))
     }
 
     #[test]
+    #[cfg_attr(not(feature = "personal-inputs"), ignore = "requires personal puzzle inputs")]
     fn solves_part_one() {
-        let (seeds, almanac) = parse_instructions(daily_input(5));
-        assert_eq!(almanac.lowest_location_number_of(seeds.iter()), Some(309796150))
+        let (seeds, almanac) = parse_instructions(daily_input_blocks(2023, 5).unwrap()).unwrap();
+        let lowest = almanac.lowest_location_number_of(seeds.iter());
+        assert_eq!(lowest.map(|n| n.to_string()), Some(expected_answer(2023, 5, Part::One)))
     }
 
     #[test]
     fn finds_lowest_location_number_for_seed_ranges() {
-        let (seeds, almanac) = parse_updated_instructions(daily_example(5));
+        let (seeds, almanac) = parse_updated_instructions(daily_example_blocks(2023, 5).unwrap()).unwrap();
         assert_eq!(almanac.lowest_location_number_of(seeds.iter()), Some(46))
     }
 
     #[test]
+    fn maps_seed_ranges_to_location_ranges() {
+        let (seeds, almanac) = parse_updated_instructions(daily_example_blocks(2023, 5).unwrap()).unwrap();
+        let ranges = seeds.fields.into_iter().map(|field| field.range).collect();
+        assert_eq!(almanac.lowest_location_number_of_ranges(ranges), Some(46))
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "personal-inputs"), ignore = "requires personal puzzle inputs")]
     fn solves_part_two() {
-        let (seeds, almanac) = parse_updated_instructions(daily_input(5));
-        assert_eq!(almanac.lowest_location_number_of(seeds.iter()), Some(50716416))
+        let (seeds, almanac) = parse_updated_instructions(daily_input_blocks(2023, 5).unwrap()).unwrap();
+        let ranges = seeds.fields.into_iter().map(|field| field.range).collect();
+        let lowest = almanac.lowest_location_number_of_ranges(ranges);
+        assert_eq!(lowest.map(|n| n.to_string()), Some(expected_answer(2023, 5, Part::Two)))
+    }
+
+    #[test]
+    fn reports_unpaired_seed_numbers() {
+        let input = ["seeds: 79 14 55", "", "seed-to-soil map:", "50 98 2"].map(str::to_owned).to_vec();
+        assert_eq!(parse_updated_instructions(split_blocks(&input)).err().unwrap().to_string(), r#"line 1: expected pairs of seed numbers, found 3 numbers in "seeds: 79 14 55""#);
+        let model = Day05::parse(input).unwrap();
+        assert_eq!(Day05::part_two(&model).err().unwrap().to_string(), r#"line 1: expected pairs of seed numbers, found 3 numbers in "seeds: 79 14 55""#);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn reports_malformed_correlation_rules() {
+        let input = ["seeds: 79 14", "", "seed-to-soil map:", "50 98 2", "52 50"].map(str::to_owned).to_vec();
+        assert_eq!(parse_instructions(split_blocks(&input)).err().unwrap().to_string(), r#"line 5: expected 3 numbers in "52 50""#);
+    }
+
+    #[test]
+    fn reports_correlation_rules_out_of_range() {
+        let input = ["seeds: 79 14", "", "seed-to-soil map:", "50 9223372036854775807 2"].map(str::to_owned).to_vec();
+        assert_eq!(parse_instructions(split_blocks(&input)).err().unwrap().to_string(), r#"line 4: numbers out of range in "50 9223372036854775807 2""#);
+    }
+
+    fn correlation_rules() -> impl Strategy<Value=Vec<(isize, isize, isize)>> {
+        prop::collection::vec((0..1000_isize, 0..1000_isize, 1..100_isize), 1..5)
+    }
+
+    proptest! {
+        #[test]
+        fn lookup_stays_within_destination_kind(rules in correlation_rules(), seed in 0..1200_isize) {
+            let mut table = vec!["seed-to-soil map:".to_owned()];
+            table.extend(rules.iter().map(|(to, from, length)| format!("{} {} {}", to, from, length)));
+            let table = CorrelationTable::parse(&table, 0).unwrap();
+            prop_assert_eq!(table.rules.len(), rules.len());
+
+            let soil = table.lookup(Seed(seed)).unwrap();
+            let expected = rules.iter()
+                .find(|&&(_, from, length)| (from..from + length).contains(&seed))
+                .map_or(seed, |&(to, from, _)| seed - from + to);
+            prop_assert!(soil.is_a(Soil));
+            prop_assert_eq!(soil.number(), expected);
+            prop_assert_eq!(table.lookup(Soil(seed)), None);
+        }
+    }
+}
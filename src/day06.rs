@@ -1,3 +1,15 @@
+use std::fmt::Display;
+
+use aoc2023_macros::aoc;
+use itertools::Itertools;
+use tracing::debug;
+
+use crate::error::{parse_line, query_argument, AocError, Result};
+use crate::math::Quadratic;
+use crate::parse::uints;
+use crate::puzzle::{Part, Puzzle, Variant};
+use crate::validate::Rule;
+
 fn ways_to_play(time: usize) -> Vec<(usize, usize)> {
     (0..=time).map(|n| (n, n * (time - n))).collect()
 }
@@ -6,7 +18,94 @@ fn ways_to_beat_record(time: usize, record: usize) -> Vec<(usize, usize)> {
     ways_to_play(time).into_iter().filter(|&(_, distance)| distance > record).collect()
 }
 
+/// Counts the ways by solving `n * (time - n) > record`, holding strictly between the roots of the quadratic.
+fn count_ways_to_beat_record(time: usize, record: usize) -> usize {
+    Quadratic { a: -1, b: time as i128, c: -(record as i128) }.count_between_roots(0..=time as i128)
+}
+
+fn parse_numbers(line: &str) -> std::result::Result<Vec<usize>, String> {
+    match uints(line) {
+        numbers if numbers.is_empty() => Err("expected numbers".to_owned()),
+        numbers => Ok(numbers.into_iter().map(|n| n as usize).collect()),
+    }
+}
+
+fn parse_races(input: Vec<String>) -> Result<Vec<(usize, usize)>> {
+    let times = parse_line(&input, 0, parse_numbers)?;
+    let records = parse_line(&input, 1, |line| match parse_numbers(line)? {
+        records if records.len() == times.len() => Ok(records),
+        _ => Err(format!("expected {} records", times.len())),
+    })?;
+    debug!(races = times.len(), "parsed races");
+    Ok(times.into_iter().zip(records).collect())
+}
+
+fn single_race(races: &[(usize, usize)]) -> Result<(usize, usize)> {
+    let time = races.iter().map(|(time, _)| time).join("");
+    let record = races.iter().map(|(_, record)| record).join("");
+    match (time.parse(), record.parse()) {
+        (Ok(time), Ok(record)) => {
+            debug!(time, record, "merged races");
+            Ok((time, record))
+        }
+        _ => Err(AocError::unsolvable(format!("race of {} ms is too long", time))),
+    }
+}
+
+fn product_of_ways_by_enumeration(races: &[(usize, usize)]) -> Result<String> {
+    Ok(races.iter().map(|&(time, record)| ways_to_beat_record(time, record).len()).product::<usize>().to_string())
+}
+
+fn ways_of_single_race_by_enumeration(races: &[(usize, usize)]) -> Result<String> {
+    let (time, record) = single_race(races)?;
+    Ok(ways_to_beat_record(time, record).len().to_string())
+}
+
+pub struct Day06;
+
+#[aoc(day = 6)]
+impl Puzzle for Day06 {
+    const RULES: &'static [Rule] = &[Rule::MinLines(2), Rule::NoTrailingBlankLines];
+
+    const QUERIES: &'static [&'static str] = &["races", "ways <time> <record>"];
+
+    type Model = Vec<(usize, usize)>;
+
+    fn parse(input: Vec<String>) -> Result<Self::Model> {
+        parse_races(input)
+    }
+
+    fn part_one(model: &Self::Model) -> Result<impl Display> {
+        Ok(model.iter().map(|&(time, record)| count_ways_to_beat_record(time, record)).product::<usize>())
+    }
+
+    fn part_two(model: &Self::Model) -> Result<impl Display> {
+        let (time, record) = single_race(model)?;
+        Ok(count_ways_to_beat_record(time, record))
+    }
+
+    fn query(model: &Self::Model, query: &[&str]) -> Result<String> {
+        match query {
+            ["races"] => Ok(model.iter().map(|(time, record)| format!("{} ms, record {} mm", time, record)).join("\n")),
+            ["ways", time, record] => Ok(count_ways_to_beat_record(query_argument(time)?, query_argument(record)?).to_string()),
+            _ => Err(AocError::unknown_query(query)),
+        }
+    }
+
+    fn variants() -> Vec<Variant<Self::Model>> {
+        vec![
+            Variant { name: "enumeration", part: Part::One, solve: |races| product_of_ways_by_enumeration(races) },
+            Variant { name: "enumeration", part: Part::Two, solve: |races| ways_of_single_race_by_enumeration(races) },
+        ]
+    }
+}
+
+#[cfg(test)]
 mod test {
+    use crate::answers::expected_answer;
+    use crate::input::daily_input;
+    use crate::puzzle::Part;
+
     use super::*;
 
     #[test]
@@ -22,18 +121,41 @@ mod test {
     }
 
     #[test]
+    fn counts_ways_to_beat_record_by_solving_quadratic() {
+        for (time, record) in [(7, 9), (15, 40), (30, 200), (71530, 940200), (4, 4), (3, 5)] {
+            assert_eq!(count_ways_to_beat_record(time, record), ways_to_beat_record(time, record).len(), "race of {} ms", time);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "personal-inputs"), ignore = "requires personal puzzle inputs")]
+    fn parses_races() {
+        assert_eq!(parse_races(daily_input(2023, 6).unwrap()).unwrap(), vec![(42, 284), (68, 1005), (69, 1122), (85, 1341)])
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "personal-inputs"), ignore = "requires personal puzzle inputs")]
     fn solves_part_one() {
-        let solution = [(42, 284), (68, 1005), (69, 1122), (85, 1341)]
+        let solution = parse_races(daily_input(2023, 6).unwrap()).unwrap()
             .iter()
-            .fold(1, |result, &(time, record)| result * ways_to_beat_record(time, record).len());
+            .map(|&(time, record)| count_ways_to_beat_record(time, record))
+            .product::<usize>();
 
-        assert_eq!(solution, 440000)
+        assert_eq!(solution.to_string(), expected_answer(2023, 6, Part::One))
     }
 
     #[test]
+    #[cfg_attr(not(feature = "personal-inputs"), ignore = "requires personal puzzle inputs")]
     fn solves_part_two() {
-        let solution = ways_to_beat_record( 42686985, 284100511221341).len();
+        let (time, record) = single_race(&parse_races(daily_input(2023, 6).unwrap()).unwrap()).unwrap();
+        let solution = count_ways_to_beat_record(time, record);
 
-        assert_eq!(solution, 26187338)
+        assert_eq!(solution.to_string(), expected_answer(2023, 6, Part::Two))
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn reports_missing_records() {
+        let input = vec!["Time:      7  15   30".to_owned(), "Distance:  9  40".to_owned()];
+        assert_eq!(parse_races(input).unwrap_err().to_string(), r#"line 2: expected 3 records in "Distance:  9  40""#);
+    }
+}
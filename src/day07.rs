@@ -1,9 +1,15 @@
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::fmt::Display;
 
+use aoc2023_macros::aoc;
 use itertools::Itertools;
+use tracing::debug;
 
+use crate::counter::Counter;
 use crate::day07::Card::*;
+use crate::error::{parse_lines, AocError, Result};
+use crate::puzzle::Puzzle;
 use crate::day07::HandType::{FiveOfAKind, FourOfAKind, FullHouse, HighCard, OnePair, ThreeOfAKind, TwoPair};
 
 #[derive(PartialEq, Eq, Ord, PartialOrd, Debug, Copy, Clone, Hash)]
@@ -37,8 +43,8 @@ impl Card {
         HashMap::from_iter(Self::symbols().zip(Self::variants()))
     }
 
-    fn lookup(symbol: char) -> Card {
-        Self::lookup_table().get(&symbol).unwrap().clone()
+    fn lookup(symbol: char) -> std::result::Result<Card, String> {
+        Self::lookup_table().get(&symbol).copied().ok_or_else(|| format!("unknown card {}", symbol))
     }
 }
 
@@ -76,23 +82,24 @@ struct Hand {
 }
 
 impl Hand {
-    fn from_str(hand: &str) -> Self {
-        let cards: Vec<Card> = hand.chars().map(Card::lookup).collect();
-        Hand { cards: cards.try_into().unwrap() }
+    fn parse(hand: &str) -> std::result::Result<Self, String> {
+        let cards = hand.chars().map(Card::lookup).collect::<std::result::Result<Vec<Card>, String>>()?;
+        let cards = cards.try_into().map_err(|cards: Vec<Card>| format!("expected 5 cards, got {}", cards.len()))?;
+        Ok(Hand { cards })
     }
 
     fn cards(&self) -> &[Card; 5] {
         &self.cards
     }
 
-    fn organized_cards(&self) -> HashMap<&Card, usize> {
-        self.cards.iter().counts()
+    fn organized_cards(&self) -> Counter<Card> {
+        self.cards.iter().copied().collect()
     }
 
     fn evaluate(&self) -> HandType {
         let cards = self.organized_cards();
-        let counts: Vec<usize> = cards.values().sorted().rev().copied().collect();
-        let &jokers = cards.get(&JOKER).unwrap_or(&0);
+        let counts = cards.counts_descending();
+        let jokers = cards.get(&JOKER);
 
         match counts.as_slice() {
             [5, ..] => FiveOfAKind,
@@ -116,6 +123,10 @@ impl Hand {
             .unwrap_or(Ordering::Equal)
     }
 
+    fn with_jokers(&self) -> Hand {
+        Hand { cards: self.cards.map(|card| if card == J { JOKER } else { card }) }
+    }
+
     fn bid(self, amount: u32) -> Bid {
         Bid { hand: self, amount }
     }
@@ -134,32 +145,36 @@ impl Ord for Hand {
 }
 
 #[derive(PartialEq, Debug)]
-struct Bid {
+pub struct Bid {
     hand: Hand,
     amount: u32,
 }
 
 impl Bid {
-    fn from_str<T: AsRef<str>>(bid: T) -> Bid {
-        let numbers = bid.as_ref().split_whitespace();
-        let &[hand, amount] = numbers.collect::<Vec<&str>>().as_slice().try_into().unwrap();
-        Bid { hand: Hand::from_str(hand), amount: amount.parse().unwrap() }
+    fn parse(bid: &str) -> std::result::Result<Bid, String> {
+        let &[hand, amount] = bid.split_whitespace().collect::<Vec<&str>>().as_slice() else {
+            return Err("expected a hand and a bid amount".to_owned());
+        };
+        let amount = amount.parse().map_err(|_| format!("invalid bid amount {}", amount))?;
+        Ok(Hand::parse(hand)?.bid(amount))
+    }
+
+    fn with_jokers(&self) -> Bid {
+        self.hand.with_jokers().bid(self.amount)
     }
 }
 
-fn parse_bids(bids: Vec<String>) -> Vec<Bid> {
-    bids.into_iter().map(Bid::from_str).collect()
+fn parse_bids(bids: Vec<String>) -> Result<Vec<Bid>> {
+    let bids = parse_lines(&bids, 0, Bid::parse)?;
+    debug!(bids = bids.len(), "parsed bids");
+    Ok(bids)
 }
 
-fn parse_bids_using_jokers(bids: Vec<String>) -> Vec<Bid> {
-    bids.iter()
-        .map(|bid| bid.replace("J", "*"))
-        .into_iter()
-        .map(Bid::from_str)
-        .collect()
+fn parse_bids_using_jokers(bids: Vec<String>) -> Result<Vec<Bid>> {
+    parse_lines(&bids, 0, |bid| Bid::parse(&bid.replace("J", "*")))
 }
 
-fn total_winnings(bids: Vec<Bid>) -> u32 {
+fn total_winnings(bids: &[Bid]) -> u32 {
     bids.iter()
         .sorted_by_key(|b| &b.hand)
         .enumerate()
@@ -167,83 +182,154 @@ fn total_winnings(bids: Vec<Bid>) -> u32 {
         .sum()
 }
 
+pub struct Day07;
+
+#[aoc(day = 7)]
+impl Puzzle for Day07 {
+    const QUERIES: &'static [&'static str] = &["hand <cards>", "hand_with_jokers <cards>"];
+
+    type Model = Vec<Bid>;
+
+    fn parse(input: Vec<String>) -> Result<Self::Model> {
+        parse_bids(input)
+    }
+
+    fn part_one(model: &Self::Model) -> Result<impl Display> {
+        Ok(total_winnings(model))
+    }
+
+    fn part_two(model: &Self::Model) -> Result<impl Display> {
+        Ok(total_winnings(&model.iter().map(Bid::with_jokers).collect::<Vec<Bid>>()))
+    }
+
+    fn query(_model: &Self::Model, query: &[&str]) -> Result<String> {
+        match query {
+            ["hand", cards] => Ok(format!("{:?}", Hand::parse(cards).map_err(AocError::Query)?.evaluate())),
+            ["hand_with_jokers", cards] => Ok(format!("{:?}", Hand::parse(cards).map_err(AocError::Query)?.with_jokers().evaluate())),
+            _ => Err(AocError::unknown_query(query)),
+        }
+    }
+}
+
+#[cfg(test)]
 mod test {
+    use proptest::prelude::*;
+
     use crate::day07::HandType::{FourOfAKind, FullHouse, HighCard, OnePair, ThreeOfAKind, TwoPair};
+    use crate::answers::expected_answer;
     use crate::input::{daily_example, daily_input};
+    use crate::puzzle::Part;
 
     use super::*;
 
+    fn hand(cards: &str) -> Hand {
+        Hand::parse(cards).unwrap()
+    }
+
     #[test]
     fn parses_hand() {
-        assert_eq!(Hand::from_str("KT82Q").cards(), &[K, T, _8, _2, Q])
+        assert_eq!(hand("KT82Q").cards(), &[K, T, _8, _2, Q])
     }
 
     #[test]
     fn knows_each_type_of_hand() {
-        assert_eq!(Hand::from_str("AAAAA").evaluate(), FiveOfAKind);
-        assert_eq!(Hand::from_str("AA8AA").evaluate(), FourOfAKind);
-        assert_eq!(Hand::from_str("23332").evaluate(), FullHouse);
-        assert_eq!(Hand::from_str("TTT98").evaluate(), ThreeOfAKind);
-        assert_eq!(Hand::from_str("23432").evaluate(), TwoPair);
-        assert_eq!(Hand::from_str("A23A4").evaluate(), OnePair);
-        assert_eq!(Hand::from_str("23456").evaluate(), HighCard);
+        assert_eq!(hand("AAAAA").evaluate(), FiveOfAKind);
+        assert_eq!(hand("AA8AA").evaluate(), FourOfAKind);
+        assert_eq!(hand("23332").evaluate(), FullHouse);
+        assert_eq!(hand("TTT98").evaluate(), ThreeOfAKind);
+        assert_eq!(hand("23432").evaluate(), TwoPair);
+        assert_eq!(hand("A23A4").evaluate(), OnePair);
+        assert_eq!(hand("23456").evaluate(), HighCard);
     }
 
     #[test]
     fn knows_which_hand_has_stronger_first_card() {
-        assert!(Hand::from_str("33332") > Hand::from_str("2AAAA"));
-        assert!(Hand::from_str("77888") > Hand::from_str("77788"));
-        assert_eq!(Hand::from_str("23456"), Hand::from_str("23456"));
+        assert!(hand("33332") > hand("2AAAA"));
+        assert!(hand("77888") > hand("77788"));
+        assert_eq!(hand("23456"), hand("23456"));
     }
 
     #[test]
     fn knows_hands_relative_strengths() {
-        assert!(Hand::from_str("55555") > Hand::from_str("KAAAA"));
-        assert!(Hand::from_str("78888") > Hand::from_str("88877"));
-        assert!(Hand::from_str("77888") > Hand::from_str("TTA66"));
-        assert!(Hand::from_str("JJ7TT") > Hand::from_str("KKAQJ"));
-        assert!(Hand::from_str("JJ762") > Hand::from_str("K89QJ"));
-        assert!(Hand::from_str("J9762") > Hand::from_str("J975A"));
+        assert!(hand("55555") > hand("KAAAA"));
+        assert!(hand("78888") > hand("88877"));
+        assert!(hand("77888") > hand("TTA66"));
+        assert!(hand("JJ7TT") > hand("KKAQJ"));
+        assert!(hand("JJ762") > hand("K89QJ"));
+        assert!(hand("J9762") > hand("J975A"));
     }
 
     #[test]
     fn parses_bids() {
-        let bids = parse_bids(daily_example(7));
+        let bids = parse_bids(daily_example(2023, 7).unwrap()).unwrap();
         assert_eq!(bids, vec![
-            Hand::from_str("32T3K").bid(765),
-            Hand::from_str("T55J5").bid(684),
-            Hand::from_str("KK677").bid(28),
-            Hand::from_str("KTJJT").bid(220),
-            Hand::from_str("QQQJA").bid(483),
+            hand("32T3K").bid(765),
+            hand("T55J5").bid(684),
+            hand("KK677").bid(28),
+            hand("KTJJT").bid(220),
+            hand("QQQJA").bid(483),
         ])
     }
 
     #[test]
     fn calculates_total_winnings() {
-        assert_eq!(total_winnings(parse_bids(daily_example(7))), 6440)
+        assert_eq!(total_winnings(&parse_bids(daily_example(2023, 7).unwrap()).unwrap()), 6440)
     }
 
     #[test]
+    #[cfg_attr(not(feature = "personal-inputs"), ignore = "requires personal puzzle inputs")]
     fn solves_part_one() {
-        assert_eq!(total_winnings(parse_bids(daily_input(7))), 241344943)
+        assert_eq!(total_winnings(&parse_bids(daily_input(2023, 7).unwrap()).unwrap()).to_string(), expected_answer(2023, 7, Part::One))
     }
 
     #[test]
     fn knows_hand_types_containing_jokers() {
-        assert_eq!(Hand::from_str("32T3K").evaluate(), OnePair);
-        assert_eq!(Hand::from_str("KK677").evaluate(), TwoPair);
-        assert_eq!(Hand::from_str("T55*5").evaluate(), FourOfAKind);
-        assert_eq!(Hand::from_str("KT**T").evaluate(), FourOfAKind);
-        assert_eq!(Hand::from_str("QQ**A").evaluate(), FourOfAKind);
+        assert_eq!(hand("32T3K").evaluate(), OnePair);
+        assert_eq!(hand("KK677").evaluate(), TwoPair);
+        assert_eq!(hand("T55*5").evaluate(), FourOfAKind);
+        assert_eq!(hand("KT**T").evaluate(), FourOfAKind);
+        assert_eq!(hand("QQ**A").evaluate(), FourOfAKind);
     }
 
     #[test]
     fn calculates_total_winnings_using_jokers() {
-        assert_eq!(total_winnings(parse_bids_using_jokers(daily_example(7))), 5905)
+        assert_eq!(total_winnings(&parse_bids_using_jokers(daily_example(2023, 7).unwrap()).unwrap()), 5905)
     }
 
     #[test]
+    #[cfg_attr(not(feature = "personal-inputs"), ignore = "requires personal puzzle inputs")]
     fn solves_part_two() {
-        assert_eq!(total_winnings(parse_bids_using_jokers(daily_input(7))), 243101568)
+        assert_eq!(total_winnings(&parse_bids_using_jokers(daily_input(2023, 7).unwrap()).unwrap()).to_string(), expected_answer(2023, 7, Part::Two))
+    }
+
+    #[test]
+    fn reports_invalid_hands() {
+        let bids = vec!["32T3K 765".to_owned(), "T55X5 684".to_owned(), "KK67 28".to_owned()];
+        assert_eq!(parse_bids(bids.clone()).unwrap_err().to_string(), r#"line 2: unknown card X in "T55X5 684""#);
+        assert_eq!(parse_bids(bids[2..].to_vec()).unwrap_err().to_string(), r#"line 1: expected 5 cards, got 4 in "KK67 28""#);
+    }
+
+    fn any_hand() -> impl Strategy<Value=Hand> {
+        "[AKQJT98765432]{5}".prop_map(|cards| Hand::parse(&cards).unwrap())
     }
-}
\ No newline at end of file
+
+    proptest! {
+        #[test]
+        fn orders_hands_antisymmetrically(a in any_hand(), b in any_hand()) {
+            prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+            prop_assert_eq!(a.cmp(&b) == Ordering::Equal, a == b);
+        }
+
+        #[test]
+        fn orders_hands_transitively(a in any_hand(), b in any_hand(), c in any_hand()) {
+            let mut hands = [a, b, c];
+            hands.sort();
+            prop_assert!(hands[0] <= hands[1] && hands[1] <= hands[2] && hands[0] <= hands[2]);
+        }
+
+        #[test]
+        fn jokers_never_weaken_hand_type(hand in any_hand()) {
+            prop_assert!(hand.with_jokers().evaluate() >= hand.evaluate());
+        }
+    }
+}
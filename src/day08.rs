@@ -1,39 +1,32 @@
-use itertools::Itertools;
-use num::integer;
-use regex::Regex;
-
-#[derive(PartialEq, Debug, Copy, Clone)]
-enum Direction {
-    Left,
-    Right,
-}
+use std::fmt::Display;
 
-impl Direction {
-    fn from_char(c: char) -> Direction {
-        match c {
-            'L' => Direction::Left,
-            'R' => Direction::Right,
-            _ => panic!()
-        }
+use aoc2023_macros::aoc;
+use itertools::Itertools;
+use num::{BigInt, Zero};
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use tracing::debug;
+
+use crate::dot::{DotGraph, ToDot};
+use crate::error::{parse_line, parse_lines, AocError, Result};
+use crate::interner::Interner;
+use crate::math::{big_lcm_of, chinese_remainder};
+use crate::parse::parse_all;
+use crate::puzzle::{Part, Puzzle, Variant};
+use crate::validate::Rule;
+use crate::vec2::Direction;
+
+fn direction_of(c: char) -> std::result::Result<Direction, String> {
+    match c {
+        'L' => Ok(Direction::Left),
+        'R' => Ok(Direction::Right),
+        _ => Err(format!("unknown direction {}", c))
     }
 }
 
-#[derive(PartialEq, Debug, Clone)]
-struct Node(String);
-
-impl Node {
-    fn new(label: &str) -> Node {
-        Node(label.to_string())
-    }
-
-    fn is_start_node(&self) -> bool {
-        self.0.ends_with("A")
-    }
-
-    fn is_end_node(&self) -> bool {
-        self.0.ends_with("Z")
-    }
-}
+/// A node of the network, numbered by the order its label first appears in the map.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+struct Node(usize);
 
 #[derive(PartialEq, Debug)]
 struct Connection {
@@ -43,47 +36,83 @@ struct Connection {
 }
 
 impl Connection {
-    fn from_str(s: &str) -> Connection {
-        let re = Regex::new(r"(?<from>\w+) = \((?<left>\w+), (?<right>\w+)\)").unwrap();
-        let (_, [from, left, right]) = re.captures(s).unwrap().extract();
-        Connection { from: Node::new(from), left: Node::new(left), right: Node::new(right) }
+    fn parse(s: &str) -> std::result::Result<(String, String, String), String> {
+        parse_all(s, |p| {
+            let from = p.word()?;
+            p.tag(" = (")?;
+            let left = p.word()?;
+            p.tag(", ")?;
+            let right = p.word()?;
+            p.tag(")")?;
+            Ok((from.to_owned(), left.to_owned(), right.to_owned()))
+        })
     }
 
-    fn navigate(&self, direction: Direction) -> &Node {
+    fn navigate(&self, direction: Direction) -> Node {
         match direction {
-            Direction::Left => &self.left,
-            Direction::Right => &self.right,
+            Direction::Left => self.left,
+            Direction::Right => self.right,
+            Direction::Up | Direction::Down => unreachable!("instructions only go left or right"),
         }
     }
 }
 
 #[derive(PartialEq)]
-struct Network {
+pub struct Network {
+    labels: Interner,
     connections: Vec<Connection>,
 }
 
 impl Network {
-    fn from_map(lines: &[String]) -> Network {
-        let connections = lines.iter().map(|each| Connection::from_str(each)).collect();
-        Network { connections }
+    /// Reads the connections of the network, interning the nodes they leave from first so that each node indexes its
+    /// own connection, which requires every node to leave from a single connection.
+    fn from_map(lines: &[String], start: usize) -> Result<Network> {
+        let connections = parse_lines(lines, start, Connection::parse)?;
+        let mut labels = Interner::new();
+        for (index, (from, _, _)) in connections.iter().enumerate() {
+            if labels.intern(from) != index {
+                return Err(AocError::parse(start + index + 1, &lines[start + index], format!("duplicate connection from {}", from)));
+            }
+        }
+        let connections = connections.iter()
+            .map(|(from, left, right)| Connection {
+                from: Node(labels.intern(from)),
+                left: Node(labels.intern(left)),
+                right: Node(labels.intern(right)),
+            })
+            .collect();
+        Ok(Network { labels, connections })
     }
 
-    fn starting_node() -> Node {
-        Node::new("AAA")
+    fn node(&self, label: &str) -> Option<Node> {
+        self.labels.id(label).map(Node)
     }
 
-    fn take_step(&self, from_node: &Node, in_direction: Direction) -> Option<&Node> {
-        self.connections.iter()
-            .find(|c| *from_node == c.from)
-            .map(|c| c.navigate(in_direction))
+    fn label(&self, node: Node) -> &str {
+        self.labels.label(node.0)
+    }
+
+    fn is_start_node(&self, node: Node) -> bool {
+        self.label(node).ends_with('A')
+    }
+
+    fn is_end_node(&self, node: Node) -> bool {
+        self.label(node).ends_with('Z')
+    }
+
+    fn take_step(&self, from_node: Node, in_direction: Direction) -> Option<Node> {
+        self.connections.get(from_node.0).map(|c| c.navigate(in_direction))
     }
 
     fn navigate_path_to_end<'a>(&'a self, start_node: Node, instructions: &'a Instructions) -> Path<'a> {
-        Box::new(self.navigate(start_node, instructions).take_while_inclusive(|&node| !node.is_end_node()))
+        Box::new(self.navigate(start_node, instructions).take_while_inclusive(|&node| !self.is_end_node(node)))
     }
 
     fn navigate_from_start_to_end<'a>(&'a self, instructions: &'a Instructions) -> Path<'a> {
-        self.navigate_path_to_end(Self::starting_node(), instructions)
+        match self.node("AAA") {
+            Some(start) => self.navigate_path_to_end(start, instructions),
+            None => Box::new(std::iter::empty()),
+        }
     }
 
     fn navigate_from_start_to_end_simultaneously<'a>(&'a self, instructions: &'a Instructions) -> Vec<Path<'a>> {
@@ -94,81 +123,212 @@ impl Network {
 
     fn start_nodes(&self) -> impl Iterator<Item=Node> + '_ {
         self.connections.iter()
-            .map(|c| c.from.clone())
-            .filter(|node| node.is_start_node())
+            .map(|c| c.from)
+            .filter(|&node| self.is_start_node(node))
     }
 
     fn navigate<'a>(&'a self, from_node: Node, instructions: &'a Instructions) -> Path<'a> {
         Box::new(instructions.iter().cycle()
             .scan(from_node, |node, &direction| {
-                self.take_step(node, direction).map(|next| {
-                    node.clone_from(next);
-                    next
-                })
+                self.take_step(*node, direction).inspect(|&next| *node = next)
             })
         )
     }
 }
 
-type Path<'a> = Box<dyn Iterator<Item=&'a Node> + 'a>;
+impl Serialize for Network {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct LabelledConnection<'a> {
+            from: &'a str,
+            left: &'a str,
+            right: &'a str,
+        }
+
+        let connections: Vec<LabelledConnection> = self.connections.iter()
+            .map(|c| LabelledConnection { from: self.label(c.from), left: self.label(c.left), right: self.label(c.right) })
+            .collect();
+        let mut network = serializer.serialize_struct("Network", 1)?;
+        network.serialize_field("connections", &connections)?;
+        network.end()
+    }
+}
+
+impl ToDot for Network {
+    fn to_dot(&self) -> DotGraph {
+        let mut graph = DotGraph::directed("network");
+        for &Connection { from, left, right } in &self.connections {
+            let (from_label, left_label, right_label) = (self.label(from), self.label(left), self.label(right));
+            if self.is_start_node(from) {
+                graph.node(from_label, &[("shape", "box"), ("color", "green")]);
+            } else if self.is_end_node(from) {
+                graph.node(from_label, &[("shape", "doublecircle"), ("color", "red")]);
+            }
+            graph.edge(from_label, left_label, &[("label", "L")]).edge(from_label, right_label, &[("label", "R")]);
+        }
+        graph
+    }
+}
+
+type Path<'a> = Box<dyn Iterator<Item=Node> + 'a>;
 
 type Instructions = Vec<Direction>;
 
-fn parse_instructions(input: &str) -> Instructions {
-    input.chars().map(|c| Direction::from_char(c)).collect()
+fn parse_instructions(input: &str) -> std::result::Result<Instructions, String> {
+    input.chars().map(direction_of).collect()
+}
+
+fn parse_input(input: Vec<String>) -> Result<(Instructions, Network)> {
+    let instructions = parse_line(&input, 0, parse_instructions)?;
+    let network = Network::from_map(&input, 2)?;
+    debug!(instructions = instructions.len(), connections = network.connections.len(), "parsed network");
+    Ok((instructions, network))
+}
+
+fn total_steps(input: Vec<String>) -> Result<usize> {
+    let (instructions, network) = parse_input(input)?;
+    steps_to_end(&instructions, &network)
+}
+
+fn steps_along(network: &Network, path: Path) -> Result<usize> {
+    match path.enumerate().last() {
+        Some((steps, node)) if network.is_end_node(node) => Ok(steps + 1),
+        _ => Err(AocError::unsolvable("path leads to a dead end")),
+    }
+}
+
+fn steps_to_end(instructions: &Instructions, network: &Network) -> Result<usize> {
+    steps_along(network, network.navigate_from_start_to_end(instructions))
 }
 
-fn parse_input(input: Vec<String>) -> (Instructions, Network) {
-    let instructions = parse_instructions(&input[0]);
-    let network = Network::from_map(&input[2..]);
-    (instructions, network)
+fn total_steps_as_ghost(input: Vec<String>) -> Result<BigInt> {
+    let (instructions, network) = parse_input(input)?;
+    steps_to_end_as_ghost(&instructions, &network)
 }
 
-fn total_steps(input: Vec<String>) -> usize {
-    let (instructions, network) = parse_input(input);
-    let path = network.navigate_from_start_to_end(&instructions);
-    path.count()
+/// Steps for all ghosts to stand on end nodes together, as a big integer since the least common multiple of their
+/// paths can outgrow `usize` on adversarial inputs.
+fn steps_to_end_as_ghost(instructions: &Instructions, network: &Network) -> Result<BigInt> {
+    let paths = network.navigate_from_start_to_end_simultaneously(instructions);
+    let steps = paths.into_iter().map(|path| steps_along(network, path)).collect::<Result<Vec<usize>>>()?;
+    debug!(?steps, "ghosts reached their end nodes");
+    Ok(if steps.is_empty() { BigInt::zero() } else { big_lcm_of(steps) })
 }
 
-fn total_steps_as_ghost(input: Vec<String>) -> usize {
-    let (instructions, network) = parse_input(input);
-    let paths = network.navigate_from_start_to_end_simultaneously(&instructions);
-    paths.into_iter().map(|p| p.count()).reduce(|a, b| integer::lcm(a, b)).unwrap_or(0)
+/// Steps until a ghost first reaches an end node, and steps it then takes to reach one again.
+fn end_cycle(instructions: &Instructions, network: &Network, start: Node) -> Result<(usize, usize)> {
+    let states = instructions.len() * network.connections.len();
+    let mut ends = network.navigate(start, instructions).take(2 * states + 1)
+        .enumerate()
+        .filter(|&(_, node)| network.is_end_node(node))
+        .map(|(step, _)| step + 1);
+    match (ends.next(), ends.next()) {
+        (Some(first), Some(second)) => Ok((first, second - first)),
+        _ => Err(AocError::unsolvable("ghost never comes back to an end node")),
+    }
+}
+
+/// Aligns the cycles of the ghosts with the Chinese remainder theorem, which does not assume, unlike the least common
+/// multiple, that each ghost takes as many steps to first reach an end node as to reach one again. Each ghost must
+/// however come across a single end node along its cycle.
+fn steps_to_end_by_cycle_alignment((instructions, network): &(Instructions, Network)) -> Result<String> {
+    let cycles = network.start_nodes().map(|start| end_cycle(instructions, network, start)).collect::<Result<Vec<_>>>()?;
+    debug!(?cycles, "ghosts cycle through their end nodes");
+    let (residue, modulus) = chinese_remainder(cycles.iter().map(|&(first, period)| ((first % period) as i128, period as i128)))
+        .ok_or_else(|| AocError::unsolvable("ghosts never reach end nodes together"))?;
+    let earliest = cycles.iter().map(|&(first, _)| first as i128).max().unwrap_or(0);
+    let steps = if residue >= earliest { residue } else { residue + (earliest - residue + modulus - 1) / modulus * modulus };
+    Ok(steps.to_string())
+}
+
+pub struct Day08;
+
+#[aoc(day = 8)]
+impl Puzzle for Day08 {
+    const RULES: &'static [Rule] = &[Rule::MinLines(3), Rule::NoTrailingBlankLines];
+
+    const QUERIES: &'static [&'static str] = &["instructions", "start_nodes", "take_step <node> <L|R>", "dot"];
+
+    type Model = (Instructions, Network);
+
+    fn parse(input: Vec<String>) -> Result<Self::Model> {
+        parse_input(input)
+    }
+
+    fn part_one((instructions, network): &Self::Model) -> Result<impl Display> {
+        steps_to_end(instructions, network)
+    }
+
+    fn part_two((instructions, network): &Self::Model) -> Result<impl Display> {
+        steps_to_end_as_ghost(instructions, network)
+    }
+
+    fn query((instructions, network): &Self::Model, query: &[&str]) -> Result<String> {
+        match query {
+            ["instructions"] => Ok(format!("{} directions", instructions.len())),
+            ["start_nodes"] => Ok(network.start_nodes().map(|node| network.label(node)).join(" ")),
+            ["take_step", node, direction] => {
+                let direction = parse_instructions(direction).map_err(AocError::Query)?;
+                let &[direction] = direction.as_slice() else { return Err(AocError::Query("expected a single direction".to_owned())) };
+                let next = network.node(node).and_then(|node| network.take_step(node, direction))
+                    .ok_or_else(|| AocError::Query(format!("no node {}", node)))?;
+                Ok(network.label(next).to_owned())
+            }
+            ["dot"] => Ok(network.to_dot().to_string().trim_end().to_owned()),
+            _ => Err(AocError::unknown_query(query)),
+        }
+    }
+
+    fn variants() -> Vec<Variant<Self::Model>> {
+        vec![Variant { name: "cycle alignment", part: Part::Two, solve: steps_to_end_by_cycle_alignment }]
+    }
 }
 
+#[cfg(test)]
 mod test {
-    use crate::day08::Direction::{Left, Right};
+    use insta::assert_yaml_snapshot;
+
+    use crate::vec2::Direction::{Left, Right};
+    use crate::answers::expected_answer;
     use crate::input::{daily_example, daily_input};
+    use crate::puzzle::Part;
 
     use super::*;
 
     #[test]
     fn parses_instructions() {
-        let (instructions, _) = parse_input(daily_example(8));
+        let (instructions, _) = parse_input(daily_example(2023, 8).unwrap()).unwrap();
 
         assert_eq!(instructions, vec![Left, Left, Right])
     }
 
     #[test]
-    fn parses_network_of_nodes() {
-        let (_, network) = parse_input(daily_example(8));
+    fn parses_instructions_and_network() {
+        assert_yaml_snapshot!(parse_input(daily_example(2023, 8).unwrap()).unwrap());
+    }
 
-        assert_eq!(network.take_step(&Node::new("AAA"), Left), Some(&Node::new("BBB")));
-        assert_eq!(network.take_step(&Node::new("AAA"), Right), Some(&Node::new("BBB")));
-        assert_eq!(network.take_step(&Node::new("BBB"), Left), Some(&Node::new("AAA")));
-        assert_eq!(network.take_step(&Node::new("BBB"), Right), Some(&Node::new("ZZZ")));
-        assert_eq!(network.take_step(&Node::new("ZZZ"), Left), Some(&Node::new("ZZZ")));
-        assert_eq!(network.take_step(&Node::new("ZZZ"), Right), Some(&Node::new("ZZZ")));
+    #[test]
+    fn parses_network_of_nodes() {
+        let (_, network) = parse_input(daily_example(2023, 8).unwrap()).unwrap();
+        let node = |label| network.node(label).unwrap();
+
+        assert_eq!(network.take_step(node("AAA"), Left), Some(node("BBB")));
+        assert_eq!(network.take_step(node("AAA"), Right), Some(node("BBB")));
+        assert_eq!(network.take_step(node("BBB"), Left), Some(node("AAA")));
+        assert_eq!(network.take_step(node("BBB"), Right), Some(node("ZZZ")));
+        assert_eq!(network.take_step(node("ZZZ"), Left), Some(node("ZZZ")));
+        assert_eq!(network.take_step(node("ZZZ"), Right), Some(node("ZZZ")));
     }
 
     #[test]
     fn counts_step_to_navigate_network() {
-        assert_eq!(total_steps(daily_example(8)), 6);
+        assert_eq!(total_steps(daily_example(2023, 8).unwrap()).unwrap(), 6);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "personal-inputs"), ignore = "requires personal puzzle inputs")]
     fn solves_part_one() {
-        assert_eq!(total_steps(daily_input(8)), 12737);
+        assert_eq!(total_steps(daily_input(2023, 8).unwrap()).unwrap().to_string(), expected_answer(2023, 8, Part::One));
     }
 
     const PART_TWO_EXAMPLES: &str = r#"
@@ -187,11 +347,45 @@ XXX = (XXX, XXX)
     #[test]
     fn navigates_as_ghost() {
         let input = PART_TWO_EXAMPLES.trim_start().trim_end().lines().map(str::to_owned).collect();
-        assert_eq!(total_steps_as_ghost(input), 6);
+        assert_eq!(total_steps_as_ghost(input).unwrap(), BigInt::from(6));
+    }
+
+    #[test]
+    fn aligns_ghost_cycles() {
+        let input = PART_TWO_EXAMPLES.trim_start().trim_end().lines().map(str::to_owned).collect();
+        assert_eq!(steps_to_end_by_cycle_alignment(&parse_input(input).unwrap()).unwrap(), "6");
     }
 
     #[test]
+    #[cfg_attr(not(feature = "personal-inputs"), ignore = "requires personal puzzle inputs")]
     fn solves_part_two() {
-        assert_eq!(total_steps_as_ghost(daily_input(8)), 9064949303801);
+        assert_eq!(total_steps_as_ghost(daily_input(2023, 8).unwrap()).unwrap().to_string(), expected_answer(2023, 8, Part::Two));
+    }
+
+    #[test]
+    fn draws_network_as_a_graph() {
+        let (_, network) = parse_input(daily_example(2023, 8).unwrap()).unwrap();
+        let dot = network.to_dot().to_string();
+        assert!(dot.contains("  \"AAA\" [shape=\"box\", color=\"green\"];\n"));
+        assert!(dot.contains("  \"BBB\" -> \"ZZZ\" [label=\"R\"];\n"));
+        assert_eq!(dot.lines().filter(|line| line.contains("->")).count(), 6);
+    }
+
+    #[test]
+    fn rejects_nodes_leaving_from_several_connections() {
+        let input = ["L", "", "AAA = (BBB, BBB)", "BBB = (AAA, ZZZ)", "AAA = (ZZZ, ZZZ)", "ZZZ = (ZZZ, ZZZ)"].map(str::to_owned).to_vec();
+        assert_eq!(parse_input(input).err().unwrap().to_string(), r#"line 5: duplicate connection from AAA in "AAA = (ZZZ, ZZZ)""#);
+    }
+
+    #[test]
+    fn follows_connections_of_nodes_met_before_their_own_line() {
+        let input = ["R", "", "AAA = (BBB, ZZZ)", "ZZZ = (ZZZ, ZZZ)", "BBB = (AAA, AAA)"].map(str::to_owned).to_vec();
+        assert_eq!(total_steps(input).unwrap(), 1);
+    }
+
+    #[test]
+    fn cannot_navigate_to_missing_nodes() {
+        let input = ["L", "", "AAA = (BBB, BBB)"].map(str::to_owned).to_vec();
+        assert_eq!(total_steps(input).unwrap_err().to_string(), "no solution: path leads to a dead end");
     }
 }
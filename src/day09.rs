@@ -0,0 +1,155 @@
+use std::fmt::Display;
+
+use aoc2023_macros::aoc;
+use itertools::Itertools;
+use tracing::debug;
+
+use crate::error::{parse_lines, query_argument, AocError, Result};
+use crate::math::extrapolate;
+use crate::parse::{parse_all, Parser};
+use crate::puzzle::{Part, Puzzle, Variant};
+use crate::validate::Rule;
+
+type History = Vec<i64>;
+
+fn parse_history(line: &str) -> std::result::Result<History, String> {
+    parse_all(line, Parser::integers)
+}
+
+fn parse_report(input: &[String]) -> Result<Vec<History>> {
+    let histories = parse_lines(input, 0, parse_history)?;
+    debug!(histories = histories.len(), "parsed report");
+    Ok(histories)
+}
+
+/// The history followed by its successive sequences of differences, down to a sequence of zeroes.
+fn differences(history: &[i64]) -> Vec<Vec<i64>> {
+    let mut sequences = vec![history.to_vec()];
+    while let Some(last) = sequences.last().filter(|sequence| sequence.iter().any(|&n| n != 0)) {
+        sequences.push(last.iter().tuple_windows().map(|(a, b)| b - a).collect());
+    }
+    sequences
+}
+
+fn next_value(history: &[i64]) -> i64 {
+    differences(history).iter().filter_map(|sequence| sequence.last()).sum()
+}
+
+fn previous_value(history: &[i64]) -> i64 {
+    differences(history).iter().rev().filter_map(|sequence| sequence.first()).fold(0, |below, &first| first - below)
+}
+
+fn sum_of_next_values(histories: &[History]) -> i64 {
+    histories.iter().map(|history| next_value(history)).sum()
+}
+
+fn sum_of_previous_values(histories: &[History]) -> i64 {
+    histories.iter().map(|history| previous_value(history)).sum()
+}
+
+fn sum_extrapolated_by_newton(histories: &[History], at: impl Fn(&History) -> i128) -> Result<String> {
+    let samples = |history: &History| history.iter().map(|&n| n as i128).collect::<Vec<_>>();
+    Ok(histories.iter().map(|history| extrapolate(&samples(history), at(history))).sum::<i128>().to_string())
+}
+
+pub struct Day09;
+
+#[aoc(day = 9)]
+impl Puzzle for Day09 {
+    const RULES: &'static [Rule] = &[Rule::MinLines(1), Rule::NoTrailingBlankLines];
+
+    const QUERIES: &'static [&'static str] = &["differences <line>"];
+
+    type Model = Vec<History>;
+
+    fn parse(input: Vec<String>) -> Result<Self::Model> {
+        parse_report(&input)
+    }
+
+    fn part_one(model: &Self::Model) -> Result<impl Display> {
+        Ok(sum_of_next_values(model))
+    }
+
+    fn part_two(model: &Self::Model) -> Result<impl Display> {
+        Ok(sum_of_previous_values(model))
+    }
+
+    fn query(model: &Self::Model, query: &[&str]) -> Result<String> {
+        match query {
+            ["differences", line] => {
+                let line: usize = query_argument(line)?;
+                let history = line.checked_sub(1).and_then(|index| model.get(index))
+                    .ok_or_else(|| AocError::Query(format!("no history on line {}", line)))?;
+                Ok(differences(history).iter().map(|sequence| sequence.iter().join(" ")).join("\n"))
+            }
+            _ => Err(AocError::unknown_query(query)),
+        }
+    }
+
+    fn variants() -> Vec<Variant<Self::Model>> {
+        vec![
+            Variant { name: "newton", part: Part::One, solve: |histories| sum_extrapolated_by_newton(histories, |history| history.len() as i128) },
+            Variant { name: "newton", part: Part::Two, solve: |histories| sum_extrapolated_by_newton(histories, |_| -1) },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::input::daily_example;
+
+    use super::*;
+
+    fn example() -> Vec<History> {
+        parse_report(&daily_example(2023, 9).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn parses_histories() {
+        assert_eq!(example()[2], vec![10, 13, 16, 21, 30, 45]);
+    }
+
+    #[test]
+    fn computes_sequences_of_differences() {
+        assert_eq!(differences(&[10, 13, 16, 21, 30, 45]), vec![
+            vec![10, 13, 16, 21, 30, 45],
+            vec![3, 3, 5, 9, 15],
+            vec![0, 2, 4, 6],
+            vec![2, 2, 2],
+            vec![0, 0],
+        ]);
+    }
+
+    #[test]
+    fn extrapolates_next_values() {
+        let next: Vec<i64> = example().iter().map(|history| next_value(history)).collect();
+        assert_eq!(next, vec![18, 28, 68]);
+        assert_eq!(sum_of_next_values(&example()), 114);
+    }
+
+    #[test]
+    fn extrapolates_previous_values() {
+        let previous: Vec<i64> = example().iter().map(|history| previous_value(history)).collect();
+        assert_eq!(previous, vec![-3, 0, 5]);
+        assert_eq!(sum_of_previous_values(&example()), 2);
+    }
+
+    #[test]
+    fn extrapolates_by_newton_forward_differences() {
+        assert_eq!(sum_extrapolated_by_newton(&example(), |history| history.len() as i128).unwrap(), "114");
+        assert_eq!(sum_extrapolated_by_newton(&example(), |_| -1).unwrap(), "2");
+    }
+
+    #[test]
+    fn handles_negative_and_single_value_histories() {
+        assert_eq!(parse_history("-4 -2 0").unwrap(), vec![-4, -2, 0]);
+        assert_eq!((next_value(&[7]), previous_value(&[7])), (7, 7));
+        assert_eq!((next_value(&[-4, -2, 0]), previous_value(&[-4, -2, 0])), (2, -6));
+    }
+
+    #[test]
+    fn reports_malformed_histories() {
+        let error = parse_report(&["0 3 6".to_owned(), "1 three 6".to_owned()]).unwrap_err();
+        assert_eq!(error.to_string(), r#"line 2: unexpected " three 6" in "1 three 6""#);
+    }
+}
@@ -0,0 +1,377 @@
+use std::fmt::Display;
+
+use aoc2023_macros::aoc;
+use itertools::Itertools;
+use tracing::debug;
+
+use crate::error::{AocError, Result};
+use crate::grid::{FromChar, Grid};
+use crate::polygon::interior_points;
+use crate::puzzle::{Part, Puzzle, Variant};
+use crate::render::{render_grid, Color, Style};
+use crate::svg::{Paint, Svg};
+use crate::validate::Rule;
+use crate::vec2::Direction::{Down, Left, Right, Up};
+use crate::vec2::{Direction, Vec2};
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+enum Tile {
+    Pipe(Direction, Direction),
+    Ground,
+    Start,
+}
+
+/// Order the two directions of a pipe come in, so that pipes of the same shape compare equal.
+const PIPE_ORDER: [Direction; 4] = [Up, Left, Right, Down];
+
+impl FromChar for Tile {
+    fn from_char(c: char) -> std::result::Result<Tile, String> {
+        match c {
+            '|' => Ok(Tile::Pipe(Up, Down)),
+            '-' => Ok(Tile::Pipe(Left, Right)),
+            'L' => Ok(Tile::Pipe(Up, Right)),
+            'J' => Ok(Tile::Pipe(Up, Left)),
+            '7' => Ok(Tile::Pipe(Left, Down)),
+            'F' => Ok(Tile::Pipe(Right, Down)),
+            '.' => Ok(Tile::Ground),
+            'S' => Ok(Tile::Start),
+            _ => Err(format!("unknown tile {}", c)),
+        }
+    }
+}
+
+impl Tile {
+    fn visual(self) -> char {
+        match self {
+            Tile::Pipe(Up, Down) => '│',
+            Tile::Pipe(Left, Right) => '─',
+            Tile::Pipe(Up, Right) => '└',
+            Tile::Pipe(Up, Left) => '┘',
+            Tile::Pipe(Left, Down) => '┐',
+            Tile::Pipe(Right, Down) => '┌',
+            Tile::Pipe(_, _) => '?',
+            Tile::Ground => '.',
+            Tile::Start => 'S',
+        }
+    }
+
+    fn connects(self, direction: Direction) -> bool {
+        matches!(self, Tile::Pipe(a, b) if a == direction || b == direction)
+    }
+
+    /// Direction a pipe leads to when entered heading `heading`, if it lets it in.
+    fn exit(self, heading: Direction) -> Option<Direction> {
+        match self {
+            Tile::Pipe(a, b) if a == heading.opposite() => Some(b),
+            Tile::Pipe(a, b) if b == heading.opposite() => Some(a),
+            _ => None,
+        }
+    }
+}
+
+/// Where a tile lies relative to the main loop.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+enum Region {
+    Loop,
+    Inside,
+    Outside,
+}
+
+pub struct Maze {
+    grid: Grid<Tile>,
+    start: Vec2,
+}
+
+impl Maze {
+    fn parse(input: &[String]) -> Result<Maze> {
+        let grid: Grid<Tile> = Grid::parse(input)?;
+        let (x, y) = grid.positions_of(&Tile::Start).next().ok_or_else(|| AocError::unsolvable("maze has no start tile"))?;
+        debug!(width = grid.width(), height = grid.height(), x, y, "parsed maze");
+        Ok(Maze { grid, start: Vec2::new(x as isize, y as isize) })
+    }
+
+    fn tile(&self, position: Vec2) -> Option<Tile> {
+        self.grid.get(position.x, position.y).copied()
+    }
+
+    /// Positions of the pipes of the loop leaving the start tile heading `heading`, starting with it, provided it comes
+    /// back to the start tile from its `back` side.
+    fn trace(&self, mut heading: Direction, back: Direction) -> Result<Vec<Vec2>> {
+        let mut pipes = vec![self.start];
+        let mut position = self.start + heading.offset();
+        while position != self.start {
+            pipes.push(position);
+            heading = self.tile(position).and_then(|tile| tile.exit(heading))
+                .ok_or_else(|| AocError::unsolvable(format!("loop breaks at {},{}", position.x, position.y)))?;
+            position += heading.offset();
+        }
+        match heading.opposite() == back {
+            true => Ok(pipes),
+            false => Err(AocError::unsolvable("loop comes back to start tile from another pipe")),
+        }
+    }
+
+    /// Pipe hidden under the start tile and the loop going through it, trying each pair of neighboring pipes that
+    /// connect back to the start tile until one traces a loop, as more than two of them may.
+    fn start_loop(&self) -> Result<(Tile, Vec<Vec2>)> {
+        let connected: Vec<Direction> = PIPE_ORDER.into_iter()
+            .filter(|&direction| self.tile(self.start + direction.offset()).is_some_and(|tile| tile.connects(direction.opposite())))
+            .collect();
+        let (start_pipe, pipes) = connected.iter().tuple_combinations()
+            .map(|(&a, &b)| self.trace(a, b).map(|pipes| (Tile::Pipe(a, b), pipes)))
+            .find_or_first(Result::is_ok)
+            .unwrap_or_else(|| Err(AocError::unsolvable(format!("start tile connects to {} pipes", connected.len()))))?;
+        debug!(length = pipes.len(), ?start_pipe, "traced main loop");
+        Ok((start_pipe, pipes))
+    }
+
+    fn start_pipe(&self) -> Result<Tile> {
+        Ok(self.start_loop()?.0)
+    }
+
+    /// Positions of the pipes of the loop going through the start tile, starting with it.
+    fn main_loop(&self) -> Result<Vec<Vec2>> {
+        Ok(self.start_loop()?.1)
+    }
+
+    fn farthest_distance(&self) -> Result<usize> {
+        Ok(self.main_loop()?.len() / 2)
+    }
+
+    /// Tiles enclosed by the loop, counted with Pick's theorem from the area the loop encloses and the number of pipes
+    /// along it, tiles squeezed between pipes being outside as they should.
+    fn enclosed_tiles(&self) -> Result<usize> {
+        Ok(interior_points(&self.main_loop()?))
+    }
+
+    /// Sides of the loop each tile lies on, found by scanning rows and flipping from outside to inside whenever
+    /// crossing a pipe of the loop going upwards: `|`, `L` and `J`, a `F--J` or `L--7` run of pipes crossing once all
+    /// together.
+    fn regions(&self) -> Result<Grid<Region>> {
+        let mut regions = self.grid.map(|_| Region::Outside);
+        let (start_pipe, pipes) = self.start_loop()?;
+        pipes.iter().for_each(|pipe| regions[(pipe.x as usize, pipe.y as usize)] = Region::Loop);
+        for y in 0..self.grid.height() {
+            let mut inside = false;
+            for x in 0..self.grid.width() {
+                let tile = if self.grid[(x, y)] == Tile::Start { start_pipe } else { self.grid[(x, y)] };
+                match regions[(x, y)] {
+                    Region::Loop if tile.connects(Up) => inside = !inside,
+                    Region::Outside if inside => regions[(x, y)] = Region::Inside,
+                    _ => {}
+                }
+            }
+        }
+        Ok(regions)
+    }
+
+    fn enclosed_tiles_by_crossings(&self) -> Result<usize> {
+        Ok(self.regions()?.cells().filter(|&(_, &region)| region == Region::Inside).count())
+    }
+
+    /// The maze drawn with box-drawing pipes, the loop in bold yellow, enclosed tiles in green and others in blue.
+    fn render(&self) -> Result<String> {
+        let regions = self.regions()?;
+        let rows: Vec<String> = self.grid.rows().map(|row| row.iter().map(|tile| tile.visual()).collect()).collect();
+        Ok(render_grid(&rows, |x, y, _| match regions[(x, y)] {
+            Region::Loop => Style::fg(Color::Yellow).bold(),
+            Region::Inside => Style::fg(Color::Green),
+            Region::Outside => Style::fg(Color::Blue),
+        }))
+    }
+
+    /// The maze as an SVG image, enclosed tiles filled in green and the loop drawn through the centers of its pipes.
+    fn to_svg(&self) -> Result<Svg> {
+        let regions = self.regions()?;
+        let mut svg = Svg::new(10);
+        svg.grid(&regions, |_, &region| (region == Region::Inside).then(|| Paint::fill(Color::Green.name())))
+            .polygon(&self.main_loop()?, &Paint::stroke(Color::Yellow.name(), 0.3));
+        Ok(svg)
+    }
+}
+
+pub struct Day10;
+
+#[aoc(day = 10)]
+impl Puzzle for Day10 {
+    const RULES: &'static [Rule] = &[Rule::MinLines(1), Rule::Rectangular, Rule::Charset("|-LJ7F.S"), Rule::NoTrailingBlankLines];
+
+    const QUERIES: &'static [&'static str] = &["start", "loop", "render", "svg"];
+
+    type Model = Maze;
+
+    fn parse(input: Vec<String>) -> Result<Self::Model> {
+        Maze::parse(&input)
+    }
+
+    fn part_one(model: &Self::Model) -> Result<impl Display> {
+        model.farthest_distance()
+    }
+
+    fn part_two(model: &Self::Model) -> Result<impl Display> {
+        model.enclosed_tiles()
+    }
+
+    fn query(model: &Self::Model, query: &[&str]) -> Result<String> {
+        match query {
+            ["start"] => Ok(format!("{},{}: {:?}", model.start.x, model.start.y, model.start_pipe()?)),
+            ["loop"] => Ok(model.main_loop()?.iter().map(|pipe| format!("{},{}", pipe.x, pipe.y)).join(" ")),
+            ["render"] => Ok(model.render()?.trim_end().to_owned()),
+            ["svg"] => Ok(model.to_svg()?.to_string().trim_end().to_owned()),
+            _ => Err(AocError::unknown_query(query)),
+        }
+    }
+
+    fn variants() -> Vec<Variant<Self::Model>> {
+        vec![Variant { name: "crossings", part: Part::Two, solve: |maze| maze.enclosed_tiles_by_crossings().map(|tiles| tiles.to_string()) }]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::input::daily_example;
+
+    use super::*;
+
+    fn maze(lines: &[&str]) -> Maze {
+        Maze::parse(&lines.iter().map(|line| line.to_string()).collect::<Vec<_>>()).unwrap()
+    }
+
+    fn example() -> Maze {
+        Maze::parse(&daily_example(2023, 10).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn finds_start_tile() {
+        assert_eq!(example().start, Vec2::new(0, 2));
+    }
+
+    #[test]
+    fn infers_pipe_under_start_tile() {
+        assert_eq!(example().start_pipe().unwrap(), Tile::Pipe(Right, Down));
+        assert_eq!(maze(&["-L|F7", "7S-7|", "L|7||", "-L-J|", "L|-JF"]).start_pipe().unwrap(), Tile::Pipe(Right, Down));
+    }
+
+    #[test]
+    fn infers_start_pipe_closing_the_loop_among_more_connecting_pipes() {
+        let maze = maze(&[".F-7.", "-S.|.", ".L-J."]);
+        assert_eq!(maze.start_pipe().unwrap(), Tile::Pipe(Up, Down));
+        assert_eq!(maze.farthest_distance().unwrap(), 4);
+    }
+
+    #[test]
+    fn infers_start_pipes_equal_to_parsed_pipes_of_the_same_shape() {
+        let shapes = [
+            (&["F7", "S|", "LJ"][..], '|'),
+            (&["FS7", "L-J"][..], '-'),
+            (&["F7", "SJ"][..], 'L'),
+            (&["F7", "LS"][..], 'J'),
+            (&["F-S", "L-J"][..], '7'),
+            (&["S7", "LJ"][..], 'F'),
+        ];
+        for (lines, shape) in shapes {
+            assert_eq!(maze(lines).start_pipe().unwrap(), Tile::from_char(shape).unwrap(), "start of {:?}", lines);
+        }
+        assert_eq!(example().start_pipe().unwrap(), Tile::from_char('F').unwrap());
+    }
+
+    #[test]
+    fn traces_main_loop() {
+        let pipes = maze(&["-L|F7", "7S-7|", "L|7||", "-L-J|", "L|-JF"]).main_loop().unwrap();
+        assert_eq!(pipes.len(), 8);
+        assert_eq!(&pipes[..3], &[Vec2::new(1, 1), Vec2::new(2, 1), Vec2::new(3, 1)]);
+    }
+
+    #[test]
+    fn measures_distance_to_farthest_pipe() {
+        assert_eq!(maze(&["-L|F7", "7S-7|", "L|7||", "-L-J|", "L|-JF"]).farthest_distance().unwrap(), 4);
+        assert_eq!(example().farthest_distance().unwrap(), 8);
+    }
+
+    const ENCLOSING_LOOP: &[&str] = &[
+        "...........",
+        ".S-------7.",
+        ".|F-----7|.",
+        ".||.....||.",
+        ".||.....||.",
+        ".|L-7.F-J|.",
+        ".|..|.|..|.",
+        ".L--J.L--J.",
+        "...........",
+    ];
+
+    const SQUEEZING_LOOP: &[&str] = &[
+        "..........",
+        ".S------7.",
+        ".|F----7|.",
+        ".||....||.",
+        ".||....||.",
+        ".|L-7F-J|.",
+        ".|..||..|.",
+        ".L--JL--J.",
+        "..........",
+    ];
+
+    const LARGER_LOOP: &[&str] = &[
+        ".F----7F7F7F7F-7....",
+        ".|F--7||||||||FJ....",
+        ".||.FJ||||||||L7....",
+        "FJL7L7LJLJ||LJ.L-7..",
+        "L--J.L7...LJS7F-7L7.",
+        "....F-J..F7FJ|L7L7L7",
+        "....L7.F7||L7|.L7L7|",
+        ".....|FJLJ|FJ|F7|.LJ",
+        "....FJL-7.||.||||...",
+        "....L---J.LJ.LJLJ...",
+    ];
+
+    const LOOP_AMONG_JUNK: &[&str] = &[
+        "FF7FSF7F7F7F7F7F---7",
+        "L|LJ||||||||||||F--J",
+        "FL-7LJLJ||||||LJL-77",
+        "F--JF--7||LJLJ7F7FJ-",
+        "L---JF-JLJ.||-FJLJJ7",
+        "|F|F-JF---7F7-L7L|7|",
+        "|FFJF7L7F-JF7|JL---7",
+        "7-L-JL7||F7|L7F-7F7|",
+        "L.L7LFJ|||||FJL7||LJ",
+        "L7JLJL-JLJLJL--JLJ.L",
+    ];
+
+    #[test]
+    fn counts_enclosed_tiles_with_picks_theorem() {
+        let enclosed: Vec<usize> = [ENCLOSING_LOOP, SQUEEZING_LOOP, LARGER_LOOP, LOOP_AMONG_JUNK].iter()
+            .map(|lines| maze(lines).enclosed_tiles().unwrap())
+            .collect();
+        assert_eq!(enclosed, vec![4, 4, 8, 10]);
+    }
+
+    #[test]
+    fn counts_enclosed_tiles_by_crossing_pipes() {
+        let enclosed: Vec<usize> = [ENCLOSING_LOOP, SQUEEZING_LOOP, LARGER_LOOP, LOOP_AMONG_JUNK].iter()
+            .map(|lines| maze(lines).enclosed_tiles_by_crossings().unwrap())
+            .collect();
+        assert_eq!(enclosed, vec![4, 4, 8, 10]);
+    }
+
+    #[test]
+    fn highlights_loop_and_enclosed_tiles() {
+        let rendered = maze(SQUEEZING_LOOP).render().unwrap();
+        assert_eq!(rendered.lines().nth(3).unwrap(), "\x1b[34m.\x1b[0m\x1b[1;33m││\x1b[0m\x1b[34m....\x1b[0m\x1b[1;33m││\x1b[0m\x1b[34m.\x1b[0m");
+        assert_eq!(rendered.lines().nth(6).unwrap(), "\x1b[34m.\x1b[0m\x1b[1;33m│\x1b[0m\x1b[32m..\x1b[0m\x1b[1;33m││\x1b[0m\x1b[32m..\x1b[0m\x1b[1;33m│\x1b[0m\x1b[34m.\x1b[0m");
+    }
+
+    #[test]
+    fn draws_loop_and_enclosed_tiles_as_svg() {
+        let svg = maze(ENCLOSING_LOOP).to_svg().unwrap().to_string();
+        assert_eq!(svg.matches("fill=\"green\"").count(), 4);
+        assert!(svg.contains("<rect x=\"2\" y=\"6\" width=\"1\" height=\"1\" fill=\"green\"/>"));
+        assert!(svg.contains("<polygon points=\"1.5,1.5 2.5,1.5 "));
+    }
+
+    #[test]
+    fn reports_broken_loops() {
+        assert_eq!(maze(&[".....", ".S-7.", ".|.|.", ".L-..", "....."]).farthest_distance().unwrap_err().to_string(), "no solution: loop breaks at 3,3");
+        assert_eq!(maze(&["S-."]).farthest_distance().unwrap_err().to_string(), "no solution: start tile connects to 1 pipes");
+    }
+}
@@ -0,0 +1,139 @@
+use std::fmt::Display;
+
+use aoc2023_macros::aoc;
+use itertools::Itertools;
+use tracing::debug;
+
+use crate::error::{query_argument, AocError, Result};
+use crate::grid::Grid;
+use crate::puzzle::Puzzle;
+use crate::validate::Rule;
+use crate::vec2::Vec2;
+
+/// How many times as large empty rows and columns grow in each part.
+const YOUNG_UNIVERSE_EXPANSION: usize = 2;
+const OLD_UNIVERSE_EXPANSION: usize = 1_000_000;
+
+pub struct Image {
+    galaxies: Vec<Vec2>,
+    empty_rows: Vec<usize>,
+    empty_columns: Vec<usize>,
+}
+
+impl Image {
+    fn parse(input: &[String]) -> Result<Image> {
+        let grid = Grid::of_chars(input)?;
+        let galaxies: Vec<Vec2> = grid.positions_of(&'#').map(|(x, y)| Vec2::new(x as isize, y as isize)).collect();
+        let empty_rows = (0..grid.height()).filter(|&y| !grid.row(y).contains(&'#')).collect();
+        let empty_columns = (0..grid.width()).filter(|&x| !grid.column(x).contains(&'#')).collect();
+        let image = Image { galaxies, empty_rows, empty_columns };
+        debug!(galaxies = image.galaxies.len(), empty_rows = image.empty_rows.len(), empty_columns = image.empty_columns.len(), "parsed image");
+        Ok(image)
+    }
+
+    /// Positions of the galaxies once every empty row and column has grown `factor` times as large, shifting each
+    /// coordinate by the empty lines before it instead of growing the image itself.
+    fn expanded_galaxies(&self, factor: usize) -> Vec<Vec2> {
+        let shift = |empty: &[usize], coordinate: isize| {
+            coordinate + (empty.partition_point(|&line| (line as isize) < coordinate) * (factor - 1)) as isize
+        };
+        self.galaxies.iter()
+            .map(|galaxy| Vec2::new(shift(&self.empty_columns, galaxy.x), shift(&self.empty_rows, galaxy.y)))
+            .collect()
+    }
+}
+
+/// Sum of the distances between every pair of `coordinates`, from each one sorted to all the ones before it.
+fn sum_of_gaps(coordinates: impl Iterator<Item=isize>) -> usize {
+    coordinates.sorted_unstable()
+        .enumerate()
+        .fold((0, 0), |(sum, before), (index, coordinate)| (sum + coordinate * index as isize - before, before + coordinate))
+        .0 as usize
+}
+
+/// Sum of the distances between every pair of galaxies once empty rows and columns have grown `factor` times as large,
+/// `factor` being at least 1.
+fn sum_of_distances(image: &Image, factor: usize) -> usize {
+    let galaxies = image.expanded_galaxies(factor);
+    sum_of_gaps(galaxies.iter().map(|galaxy| galaxy.x)) + sum_of_gaps(galaxies.iter().map(|galaxy| galaxy.y))
+}
+
+pub struct Day11;
+
+#[aoc(day = 11)]
+impl Puzzle for Day11 {
+    const RULES: &'static [Rule] = &[Rule::MinLines(1), Rule::Rectangular, Rule::Charset(".#"), Rule::NoTrailingBlankLines];
+
+    const QUERIES: &'static [&'static str] = &["galaxies", "empty", "distances <factor>"];
+
+    type Model = Image;
+
+    fn parse(input: Vec<String>) -> Result<Self::Model> {
+        Image::parse(&input)
+    }
+
+    fn part_one(model: &Self::Model) -> Result<impl Display> {
+        Ok(sum_of_distances(model, YOUNG_UNIVERSE_EXPANSION))
+    }
+
+    fn part_two(model: &Self::Model) -> Result<impl Display> {
+        Ok(sum_of_distances(model, OLD_UNIVERSE_EXPANSION))
+    }
+
+    fn query(model: &Self::Model, query: &[&str]) -> Result<String> {
+        match query {
+            ["galaxies"] => Ok(model.galaxies.iter().map(|galaxy| format!("{},{}", galaxy.x, galaxy.y)).join(" ")),
+            ["empty"] => Ok(format!("rows {}, columns {}", model.empty_rows.iter().join(" "), model.empty_columns.iter().join(" "))),
+            ["distances", factor] => match query_argument(factor)? {
+                0 => Err(AocError::Query("expansion factor must be at least 1".to_owned())),
+                factor => Ok(sum_of_distances(model, factor).to_string()),
+            },
+            _ => Err(AocError::unknown_query(query)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::input::daily_example;
+
+    use super::*;
+
+    fn example() -> Image {
+        Image::parse(&daily_example(2023, 11).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn finds_empty_rows_and_columns() {
+        let image = example();
+        assert_eq!(image.galaxies.len(), 9);
+        assert_eq!((image.empty_rows, image.empty_columns), (vec![3, 7], vec![2, 5, 8]));
+    }
+
+    #[test]
+    fn expands_empty_space() {
+        let galaxies = example().expanded_galaxies(2);
+        assert_eq!(galaxies[0], Vec2::new(4, 0));
+        assert_eq!(galaxies[8], Vec2::new(5, 11));
+    }
+
+    #[test]
+    fn sums_distances_between_pairs_of_galaxies() {
+        assert_eq!(sum_of_gaps([4, 0, 9].into_iter()), 18);
+        assert_eq!(sum_of_distances(&example(), 2), 374);
+    }
+
+    #[test]
+    fn sums_distances_for_any_expansion_factor() {
+        assert_eq!(sum_of_distances(&example(), 1), 292);
+        assert_eq!(sum_of_distances(&example(), 10), 1030);
+        assert_eq!(sum_of_distances(&example(), 100), 8410);
+        assert_eq!(sum_of_distances(&example(), OLD_UNIVERSE_EXPANSION), 82000210);
+    }
+
+    #[test]
+    fn answers_distances_for_a_given_factor() {
+        assert_eq!(Day11::query(&example(), &["distances", "10"]).unwrap(), "1030");
+        assert_eq!(Day11::query(&example(), &["distances", "0"]).unwrap_err().to_string(), "cannot answer: expansion factor must be at least 1");
+    }
+}
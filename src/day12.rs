@@ -0,0 +1,208 @@
+use std::fmt::Display;
+
+use aoc2023_macros::aoc;
+use itertools::Itertools;
+use rayon::prelude::*;
+use tracing::debug;
+
+use crate::error::{parse_lines, query_argument, AocError, Result};
+use crate::grid::FromChar;
+use crate::memo::memoize;
+use crate::puzzle::{Part, Puzzle, Variant};
+use crate::validate::Rule;
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash)]
+enum Spring {
+    Operational,
+    Damaged,
+    Unknown,
+}
+
+impl FromChar for Spring {
+    fn from_char(c: char) -> std::result::Result<Spring, String> {
+        match c {
+            '.' => Ok(Spring::Operational),
+            '#' => Ok(Spring::Damaged),
+            '?' => Ok(Spring::Unknown),
+            _ => Err(format!("unknown spring {}", c)),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Row {
+    springs: Vec<Spring>,
+    groups: Vec<usize>,
+}
+
+impl Row {
+    fn parse(line: &str) -> std::result::Result<Row, String> {
+        let (springs, groups) = line.split_once(' ').ok_or("expected springs and groups")?;
+        let springs = springs.chars().map(Spring::from_char).collect::<std::result::Result<_, _>>()?;
+        let groups = groups.split(',')
+            .map(|group| group.parse().map_err(|_| format!("invalid group {}", group)))
+            .collect::<std::result::Result<_, _>>()?;
+        Ok(Row { springs, groups })
+    }
+
+    /// The row repeated `times` times, copies of springs being separated by an unknown spring.
+    fn unfold(&self, times: usize) -> Row {
+        Row {
+            springs: vec![self.springs.as_slice(); times].join(&Spring::Unknown),
+            groups: self.groups.repeat(times),
+        }
+    }
+
+    /// Fits the groups of damaged springs to the springs left to right, counting the ways to place the remaining
+    /// groups on the remaining springs once for each position reached.
+    fn arrangements(&self) -> u64 {
+        let (springs, groups) = (&self.springs, &self.groups);
+        let mut count = memoize(|count: &mut dyn FnMut((usize, usize)) -> u64, (spring, group): (usize, usize)| {
+            if spring >= springs.len() {
+                return (group == groups.len()) as u64;
+            }
+            let mut ways = 0;
+            if springs[spring] != Spring::Damaged {
+                ways += count((spring + 1, group));
+            }
+            if springs[spring] != Spring::Operational && group < groups.len() {
+                let end = spring + groups[group];
+                let fits = end <= springs.len()
+                    && springs[spring..end].iter().all(|&s| s != Spring::Operational)
+                    && springs.get(end) != Some(&Spring::Damaged);
+                if fits {
+                    ways += count((end + 1, group + 1));
+                }
+            }
+            ways
+        });
+        count((0, 0))
+    }
+}
+
+fn parse_rows(input: &[String]) -> Result<Vec<Row>> {
+    let rows = parse_lines(input, 0, Row::parse)?;
+    debug!(rows = rows.len(), "parsed rows");
+    Ok(rows)
+}
+
+fn sum_of_arrangements(rows: &[Row], unfolding: usize) -> u64 {
+    rows.iter().map(|row| row.unfold(unfolding).arrangements()).sum()
+}
+
+/// Rows being independent, each is unfolded and counted on its own thread, with a memo of its own.
+fn sum_of_arrangements_in_parallel(rows: &[Row], unfolding: usize) -> u64 {
+    rows.par_iter().map(|row| row.unfold(unfolding).arrangements()).sum()
+}
+
+pub struct Day12;
+
+#[aoc(day = 12)]
+impl Puzzle for Day12 {
+    const RULES: &'static [Rule] = &[Rule::MinLines(1), Rule::Charset(".#?0123456789, "), Rule::NoTrailingBlankLines];
+
+    const QUERIES: &'static [&'static str] = &["arrangements <line>"];
+
+    type Model = Vec<Row>;
+
+    fn parse(input: Vec<String>) -> Result<Self::Model> {
+        parse_rows(&input)
+    }
+
+    fn part_one(model: &Self::Model) -> Result<impl Display> {
+        Ok(sum_of_arrangements(model, 1))
+    }
+
+    fn part_two(model: &Self::Model) -> Result<impl Display> {
+        Ok(sum_of_arrangements_in_parallel(model, 5))
+    }
+
+    fn query(model: &Self::Model, query: &[&str]) -> Result<String> {
+        match query {
+            ["arrangements", line] => {
+                let line: usize = query_argument(line)?;
+                let row = line.checked_sub(1).and_then(|index| model.get(index))
+                    .ok_or_else(|| AocError::Query(format!("no row on line {}", line)))?;
+                Ok([1, 5].iter()
+                    .map(|&times| format!("{} unfolded {} times", row.unfold(times).arrangements(), times))
+                    .join(", "))
+            }
+            _ => Err(AocError::unknown_query(query)),
+        }
+    }
+
+    fn variants() -> Vec<Variant<Self::Model>> {
+        vec![Variant { name: "sequential", part: Part::Two, solve: |rows| Ok(sum_of_arrangements(rows, 5).to_string()) }]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::input::daily_example;
+    use crate::rle::run_lengths_of;
+
+    use super::*;
+
+    fn example() -> Vec<Row> {
+        parse_rows(&daily_example(2023, 12).unwrap()).unwrap()
+    }
+
+    /// Arrangements counted by trying every way to replace unknown springs.
+    fn arrangements_by_brute_force(row: &Row) -> u64 {
+        let unknowns = row.springs.iter().filter(|&&s| s == Spring::Unknown).count();
+        (0..1_u32 << unknowns)
+            .filter(|damaged| {
+                let mut index = 0;
+                let springs = row.springs.iter().map(|&spring| match spring {
+                    Spring::Unknown => {
+                        index += 1;
+                        if damaged & (1 << (index - 1)) != 0 { Spring::Damaged } else { Spring::Operational }
+                    }
+                    known => known,
+                });
+                run_lengths_of(springs, &Spring::Damaged) == row.groups
+            })
+            .count() as u64
+    }
+
+    #[test]
+    fn parses_rows_of_springs() {
+        let row = &example()[0];
+        use Spring::*;
+        assert_eq!(row.springs, vec![Unknown, Unknown, Unknown, Operational, Damaged, Damaged, Damaged]);
+        assert_eq!(row.groups, vec![1, 1, 3]);
+    }
+
+    #[test]
+    fn counts_arrangements_of_each_row() {
+        let arrangements: Vec<u64> = example().iter().map(Row::arrangements).collect();
+        assert_eq!(arrangements, vec![1, 4, 1, 1, 4, 10]);
+        assert!(example().iter().all(|row| row.arrangements() == arrangements_by_brute_force(row)));
+        assert_eq!(sum_of_arrangements(&example(), 1), 21);
+    }
+
+    #[test]
+    fn unfolds_rows() {
+        let row = Row::parse(".# 1").unwrap().unfold(5);
+        assert_eq!(row, Row::parse(".#?.#?.#?.#?.# 1,1,1,1,1").unwrap());
+    }
+
+    #[test]
+    fn counts_arrangements_of_unfolded_rows() {
+        let arrangements: Vec<u64> = example().iter().map(|row| row.unfold(5).arrangements()).collect();
+        assert_eq!(arrangements, vec![1, 16384, 1, 16, 2500, 506250]);
+        assert_eq!(sum_of_arrangements(&example(), 5), 525152);
+    }
+
+    #[test]
+    fn counts_arrangements_of_rows_in_parallel() {
+        assert_eq!(sum_of_arrangements_in_parallel(&example(), 1), 21);
+        assert_eq!(sum_of_arrangements_in_parallel(&example(), 5), 525152);
+    }
+
+    #[test]
+    fn reports_malformed_rows() {
+        let error = parse_rows(&["???.### 1,1,3".to_owned(), "???.### 1,x".to_owned()]).unwrap_err();
+        assert_eq!(error.to_string(), r#"line 2: invalid group x in "???.### 1,x""#);
+    }
+}
@@ -0,0 +1,177 @@
+use std::fmt::{self, Display, Formatter};
+
+use aoc2023_macros::aoc;
+use tracing::debug;
+
+use crate::error::{query_argument, AocError, Result};
+use crate::grid::{FromChar, Grid};
+use crate::puzzle::{split_blocks, Puzzle};
+use crate::validate::Rule;
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+enum Terrain {
+    Ash,
+    Rock,
+}
+
+impl FromChar for Terrain {
+    fn from_char(c: char) -> std::result::Result<Terrain, String> {
+        match c {
+            '.' => Ok(Terrain::Ash),
+            '#' => Ok(Terrain::Rock),
+            _ => Err(format!("unknown terrain {}", c)),
+        }
+    }
+}
+
+/// Line of a pattern mirroring it, with the number of columns to its left or rows above it.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+enum Reflection {
+    Vertical(usize),
+    Horizontal(usize),
+}
+
+impl Reflection {
+    fn summary(&self) -> usize {
+        match *self {
+            Reflection::Vertical(columns) => columns,
+            Reflection::Horizontal(rows) => 100 * rows,
+        }
+    }
+}
+
+impl Display for Reflection {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Reflection::Vertical(columns) => write!(f, "vertical line after column {}", columns),
+            Reflection::Horizontal(rows) => write!(f, "horizontal line after row {}", rows),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Pattern {
+    grid: Grid<Terrain>,
+}
+
+impl Pattern {
+    /// Line mirroring the pattern once exactly `smudges` cells are swapped between ash and rock.
+    fn reflection(&self, smudges: usize) -> Option<Reflection> {
+        self.grid.reflection_column(smudges).map(Reflection::Vertical)
+            .or_else(|| self.grid.reflection_row(smudges).map(Reflection::Horizontal))
+    }
+}
+
+fn parse_patterns(input: &[String]) -> Result<Vec<Pattern>> {
+    let mut patterns = Vec::new();
+    let mut offset = 0;
+    for block in split_blocks(input) {
+        let grid = Grid::parse(&block).map_err(|error| error.offset_by(offset))?;
+        patterns.push(Pattern { grid });
+        offset += block.len() + 1;
+    }
+    debug!(patterns = patterns.len(), "parsed patterns");
+    Ok(patterns)
+}
+
+const CLEAN: usize = 0;
+const SMUDGED: usize = 1;
+
+fn summarize_reflections(patterns: &[Pattern], smudges: usize) -> Result<usize> {
+    patterns.iter().enumerate()
+        .map(|(index, pattern)| {
+            pattern.reflection(smudges).map(|reflection| reflection.summary())
+                .ok_or_else(|| AocError::unsolvable(format!("pattern {} has no line of reflection with {} smudges", index + 1, smudges)))
+        })
+        .sum()
+}
+
+pub struct Day13;
+
+#[aoc(day = 13)]
+impl Puzzle for Day13 {
+    const RULES: &'static [Rule] = &[Rule::MinLines(1), Rule::Charset(".#"), Rule::NoTrailingBlankLines];
+
+    const QUERIES: &'static [&'static str] = &["reflection <pattern> <smudges>"];
+
+    type Model = Vec<Pattern>;
+
+    fn parse(input: Vec<String>) -> Result<Self::Model> {
+        parse_patterns(&input)
+    }
+
+    fn part_one(model: &Self::Model) -> Result<impl Display> {
+        summarize_reflections(model, CLEAN)
+    }
+
+    fn part_two(model: &Self::Model) -> Result<impl Display> {
+        summarize_reflections(model, SMUDGED)
+    }
+
+    fn query(model: &Self::Model, query: &[&str]) -> Result<String> {
+        match query {
+            ["reflection", number, smudges] => {
+                let number: usize = query_argument(number)?;
+                let smudges = query_argument(smudges)?;
+                let pattern = number.checked_sub(1).and_then(|index| model.get(index))
+                    .ok_or_else(|| AocError::Query(format!("no pattern {}", number)))?;
+                Ok(pattern.reflection(smudges).map_or("no line of reflection".to_owned(), |reflection| reflection.to_string()))
+            }
+            _ => Err(AocError::unknown_query(query)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::input::daily_example;
+
+    use super::*;
+
+    fn example() -> Vec<Pattern> {
+        parse_patterns(&daily_example(2023, 13).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn parses_patterns_separated_by_blank_lines() {
+        let patterns = example();
+        assert_eq!(patterns.len(), 2);
+        assert_eq!((patterns[1].grid.width(), patterns[1].grid.height()), (9, 7));
+        assert_eq!(patterns[0].grid[(0, 0)], Terrain::Rock);
+    }
+
+    #[test]
+    fn finds_lines_of_reflection() {
+        let reflections: Vec<_> = example().iter().map(|pattern| pattern.reflection(CLEAN)).collect();
+        assert_eq!(reflections, vec![Some(Reflection::Vertical(5)), Some(Reflection::Horizontal(4))]);
+        assert_eq!(summarize_reflections(&example(), CLEAN).unwrap(), 405);
+    }
+
+    #[test]
+    fn finds_lines_of_reflection_of_smudged_patterns() {
+        let reflections: Vec<_> = example().iter().map(|pattern| pattern.reflection(SMUDGED)).collect();
+        assert_eq!(reflections, vec![Some(Reflection::Horizontal(3)), Some(Reflection::Horizontal(1))]);
+        assert_eq!(summarize_reflections(&example(), SMUDGED).unwrap(), 400);
+    }
+
+    #[test]
+    fn tolerates_any_number_of_smudges() {
+        let pattern = &parse_patterns(&["#..#.".to_owned(), ".##..".to_owned()]).unwrap()[0];
+        assert_eq!(pattern.reflection(0), Some(Reflection::Vertical(2)));
+        assert_eq!(pattern.reflection(1), Some(Reflection::Vertical(4)));
+        assert_eq!(pattern.reflection(2), Some(Reflection::Vertical(1)));
+        assert_eq!(pattern.reflection(6), None);
+    }
+
+    #[test]
+    fn reports_patterns_without_reflection() {
+        let patterns = parse_patterns(&["#.".to_owned(), "..".to_owned()]).unwrap();
+        assert_eq!(summarize_reflections(&patterns, CLEAN).unwrap_err().to_string(), "no solution: pattern 1 has no line of reflection with 0 smudges");
+    }
+
+    #[test]
+    fn reports_malformed_patterns_with_their_line() {
+        let error = parse_patterns(&["#.".to_owned(), "".to_owned(), "#.".to_owned(), "#x".to_owned()]).unwrap_err();
+        assert_eq!(error.to_string(), r##"line 4: unknown terrain x in "#x""##);
+    }
+}
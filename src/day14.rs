@@ -0,0 +1,357 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+
+use aoc2023_macros::aoc;
+use tracing::debug;
+
+use crate::cycle::Cycle;
+use crate::error::{query_argument, AocError, Result};
+use crate::grid::{FromChar, Grid};
+use crate::puzzle::Puzzle;
+use crate::render::{render_grid, Color, Style};
+use crate::validate::Rule;
+use crate::vec2::Direction;
+use crate::zobrist::Zobrist;
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash)]
+enum Rock {
+    Rounded,
+    Cube,
+    Empty,
+}
+
+impl FromChar for Rock {
+    fn from_char(c: char) -> std::result::Result<Rock, String> {
+        match c {
+            'O' => Ok(Rock::Rounded),
+            '#' => Ok(Rock::Cube),
+            '.' => Ok(Rock::Empty),
+            _ => Err(format!("unknown rock {}", c)),
+        }
+    }
+}
+
+impl Display for Rock {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let c = match self {
+            Rock::Rounded => 'O',
+            Rock::Cube => '#',
+            Rock::Empty => '.',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+/// Rocks on the reflector dish, rounded ones rolling when it tilts while cube-shaped ones stay in place.
+#[derive(PartialEq, Eq, Debug, Clone, Hash)]
+pub struct Platform {
+    grid: Grid<Rock>,
+}
+
+impl Platform {
+    /// Rolls every rounded rock towards `heading` until it stops against the edge, a cube-shaped rock or another
+    /// rounded rock.
+    fn tilt(&mut self, heading: Direction) {
+        let (width, height) = (self.grid.width(), self.grid.height());
+        let lanes: Vec<Vec<(usize, usize)>> = match heading {
+            Direction::Up => (0..width).map(|x| (0..height).map(|y| (x, y)).collect()).collect(),
+            Direction::Down => (0..width).map(|x| (0..height).rev().map(|y| (x, y)).collect()).collect(),
+            Direction::Left => (0..height).map(|y| (0..width).map(|x| (x, y)).collect()).collect(),
+            Direction::Right => (0..height).map(|y| (0..width).rev().map(|x| (x, y)).collect()).collect(),
+        };
+        for lane in lanes {
+            let mut free = 0;
+            for (index, &position) in lane.iter().enumerate() {
+                match self.grid[position] {
+                    Rock::Cube => free = index + 1,
+                    Rock::Rounded => {
+                        self.grid[position] = Rock::Empty;
+                        self.grid[lane[free]] = Rock::Rounded;
+                        free += 1;
+                    }
+                    Rock::Empty => {}
+                }
+            }
+        }
+    }
+
+    fn tilt_north(&mut self) {
+        self.tilt(Direction::Up);
+    }
+
+    /// Platform once every rounded rock with an empty cell towards `heading` moved into it, with the positions the
+    /// rocks moved to, or nothing when no rock can move. Repeating it until then ends as tilting does.
+    fn roll_step(&self, heading: Direction) -> Option<(Platform, Vec<(usize, usize)>)> {
+        let offset = heading.offset();
+        let moves: Vec<((usize, usize), (usize, usize))> = self.grid.positions_of(&Rock::Rounded)
+            .filter_map(|(x, y)| {
+                let (nx, ny) = (x as isize + offset.x, y as isize + offset.y);
+                (self.grid.get(nx, ny) == Some(&Rock::Empty)).then_some(((x, y), (nx as usize, ny as usize)))
+            })
+            .collect();
+        if moves.is_empty() {
+            return None;
+        }
+        let mut platform = self.clone();
+        moves.iter().for_each(|&(from, _)| platform.grid[from] = Rock::Empty);
+        moves.iter().for_each(|&(_, to)| platform.grid[to] = Rock::Rounded);
+        Some((platform, moves.into_iter().map(|(_, to)| to).collect()))
+    }
+
+    /// Rounded rocks in white, the `moved` ones in bold yellow, and cube-shaped rocks in blue.
+    fn render(&self, moved: &[(usize, usize)]) -> String {
+        let rows: Vec<String> = self.to_string().lines().map(str::to_owned).collect();
+        render_grid(&rows, |x, y, _| match self.grid[(x, y)] {
+            Rock::Rounded if moved.contains(&(x, y)) => Style::fg(Color::Yellow).bold(),
+            Rock::Rounded => Style::fg(Color::White),
+            Rock::Cube => Style::fg(Color::Blue),
+            Rock::Empty => Style::PLAIN,
+        })
+    }
+
+    /// Frames of `cycles` spin cycles, rocks rolling a cell per frame, each headed by the cycle, the tilt and the step.
+    fn animate(&self, cycles: usize) -> String {
+        let mut frames = vec![format!("initial\n{}", self.render(&[]))];
+        let mut platform = self.clone();
+        for cycle in 1..=cycles {
+            for (heading, name) in SPIN.iter().zip(["north", "west", "south", "east"]) {
+                let mut step = 0;
+                while let Some((next, moved)) = platform.roll_step(*heading) {
+                    step += 1;
+                    frames.push(format!("cycle {}, tilting {}, step {}\n{}", cycle, name, step, next.render(&moved)));
+                    platform = next;
+                }
+            }
+        }
+        frames.join("\n")
+    }
+
+    /// Tilts the platform north, west, south and east in turn.
+    fn spin(&mut self) {
+        for heading in SPIN {
+            self.tilt(heading);
+        }
+    }
+
+    /// Platform after tilting it north, west, south and east in turn.
+    fn spin_cycle(&self) -> Platform {
+        let mut platform = self.clone();
+        platform.spin();
+        platform
+    }
+
+    /// Earliest number of spin cycles leaving the platform as `cycles` spin cycles do, along with the north load after
+    /// each spin cycle until the platform comes back to an earlier state. Only the Zobrist hashes of the rounded rocks
+    /// are remembered, cube-shaped rocks never moving, rather than every platform met along the way.
+    fn spin_loads(&self, cycles: usize) -> (usize, Vec<usize>) {
+        let zobrist = Zobrist::for_grid(&self.grid, 1);
+        let hash = |platform: &Platform| zobrist.hash(&platform.grid, |&rock| (rock == Rock::Rounded).then_some(0));
+        let mut platform = self.clone();
+        let mut seen = HashMap::from([(hash(&platform), 0)]);
+        let mut loads = vec![platform.north_load()];
+        while loads.len() <= cycles {
+            platform.spin();
+            let state = hash(&platform);
+            if let Some(&prefix) = seen.get(&state) {
+                let cycle = Cycle { prefix, length: loads.len() - prefix };
+                debug!(?cycle, "found spin cycles repeating");
+                return (cycle.equivalent_step(cycles), loads);
+            }
+            seen.insert(state, loads.len());
+            loads.push(platform.north_load());
+        }
+        (cycles, loads)
+    }
+
+    /// Platform after `cycles` spin cycles, skipping the repeats once the platform comes back to an earlier state.
+    fn spun(&self, cycles: usize) -> Platform {
+        let (step, _) = self.spin_loads(cycles);
+        (0..step).fold(self.clone(), |platform, _| platform.spin_cycle())
+    }
+
+    /// North load after `cycles` spin cycles, read off the load at the equivalent step before the platform repeats.
+    fn spun_load(&self, cycles: usize) -> usize {
+        let (step, loads) = self.spin_loads(cycles);
+        loads[step]
+    }
+
+    /// Load on the north support beams, each rounded rock adding its distance to the south edge, itself included.
+    fn north_load(&self) -> usize {
+        self.grid.positions_of(&Rock::Rounded).map(|(_, y)| self.grid.height() - y).sum()
+    }
+}
+
+impl Display for Platform {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+}
+
+fn parse_platform(input: &[String]) -> Result<Platform> {
+    let grid = Grid::parse(input)?;
+    debug!(width = grid.width(), height = grid.height(), "parsed platform");
+    Ok(Platform { grid })
+}
+
+fn load_after_tilting_north(platform: &Platform) -> usize {
+    let mut platform = platform.clone();
+    platform.tilt_north();
+    platform.north_load()
+}
+
+const SPIN: [Direction; 4] = [Direction::Up, Direction::Left, Direction::Down, Direction::Right];
+
+const SPIN_CYCLES: usize = 1_000_000_000;
+
+pub struct Day14;
+
+#[aoc(day = 14)]
+impl Puzzle for Day14 {
+    const RULES: &'static [Rule] = &[Rule::MinLines(1), Rule::Rectangular, Rule::Charset("O#."), Rule::NoTrailingBlankLines];
+
+    const QUERIES: &'static [&'static str] = &["load", "tilted", "spun <cycles>", "animate <cycles>"];
+
+    type Model = Platform;
+
+    fn parse(input: Vec<String>) -> Result<Self::Model> {
+        parse_platform(&input)
+    }
+
+    fn part_one(model: &Self::Model) -> Result<impl Display> {
+        Ok(load_after_tilting_north(model))
+    }
+
+    fn part_two(model: &Self::Model) -> Result<impl Display> {
+        Ok(model.spun_load(SPIN_CYCLES))
+    }
+
+    fn query(model: &Self::Model, query: &[&str]) -> Result<String> {
+        match query {
+            ["load"] => Ok(model.north_load().to_string()),
+            ["tilted"] => {
+                let mut platform = model.clone();
+                platform.tilt_north();
+                Ok(platform.to_string().trim_end().to_owned())
+            }
+            ["spun", cycles] => {
+                let platform = model.spun(query_argument(cycles)?);
+                Ok(format!("{}load {}", platform, platform.north_load()))
+            }
+            ["animate", cycles] => Ok(model.animate(query_argument(cycles)?).trim_end().to_owned()),
+            _ => Err(AocError::unknown_query(query)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::input::daily_example;
+
+    use super::*;
+
+    fn example() -> Platform {
+        parse_platform(&daily_example(2023, 14).unwrap()).unwrap()
+    }
+
+    fn platform(lines: &[&str]) -> Platform {
+        parse_platform(&lines.iter().map(|line| line.to_string()).collect::<Vec<_>>()).unwrap()
+    }
+
+    #[test]
+    fn rolls_rounded_rocks_north() {
+        let mut platform = example();
+        platform.tilt_north();
+        assert_eq!(platform, self::platform(&[
+            "OOOO.#.O..",
+            "OO..#....#",
+            "OO..O##..O",
+            "O..#.OO...",
+            "........#.",
+            "..#....#.#",
+            "..O..#.O.O",
+            "..O.......",
+            "#....###..",
+            "#....#....",
+        ]));
+    }
+
+    #[test]
+    fn weighs_load_on_north_beams() {
+        assert_eq!(example().north_load(), 104);
+        assert_eq!(load_after_tilting_north(&example()), 136);
+    }
+
+    #[test]
+    fn tilts_north_west_south_and_east_in_a_spin_cycle() {
+        let platform = example().spin_cycle();
+        assert_eq!(platform, self::platform(&[
+            ".....#....",
+            "....#...O#",
+            "...OO##...",
+            ".OO#......",
+            ".....OOO#.",
+            ".O#...O#.#",
+            "....O#....",
+            "......OOOO",
+            "#...O###..",
+            "#..OO#....",
+        ]));
+        assert_eq!(platform.spin_cycle().spin_cycle(), self::platform(&[
+            ".....#....",
+            "....#...O#",
+            ".....##...",
+            "..O#......",
+            ".....OOO#.",
+            ".O#...O#.#",
+            "....O#...O",
+            ".......OOO",
+            "#...O###.O",
+            "#.OOO#...O",
+        ]));
+    }
+
+    #[test]
+    fn rolls_rocks_a_cell_at_a_time_until_tilted() {
+        for heading in Direction::ALL {
+            let mut platform = example();
+            while let Some((next, _)) = platform.roll_step(heading) {
+                platform = next;
+            }
+            let mut tilted = example();
+            tilted.tilt(heading);
+            assert_eq!(platform, tilted, "tilting {:?}", heading);
+        }
+    }
+
+    #[test]
+    fn highlights_rocks_that_just_moved() {
+        let (platform, moved) = self::platform(&[".#", "OO"]).roll_step(Direction::Up).unwrap();
+        assert_eq!(moved, vec![(0, 0)]);
+        assert_eq!(platform.render(&moved), "\x1b[1;33mO\x1b[0m\x1b[34m#\x1b[0m\n.\x1b[37mO\x1b[0m\n");
+        assert_eq!(platform.roll_step(Direction::Up), None);
+    }
+
+    #[test]
+    fn animates_spin_cycles_frame_by_frame() {
+        let animation = self::platform(&["..", "O."]).animate(1);
+        let headers: Vec<_> = animation.lines().filter(|line| line.starts_with(char::is_alphabetic)).collect();
+        assert_eq!(headers, vec!["initial", "cycle 1, tilting north, step 1", "cycle 1, tilting south, step 1", "cycle 1, tilting east, step 1"]);
+    }
+
+    #[test]
+    fn skips_repeated_spin_cycles() {
+        let platform = example();
+        assert_eq!(platform.spun(3), platform.spin_cycle().spin_cycle().spin_cycle());
+        assert_eq!(platform.spun(SPIN_CYCLES).north_load(), 64);
+    }
+
+    #[test]
+    fn reads_load_after_spin_cycles_off_the_repeating_loads() {
+        let platform = example();
+        let (step, loads) = platform.spin_loads(SPIN_CYCLES);
+        assert!(step < loads.len());
+        assert_eq!(platform.spun_load(3), platform.spun(3).north_load());
+        assert_eq!(platform.spun_load(SPIN_CYCLES), 64);
+        assert_eq!(platform.spun_load(0), platform.north_load());
+    }
+}
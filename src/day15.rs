@@ -0,0 +1,175 @@
+use std::fmt::Display;
+
+use aoc2023_macros::aoc;
+use itertools::Itertools;
+use tracing::debug;
+
+use crate::error::{parse_line, query_argument, AocError, Result};
+use crate::puzzle::Puzzle;
+use crate::validate::Rule;
+
+/// Holiday ASCII String Helper: each character adds its code to the value, which is then multiplied by 17 and kept
+/// modulo 256.
+fn hash(text: &str) -> u8 {
+    text.bytes().fold(0, |value, byte| value.wrapping_add(byte).wrapping_mul(17))
+}
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+enum Operation {
+    Remove,
+    Insert(u8),
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Step {
+    text: String,
+    label: String,
+    operation: Operation,
+}
+
+impl Step {
+    fn parse(text: &str) -> std::result::Result<Step, String> {
+        let (label, operation) = if let Some(label) = text.strip_suffix('-') {
+            (label, Operation::Remove)
+        } else if let Some((label, focal_length)) = text.split_once('=') {
+            let focal_length = focal_length.parse().map_err(|_| format!("invalid focal length {}", focal_length))?;
+            (label, Operation::Insert(focal_length))
+        } else {
+            return Err(format!("expected - or = in step {}", text));
+        };
+        if label.is_empty() || !label.chars().all(|c| c.is_ascii_lowercase()) {
+            return Err(format!("invalid label in step {}", text));
+        }
+        Ok(Step { text: text.to_owned(), label: label.to_owned(), operation })
+    }
+}
+
+fn parse_steps(input: &[String]) -> Result<Vec<Step>> {
+    let steps: Vec<Step> = parse_line(input, 0, |line| line.split(',').map(Step::parse).collect())?;
+    debug!(steps = steps.len(), "parsed steps");
+    Ok(steps)
+}
+
+/// Lenses in each of the 256 boxes, in the order they were put in, with their focal length.
+#[derive(PartialEq, Eq, Debug, Clone)]
+struct Boxes {
+    lenses: Vec<Vec<(String, u8)>>,
+}
+
+impl Boxes {
+    fn new() -> Boxes {
+        Boxes { lenses: vec![Vec::new(); 256] }
+    }
+
+    /// Removes the lens of the label of `step` from its box, or puts it there, replacing a lens of the same label
+    /// where it stands or going behind the others.
+    fn perform(&mut self, step: &Step) {
+        let lenses = &mut self.lenses[hash(&step.label) as usize];
+        let position = lenses.iter().position(|(label, _)| *label == step.label);
+        match (step.operation, position) {
+            (Operation::Remove, Some(position)) => { lenses.remove(position); }
+            (Operation::Remove, None) => {}
+            (Operation::Insert(focal_length), Some(position)) => lenses[position].1 = focal_length,
+            (Operation::Insert(focal_length), None) => lenses.push((step.label.clone(), focal_length)),
+        }
+    }
+
+    /// Sum for every lens of the product of its box number plus one, its slot number counting from one, and its focal
+    /// length.
+    fn focusing_power(&self) -> usize {
+        self.lenses.iter().enumerate()
+            .flat_map(|(number, lenses)| {
+                lenses.iter().enumerate().map(move |(slot, &(_, focal_length))| (number + 1) * (slot + 1) * focal_length as usize)
+            })
+            .sum()
+    }
+
+    /// Non empty boxes in the puzzle notation, such as `Box 0: [rn 1] [cm 2]`.
+    fn describe(&self) -> String {
+        self.lenses.iter().enumerate()
+            .filter(|(_, lenses)| !lenses.is_empty())
+            .map(|(number, lenses)| {
+                format!("Box {}: {}", number, lenses.iter().map(|(label, focal_length)| format!("[{} {}]", label, focal_length)).join(" "))
+            })
+            .join("\n")
+    }
+}
+
+fn sum_of_hashes(steps: &[Step]) -> usize {
+    steps.iter().map(|step| hash(&step.text) as usize).sum()
+}
+
+fn arrange_lenses(steps: &[Step]) -> Boxes {
+    let mut boxes = Boxes::new();
+    steps.iter().for_each(|step| boxes.perform(step));
+    boxes
+}
+
+pub struct Day15;
+
+#[aoc(day = 15)]
+impl Puzzle for Day15 {
+    const RULES: &'static [Rule] = &[Rule::MinLines(1), Rule::NoTrailingBlankLines];
+
+    const QUERIES: &'static [&'static str] = &["hash <text>", "boxes <steps>"];
+
+    type Model = Vec<Step>;
+
+    fn parse(input: Vec<String>) -> Result<Self::Model> {
+        parse_steps(&input)
+    }
+
+    fn part_one(model: &Self::Model) -> Result<impl Display> {
+        Ok(sum_of_hashes(model))
+    }
+
+    fn part_two(model: &Self::Model) -> Result<impl Display> {
+        Ok(arrange_lenses(model).focusing_power())
+    }
+
+    fn query(model: &Self::Model, query: &[&str]) -> Result<String> {
+        match query {
+            ["hash", text] => Ok(hash(text).to_string()),
+            ["boxes", steps] => {
+                let steps: usize = query_argument(steps)?;
+                Ok(arrange_lenses(&model[..steps.min(model.len())]).describe())
+            }
+            _ => Err(AocError::unknown_query(query)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::input::daily_example;
+
+    use super::*;
+
+    fn example() -> Vec<Step> {
+        parse_steps(&daily_example(2023, 15).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn hashes_text() {
+        assert_eq!(hash("HASH"), 52);
+        assert_eq!(example().iter().map(|step| hash(&step.text)).collect::<Vec<_>>(), vec![30, 253, 97, 47, 14, 180, 9, 197, 48, 214, 231]);
+        assert_eq!(sum_of_hashes(&example()), 1320);
+    }
+
+    #[test]
+    fn parses_steps() {
+        let steps = example();
+        assert_eq!(steps[0], Step { text: "rn=1".to_owned(), label: "rn".to_owned(), operation: Operation::Insert(1) });
+        assert_eq!(steps[1].operation, Operation::Remove);
+        assert_eq!(parse_steps(&["rn=1,cm".to_owned()]).unwrap_err().to_string(), r#"line 1: expected - or = in step cm in "rn=1,cm""#);
+    }
+
+    #[test]
+    fn arranges_lenses_in_boxes() {
+        let steps = example();
+        assert_eq!(arrange_lenses(&steps[..4]).describe(), "Box 0: [rn 1] [cm 2]\nBox 1: [qp 3]");
+        let boxes = arrange_lenses(&steps);
+        assert_eq!(boxes.describe(), "Box 0: [rn 1] [cm 2]\nBox 3: [ot 7] [ab 5] [pc 6]");
+        assert_eq!(boxes.focusing_power(), 145);
+    }
+}
@@ -0,0 +1,182 @@
+use std::fmt::Display;
+
+use aoc2023_macros::aoc;
+use rayon::prelude::*;
+use tracing::debug;
+
+use crate::beam::{trace_beams, Beams};
+use crate::error::{AocError, Result};
+use crate::grid::{FromChar, Grid};
+use crate::puzzle::{Part, Puzzle, Variant};
+use crate::validate::Rule;
+use crate::vec2::Direction::{self, Down, Left, Right, Up};
+use crate::vec2::Vec2;
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+enum Tile {
+    Empty,
+    /// `/`
+    RisingMirror,
+    /// `\`
+    FallingMirror,
+    /// `|`
+    VerticalSplitter,
+    /// `-`
+    HorizontalSplitter,
+}
+
+impl FromChar for Tile {
+    fn from_char(c: char) -> std::result::Result<Tile, String> {
+        match c {
+            '.' => Ok(Tile::Empty),
+            '/' => Ok(Tile::RisingMirror),
+            '\\' => Ok(Tile::FallingMirror),
+            '|' => Ok(Tile::VerticalSplitter),
+            '-' => Ok(Tile::HorizontalSplitter),
+            _ => Err(format!("unknown tile {}", c)),
+        }
+    }
+}
+
+impl Tile {
+    /// Headings a beam entering the tile heading `heading` leaves it in.
+    fn redirect(&self, heading: Direction) -> Vec<Direction> {
+        match (self, heading) {
+            (Tile::RisingMirror, Up | Down) | (Tile::FallingMirror, Left | Right) => vec![heading.turn_right()],
+            (Tile::RisingMirror, Left | Right) | (Tile::FallingMirror, Up | Down) => vec![heading.turn_left()],
+            (Tile::VerticalSplitter, Left | Right) => vec![Up, Down],
+            (Tile::HorizontalSplitter, Up | Down) => vec![Left, Right],
+            _ => vec![heading],
+        }
+    }
+}
+
+pub struct Contraption {
+    grid: Grid<Tile>,
+}
+
+impl Contraption {
+    fn energize(&self, start: Vec2, heading: Direction) -> Beams {
+        trace_beams(&self.grid, start, heading, Tile::redirect)
+    }
+
+    /// Edge tiles a beam can enter the contraption by, with the heading it enters them in, clockwise from the top edge.
+    fn entries(&self) -> Vec<(Vec2, Direction)> {
+        let (width, height) = (self.grid.width() as isize, self.grid.height() as isize);
+        let top = (0..width).map(|x| (Vec2::new(x, 0), Down));
+        let right = (0..height).map(|y| (Vec2::new(width - 1, y), Left));
+        let bottom = (0..width).map(|x| (Vec2::new(x, height - 1), Up));
+        let left = (0..height).map(|y| (Vec2::new(0, y), Right));
+        top.chain(right).chain(bottom).chain(left).collect()
+    }
+
+    /// Most tiles energized by a beam entering from any edge, beams being traced on as many threads as available.
+    fn most_energized(&self) -> usize {
+        self.entries().into_par_iter().map(|(start, heading)| self.energize(start, heading).lit_count()).max().unwrap_or(0)
+    }
+
+    fn most_energized_sequentially(&self) -> usize {
+        self.entries().into_iter().map(|(start, heading)| self.energize(start, heading).lit_count()).max().unwrap_or(0)
+    }
+
+    /// Tiles crossed by the beam, `#` when energized and `.` otherwise.
+    fn energized(&self, beams: &Beams) -> String {
+        (0..self.grid.height())
+            .map(|y| (0..self.grid.width()).map(|x| if beams.is_lit((x, y)) { '#' } else { '.' }).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn parse_contraption(input: &[String]) -> Result<Contraption> {
+    let grid = Grid::parse(input)?;
+    debug!(width = grid.width(), height = grid.height(), "parsed contraption");
+    Ok(Contraption { grid })
+}
+
+pub struct Day16;
+
+#[aoc(day = 16)]
+impl Puzzle for Day16 {
+    const RULES: &'static [Rule] = &[Rule::MinLines(1), Rule::Rectangular, Rule::Charset(".|-/\\"), Rule::NoTrailingBlankLines];
+
+    const QUERIES: &'static [&'static str] = &["energized"];
+
+    type Model = Contraption;
+
+    fn parse(input: Vec<String>) -> Result<Self::Model> {
+        parse_contraption(&input)
+    }
+
+    fn part_one(model: &Self::Model) -> Result<impl Display> {
+        Ok(model.energize(Vec2::ZERO, Right).lit_count())
+    }
+
+    fn part_two(model: &Self::Model) -> Result<impl Display> {
+        Ok(model.most_energized())
+    }
+
+    fn query(model: &Self::Model, query: &[&str]) -> Result<String> {
+        match query {
+            ["energized"] => Ok(model.energized(&model.energize(Vec2::ZERO, Right))),
+            _ => Err(AocError::unknown_query(query)),
+        }
+    }
+
+    fn variants() -> Vec<Variant<Self::Model>> {
+        vec![Variant { name: "sequential", part: Part::Two, solve: |contraption| Ok(contraption.most_energized_sequentially().to_string()) }]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::input::daily_example;
+
+    use super::*;
+
+    fn example() -> Contraption {
+        parse_contraption(&daily_example(2023, 16).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn reflects_and_splits_beams() {
+        assert_eq!(Tile::RisingMirror.redirect(Right), vec![Up]);
+        assert_eq!(Tile::FallingMirror.redirect(Right), vec![Down]);
+        assert_eq!(Tile::VerticalSplitter.redirect(Left), vec![Up, Down]);
+        assert_eq!(Tile::VerticalSplitter.redirect(Up), vec![Up]);
+    }
+
+    #[test]
+    fn energizes_tiles_crossed_by_the_beam() {
+        let contraption = example();
+        let beams = contraption.energize(Vec2::ZERO, Right);
+        assert_eq!(beams.lit_count(), 46);
+        assert_eq!(contraption.energized(&beams), [
+            "######....",
+            ".#...#....",
+            ".#...#####",
+            ".#...##...",
+            ".#...##...",
+            ".#...##...",
+            ".#..####..",
+            "########..",
+            ".#######..",
+            ".#...#.#..",
+        ].join("\n"));
+    }
+
+    #[test]
+    fn enters_from_every_edge_tile() {
+        let entries = example().entries();
+        assert_eq!(entries.len(), 40);
+        assert!(entries.contains(&(Vec2::new(3, 0), Down)) && entries.contains(&(Vec2::new(9, 9), Left)));
+    }
+
+    #[test]
+    fn finds_the_entry_energizing_most_tiles() {
+        let contraption = example();
+        assert_eq!(contraption.energize(Vec2::new(3, 0), Down).lit_count(), 51);
+        assert_eq!(contraption.most_energized(), 51);
+        assert_eq!(contraption.most_energized_sequentially(), 51);
+    }
+}
@@ -0,0 +1,164 @@
+use std::fmt::Display;
+
+use aoc2023_macros::aoc;
+use tracing::debug;
+
+use crate::constrained_path::{cheapest_constrained_path, RunLimits};
+use crate::error::{AocError, Result};
+use crate::grid::Grid;
+use crate::puzzle::Puzzle;
+use crate::render::{render_grid, Color, Style};
+use crate::svg::{Paint, Svg};
+use crate::validate::Rule;
+use crate::vec2::Vec2;
+
+/// A crucible moves at most three blocks in a row.
+const CRUCIBLE: RunLimits = RunLimits { min: 0, max: 3 };
+
+/// An ultra crucible moves at least four blocks in a row, stopping included, and at most ten.
+const ULTRA_CRUCIBLE: RunLimits = RunLimits { min: 4, max: 10 };
+
+/// Heat lost entering each city block.
+pub struct City {
+    blocks: Grid<usize>,
+}
+
+impl City {
+    fn factory(&self) -> Vec2 {
+        Vec2::new(self.blocks.width() as isize - 1, self.blocks.height() as isize - 1)
+    }
+
+    /// Blocks a crucible bound by `limits` goes through from the lava pool in the top-left corner to the factory in
+    /// the bottom-right corner losing the least heat, with the heat lost.
+    fn best_route(&self, limits: RunLimits) -> Result<(Vec<Vec2>, usize)> {
+        cheapest_constrained_path(&self.blocks, Vec2::ZERO, self.factory(), limits, |&loss| loss)
+            .ok_or_else(|| AocError::unsolvable(format!("no route to the factory moving {} to {} blocks in a row", limits.min, limits.max)))
+    }
+
+    fn least_heat_loss(&self, limits: RunLimits) -> Result<usize> {
+        Ok(self.best_route(limits)?.1)
+    }
+
+    /// Heat losses with the blocks of the best route replaced by arrows showing where the crucible went, in bold
+    /// yellow, as in the puzzle.
+    fn render(&self, limits: RunLimits) -> Result<String> {
+        let (route, _) = self.best_route(limits)?;
+        let mut rows: Vec<Vec<char>> = self.blocks.rows().map(|row| row.iter().map(|loss| char::from(b'0' + *loss as u8)).collect()).collect();
+        for (from, to) in route.iter().zip(route.iter().skip(1)) {
+            rows[to.y as usize][to.x as usize] = match (to.x - from.x, to.y - from.y) {
+                (1, _) => '>',
+                (-1, _) => '<',
+                (_, 1) => 'v',
+                _ => '^',
+            };
+        }
+        let rows: Vec<String> = rows.into_iter().map(String::from_iter).collect();
+        Ok(render_grid(&rows, |x, y, _| {
+            if route.contains(&Vec2::new(x as isize, y as isize)) { Style::fg(Color::Yellow).bold() } else { Style::PLAIN }
+        }))
+    }
+
+    /// The city as an SVG image, blocks shaded darker the more heat they lose and the best route drawn over them in red.
+    fn to_svg(&self, limits: RunLimits) -> Result<Svg> {
+        let (route, _) = self.best_route(limits)?;
+        let shade = |loss: usize| 255 - loss.min(9) * 20;
+        let mut svg = Svg::new(10);
+        svg.grid(&self.blocks, |_, &loss| Some(Paint::fill(&format!("#{0:02x}{0:02x}{0:02x}", shade(loss)))))
+            .path(&route, &Paint::stroke(Color::Red.name(), 0.3));
+        Ok(svg)
+    }
+}
+
+fn crucible_limits(crucible: &str) -> Result<RunLimits> {
+    match crucible {
+        "crucible" => Ok(CRUCIBLE),
+        "ultra" => Ok(ULTRA_CRUCIBLE),
+        _ => Err(AocError::Query(format!("unknown crucible {}, expected crucible or ultra", crucible))),
+    }
+}
+
+fn parse_city(input: &[String]) -> Result<City> {
+    let blocks = Grid::parse_with(input, |c| c.to_digit(10).map(|loss| loss as usize).ok_or(format!("invalid heat loss {}", c)))?;
+    debug!(width = blocks.width(), height = blocks.height(), "parsed city");
+    Ok(City { blocks })
+}
+
+pub struct Day17;
+
+#[aoc(day = 17)]
+impl Puzzle for Day17 {
+    const RULES: &'static [Rule] = &[Rule::MinLines(1), Rule::Rectangular, Rule::Charset("0123456789"), Rule::NoTrailingBlankLines];
+
+    const QUERIES: &'static [&'static str] = &["route <crucible|ultra>", "svg <crucible|ultra>"];
+
+    type Model = City;
+
+    fn parse(input: Vec<String>) -> Result<Self::Model> {
+        parse_city(&input)
+    }
+
+    fn part_one(model: &Self::Model) -> Result<impl Display> {
+        model.least_heat_loss(CRUCIBLE)
+    }
+
+    fn part_two(model: &Self::Model) -> Result<impl Display> {
+        model.least_heat_loss(ULTRA_CRUCIBLE)
+    }
+
+    fn query(model: &Self::Model, query: &[&str]) -> Result<String> {
+        match query {
+            ["route", crucible] => Ok(model.render(crucible_limits(crucible)?)?.trim_end().to_owned()),
+            ["svg", crucible] => Ok(model.to_svg(crucible_limits(crucible)?)?.to_string().trim_end().to_owned()),
+            _ => Err(AocError::unknown_query(query)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::input::daily_example;
+
+    use super::*;
+
+    fn example() -> City {
+        parse_city(&daily_example(2023, 17).unwrap()).unwrap()
+    }
+
+    fn city(lines: &[&str]) -> City {
+        parse_city(&lines.iter().map(|line| line.to_string()).collect::<Vec<_>>()).unwrap()
+    }
+
+    #[test]
+    fn guides_crucibles_losing_the_least_heat() {
+        assert_eq!(example().least_heat_loss(CRUCIBLE).unwrap(), 102);
+    }
+
+    #[test]
+    fn guides_ultra_crucibles_losing_the_least_heat() {
+        assert_eq!(example().least_heat_loss(ULTRA_CRUCIBLE).unwrap(), 94);
+        let city = city(&["111111111111", "999999999991", "999999999991", "999999999991", "999999999991"]);
+        assert_eq!(city.least_heat_loss(ULTRA_CRUCIBLE).unwrap(), 71);
+    }
+
+    #[test]
+    fn draws_the_best_route_over_the_city() {
+        let city = city(&["11199", "99111"]);
+        assert_eq!(city.least_heat_loss(CRUCIBLE).unwrap(), 5);
+        let plain = city.render(CRUCIBLE).unwrap().replace("\x1b[1;33m", "").replace("\x1b[0m", "");
+        assert_eq!(plain, "1>>99\n99v>>\n");
+        assert!(city.to_svg(CRUCIBLE).unwrap().to_string().contains(r#"<polyline points="0.5,0.5 1.5,0.5 2.5,0.5 2.5,1.5 3.5,1.5 4.5,1.5""#));
+        assert_eq!(crucible_limits("ultra").unwrap(), ULTRA_CRUCIBLE);
+        assert_eq!(crucible_limits("cart").unwrap_err().to_string(), "cannot answer: unknown crucible cart, expected crucible or ultra");
+    }
+
+    #[test]
+    fn finds_the_factory_at_the_lava_pool_of_a_single_block_city() {
+        assert_eq!(city(&["7"]).least_heat_loss(CRUCIBLE).unwrap(), 0);
+    }
+
+    #[test]
+    fn reports_unreachable_factories() {
+        let city = city(&["11", "11"]);
+        assert_eq!(city.least_heat_loss(ULTRA_CRUCIBLE).unwrap_err().to_string(), "no solution: no route to the factory moving 4 to 10 blocks in a row");
+    }
+}
@@ -0,0 +1,179 @@
+use std::fmt::Display;
+
+use aoc2023_macros::aoc;
+use tracing::debug;
+
+use crate::error::{parse_lines, AocError, Result};
+use crate::parse::parse_all;
+use crate::polygon::{boundary_points, double_area};
+use crate::puzzle::Puzzle;
+use crate::render::Color;
+use crate::svg::{Paint, Svg};
+use crate::validate::Rule;
+use crate::vec2::{Direction, Vec2};
+
+/// A dig instruction, with the color of the edge as it is written in the dig plan.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct Instruction {
+    direction: Direction,
+    meters: isize,
+    color: u32,
+}
+
+impl Instruction {
+    fn parse(line: &str) -> std::result::Result<Instruction, String> {
+        parse_all(line, |parser| {
+            let direction = direction(parser.word()?)?;
+            parser.skip_spaces();
+            let meters = parser.integer()?;
+            parser.skip_spaces();
+            parser.tag("(#")?;
+            let color = parser.word()?;
+            parser.tag(")")?;
+            if color.len() != 6 {
+                return Err(format!("expected 6 hexadecimal digits in color {}", color));
+            }
+            let color = u32::from_str_radix(color, 16).map_err(|_| format!("invalid color {}", color))?;
+            if color & 0xf > 3 {
+                return Err(format!("color {:06x} does not end with a direction digit", color));
+            }
+            Ok(Instruction { direction, meters, color })
+        })
+    }
+
+    /// The instruction hidden in the color, the first five hexadecimal digits giving the meters and the last one
+    /// the direction: `0` for right, `1` for down, `2` for left and `3` for up.
+    fn decoded(&self) -> Instruction {
+        let direction = [Direction::Right, Direction::Down, Direction::Left, Direction::Up][(self.color & 0xf) as usize];
+        Instruction { direction, meters: (self.color >> 4) as isize, ..*self }
+    }
+}
+
+fn direction(letter: &str) -> std::result::Result<Direction, String> {
+    match letter {
+        "U" => Ok(Direction::Up),
+        "R" => Ok(Direction::Right),
+        "D" => Ok(Direction::Down),
+        "L" => Ok(Direction::Left),
+        _ => Err(format!("unknown direction {}", letter)),
+    }
+}
+
+fn parse_dig_plan(input: &[String]) -> Result<Vec<Instruction>> {
+    let instructions = parse_lines(input, 0, Instruction::parse)?;
+    debug!(instructions = instructions.len(), "parsed dig plan");
+    Ok(instructions)
+}
+
+/// Corners of the trench dug from the starting cube, the first corner being the one it ends on.
+fn trench(instructions: &[Instruction]) -> Vec<Vec2> {
+    instructions.iter()
+        .scan(Vec2::ZERO, |position, instruction| {
+            *position += instruction.direction.offset() * instruction.meters;
+            Some(*position)
+        })
+        .collect()
+}
+
+/// Cubic meters of lava held by the lagoon of the trench with the given corners, one meter deep. The Shoelace formula
+/// gives the area of the polygon through the centers of the trench cubes, which leaves out the outer half of every
+/// cube along its edges plus the outer corners that the inner ones do not make up for: a full cube around the loop.
+fn lagoon_volume(corners: &[Vec2]) -> usize {
+    (double_area(corners) + boundary_points(corners)) / 2 + 1
+}
+
+fn volume_of(instructions: &[Instruction]) -> Result<usize> {
+    let corners = trench(instructions);
+    if corners.last() != Some(&Vec2::ZERO) {
+        return Err(AocError::unsolvable("the trench does not loop back to its start"));
+    }
+    Ok(lagoon_volume(&corners))
+}
+
+fn decoded(instructions: &[Instruction]) -> Vec<Instruction> {
+    instructions.iter().map(Instruction::decoded).collect()
+}
+
+pub struct Day18;
+
+#[aoc(day = 18)]
+impl Puzzle for Day18 {
+    const RULES: &'static [Rule] = &[Rule::MinLines(1), Rule::NoTrailingBlankLines];
+
+    const QUERIES: &'static [&'static str] = &["corners", "svg"];
+
+    type Model = Vec<Instruction>;
+
+    fn parse(input: Vec<String>) -> Result<Self::Model> {
+        parse_dig_plan(&input)
+    }
+
+    fn part_one(model: &Self::Model) -> Result<impl Display> {
+        volume_of(model)
+    }
+
+    fn part_two(model: &Self::Model) -> Result<impl Display> {
+        volume_of(&decoded(model))
+    }
+
+    fn query(model: &Self::Model, query: &[&str]) -> Result<String> {
+        match query {
+            ["corners"] => Ok(trench(model).iter().map(|corner| format!("{},{}", corner.x, corner.y)).collect::<Vec<_>>().join(" ")),
+            ["svg"] => {
+                let mut svg = Svg::new(10);
+                svg.polygon(&trench(model), &Paint::fill(Color::Red.name()).outlined(Color::Black.name(), 0.2));
+                Ok(svg.to_string().trim_end().to_owned())
+            }
+            _ => Err(AocError::unknown_query(query)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::input::daily_example;
+
+    use super::*;
+
+    fn example() -> Vec<Instruction> {
+        parse_dig_plan(&daily_example(2023, 18).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn parses_dig_instructions() {
+        assert_eq!(example()[0], Instruction { direction: Direction::Right, meters: 6, color: 0x70c710 });
+        assert_eq!(Instruction::parse("U 2 (#7a21e5)").unwrap_err(), "color 7a21e5 does not end with a direction digit");
+        assert_eq!(Instruction::parse("N 2 (#7a21e3)").unwrap_err(), "unknown direction N");
+        assert_eq!(Instruction::parse("U 2 (#7a21e3) x").unwrap_err(), r#"unexpected " x""#);
+    }
+
+    #[test]
+    fn follows_the_trench_around_its_corners() {
+        let corners = trench(&example());
+        assert_eq!(corners.len(), 14);
+        assert_eq!(corners[..3], [Vec2::new(6, 0), Vec2::new(6, 5), Vec2::new(4, 5)]);
+        assert_eq!(corners.last(), Some(&Vec2::ZERO));
+    }
+
+    #[test]
+    fn measures_the_lagoon_volume() {
+        assert_eq!(volume_of(&example()).unwrap(), 62);
+        let square: Vec<String> = ["R 2 (#000000)", "D 2 (#000000)", "L 2 (#000000)", "U 2 (#000000)"].map(str::to_owned).to_vec();
+        let square = parse_dig_plan(&square).unwrap();
+        assert_eq!(volume_of(&square).unwrap(), 9);
+    }
+
+    #[test]
+    fn decodes_instructions_from_colors() {
+        let decoded = decoded(&example());
+        assert_eq!((decoded[0].direction, decoded[0].meters), (Direction::Right, 461937));
+        assert_eq!((decoded[13].direction, decoded[13].meters), (Direction::Up, 500254));
+        assert_eq!(volume_of(&decoded).unwrap(), 952408144115);
+    }
+
+    #[test]
+    fn reports_trenches_not_looping_back() {
+        let open = parse_dig_plan(&["R 2 (#000000)".to_owned()]).unwrap();
+        assert_eq!(volume_of(&open).unwrap_err().to_string(), "no solution: the trench does not loop back to its start");
+    }
+}
@@ -0,0 +1,96 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+type Attributes = Vec<(String, String)>;
+
+/// A graph described in the DOT language of GraphViz.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct DotGraph {
+    name: String,
+    directed: bool,
+    nodes: Vec<(String, Attributes)>,
+    edges: Vec<(String, String, Attributes)>,
+}
+
+impl DotGraph {
+    pub fn directed(name: &str) -> DotGraph {
+        DotGraph { name: name.to_owned(), directed: true, nodes: Vec::new(), edges: Vec::new() }
+    }
+
+    pub fn undirected(name: &str) -> DotGraph {
+        DotGraph { directed: false, ..DotGraph::directed(name) }
+    }
+
+    /// Declares a node with attributes such as `("shape", "box")`. Nodes only met in edges need no declaration.
+    pub fn node(&mut self, id: &str, attributes: &[(&str, &str)]) -> &mut DotGraph {
+        self.nodes.push((id.to_owned(), owned(attributes)));
+        self
+    }
+
+    pub fn edge(&mut self, from: &str, to: &str, attributes: &[(&str, &str)]) -> &mut DotGraph {
+        self.edges.push((from.to_owned(), to.to_owned(), owned(attributes)));
+        self
+    }
+
+    /// Writes the graph to `path`, ready for `dot -Tsvg`.
+    pub fn write(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+}
+
+fn owned(attributes: &[(&str, &str)]) -> Attributes {
+    attributes.iter().map(|&(key, value)| (key.to_owned(), value.to_owned())).collect()
+}
+
+/// Identifier quoted and escaped as a DOT string.
+fn quoted(id: &str) -> String {
+    format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn attribute_list(attributes: &[(String, String)]) -> String {
+    if attributes.is_empty() {
+        return String::new();
+    }
+    let attributes: Vec<String> = attributes.iter().map(|(key, value)| format!("{}={}", key, quoted(value))).collect();
+    format!(" [{}]", attributes.join(", "))
+}
+
+impl Display for DotGraph {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (keyword, arrow) = if self.directed { ("digraph", "->") } else { ("graph", "--") };
+        writeln!(f, "{} {} {{", keyword, quoted(&self.name))?;
+        for (id, attributes) in &self.nodes {
+            writeln!(f, "  {}{};", quoted(id), attribute_list(attributes))?;
+        }
+        for (from, to, attributes) in &self.edges {
+            writeln!(f, "  {} {} {}{};", quoted(from), arrow, quoted(to), attribute_list(attributes))?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/// A structure that can be drawn as a graph, to inspect it with GraphViz.
+pub trait ToDot {
+    fn to_dot(&self) -> DotGraph;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_dot_language() {
+        let mut graph = DotGraph::directed("network");
+        graph.node("AAA", &[("shape", "box")]).edge("AAA", "BBB", &[("label", "L")]).edge("BBB", "\"Z\"", &[]);
+        assert_eq!(graph.to_string(), concat!(
+            "digraph \"network\" {\n",
+            "  \"AAA\" [shape=\"box\"];\n",
+            "  \"AAA\" -> \"BBB\" [label=\"L\"];\n",
+            "  \"BBB\" -> \"\\\"Z\\\"\";\n",
+            "}\n",
+        ));
+        assert!(DotGraph::undirected("wires").edge("a", "b", &[]).to_string().contains("\"a\" -- \"b\";"));
+    }
+}
@@ -0,0 +1,17 @@
+include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+
+pub fn embedded_input(year: u32, day: u32) -> Option<&'static str> {
+    INPUTS.iter().find(|&&(y, d, _)| y == year && d == day).map(|&(_, _, input)| input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(not(feature = "personal-inputs"), ignore = "requires personal puzzle inputs")]
+    fn embeds_inputs_found_at_build_time() {
+        assert_eq!(embedded_input(2023, 6), Some(include_str!("inputs/2023/day06.txt")));
+        assert_eq!(embedded_input(2023, 26), None);
+    }
+}
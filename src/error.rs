@@ -0,0 +1,107 @@
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub enum AocError {
+    Parse { line: usize, content: String, reason: String },
+    Io(io::Error),
+    Unsolvable(String),
+    OverBudget(Duration),
+    Query(String),
+}
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+impl AocError {
+    pub fn parse(line: usize, content: &str, reason: impl Display) -> AocError {
+        AocError::Parse { line, content: content.to_owned(), reason: reason.to_string() }
+    }
+
+    pub fn unsolvable(reason: impl Display) -> AocError {
+        AocError::Unsolvable(reason.to_string())
+    }
+
+    pub fn unknown_query(query: &[&str]) -> AocError {
+        AocError::Query(format!("unknown query {:?}", query.join(" ")))
+    }
+
+    /// Error raised parsing a block of lines, reported at its line in an input where the block starts after `offset`
+    /// lines.
+    pub fn offset_by(self, offset: usize) -> AocError {
+        match self {
+            AocError::Parse { line, content, reason } => AocError::Parse { line: line + offset, content, reason },
+            error => error,
+        }
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::Parse { line, content, reason } => write!(f, "line {}: {} in {:?}", line, reason, content),
+            AocError::Io(error) => write!(f, "{}", error),
+            AocError::Unsolvable(reason) => write!(f, "no solution: {}", reason),
+            AocError::OverBudget(budget) => write!(f, "over time budget of {:?}", budget),
+            AocError::Query(reason) => write!(f, "cannot answer: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+impl From<io::Error> for AocError {
+    fn from(error: io::Error) -> AocError {
+        AocError::Io(error)
+    }
+}
+
+pub fn parse_line<T>(lines: &[String], index: usize, parse: impl FnOnce(&str) -> std::result::Result<T, String>) -> Result<T> {
+    let line = lines.get(index).ok_or_else(|| AocError::parse(index + 1, "", "missing line"))?;
+    parse(line).map_err(|reason| AocError::parse(index + 1, line, reason))
+}
+
+pub fn parse_lines<T>(lines: &[String], from: usize, parse: impl Fn(&str) -> std::result::Result<T, String>) -> Result<Vec<T>> {
+    (from..lines.len()).map(|index| parse_line(lines, index, &parse)).collect()
+}
+
+pub fn query_argument<T: FromStr>(argument: &str) -> Result<T> {
+    argument.parse().map_err(|_| AocError::Query(format!("invalid argument {}", argument)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(input: &[&str]) -> Vec<String> {
+        input.iter().map(|line| line.to_string()).collect()
+    }
+
+    fn number(line: &str) -> std::result::Result<u32, String> {
+        line.parse().map_err(|_| "expected a number".to_owned())
+    }
+
+    #[test]
+    fn reports_line_of_malformed_input() {
+        let error = parse_lines(&lines(&["1", "2", "three"]), 0, number).unwrap_err();
+        assert_eq!(error.to_string(), r#"line 3: expected a number in "three""#);
+    }
+
+    #[test]
+    fn reports_missing_lines() {
+        let error = parse_line(&lines(&["1"]), 2, number).unwrap_err();
+        assert_eq!(error.to_string(), r#"line 3: missing line in """#);
+    }
+
+    #[test]
+    fn reports_line_of_malformed_block_in_input() {
+        let error = parse_lines(&lines(&["1", "two"]), 0, number).unwrap_err().offset_by(3);
+        assert_eq!(error.to_string(), r#"line 5: expected a number in "two""#);
+    }
+
+    #[test]
+    fn parses_lines_from_offset() {
+        assert_eq!(parse_lines(&lines(&["header", "", "1", "2"]), 2, number).unwrap(), vec![1, 2]);
+    }
+}
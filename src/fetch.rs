@@ -0,0 +1,94 @@
+use std::env;
+use std::fs;
+use std::io;
+
+use regex::Regex;
+
+use crate::config::config;
+
+const AOC_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/testinfected/aoc2023";
+
+fn session() -> io::Result<String> {
+    match (env::var("AOC_SESSION"), &config().session_file) {
+        (Ok(session), _) => Ok(session),
+        (Err(_), Some(path)) => fs::read_to_string(path),
+        (Err(_), None) => Err(io::Error::new(io::ErrorKind::NotFound, "AOC_SESSION is not set")),
+    }
+}
+
+fn session_cookie() -> io::Result<String> {
+    session().map(|session| format!("session={}", session.trim()))
+}
+
+fn puzzle_url(year: u32, day: u32, page: &str) -> String {
+    format!("{}/{}/day/{}{}", AOC_URL, year, day, page)
+}
+
+/// Whether a download failed for lack of a connection to the server, rather than the server turning the request down.
+pub fn is_transport_error(error: &io::Error) -> bool {
+    error.get_ref()
+        .and_then(|error| error.downcast_ref::<ureq::Error>())
+        .is_some_and(|error| matches!(error, ureq::Error::Transport(_)))
+}
+
+pub fn download_input(year: u32, day: u32) -> io::Result<String> {
+    ureq::get(&puzzle_url(year, day, "/input"))
+        .set("Cookie", &session_cookie()?)
+        .set("User-Agent", USER_AGENT)
+        .call()
+        .map_err(io::Error::other)?
+        .into_string()
+}
+
+pub fn download_puzzle(year: u32, day: u32) -> io::Result<String> {
+    let request = ureq::get(&puzzle_url(year, day, "")).set("User-Agent", USER_AGENT);
+    let request = match session_cookie() {
+        Ok(cookie) => request.set("Cookie", &cookie),
+        Err(_) => request,
+    };
+    request.call().map_err(io::Error::other)?.into_string()
+}
+
+fn unescape(html: &str) -> String {
+    let text = Regex::new(r"<[^>]*>").unwrap().replace_all(html, "");
+    text.replace("&lt;", "<").replace("&gt;", ">").replace("&quot;", "\"").replace("&#39;", "'").replace("&amp;", "&")
+}
+
+pub fn extract_example(page: &str) -> Option<String> {
+    let re = Regex::new(r"(?s)<pre><code>(?<example>.*?)</code></pre>").unwrap();
+    re.captures(page).map(|captures| unescape(&captures["example"]))
+}
+
+pub fn post_answer(year: u32, day: u32, part: u8, answer: &str) -> io::Result<String> {
+    ureq::post(&puzzle_url(year, day, "/answer"))
+        .set("Cookie", &session_cookie()?)
+        .set("User-Agent", USER_AGENT)
+        .send_form(&[("level", &part.to_string()), ("answer", answer)])
+        .map_err(io::Error::other)?
+        .into_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_first_code_block_of_puzzle_page() {
+        let page = "<p>For example:</p>\n<pre><code>32T3K 765\n<em>T55J5</em> 684\n</code></pre>\n<pre><code>other</code></pre>";
+        assert_eq!(extract_example(page), Some("32T3K 765\nT55J5 684\n".to_owned()));
+    }
+
+    #[test]
+    fn unescapes_html_entities() {
+        let page = "<pre><code>-L|F7\n7S-7|\n&lt;&gt;&amp;\n</code></pre>";
+        assert_eq!(extract_example(page), Some("-L|F7\n7S-7|\n<>&\n".to_owned()));
+    }
+
+    #[test]
+    fn tells_transport_errors_from_other_failures() {
+        let unreachable = ureq::get("http://localhost:0/input").call().map_err(io::Error::other).unwrap_err();
+        assert!(is_transport_error(&unreachable));
+        assert!(!is_transport_error(&io::Error::new(io::ErrorKind::NotFound, "AOC_SESSION is not set")));
+    }
+}
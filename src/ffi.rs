@@ -0,0 +1,90 @@
+use std::cell::RefCell;
+use std::ffi::{c_char, CString};
+use std::ptr;
+use std::slice;
+
+use crate::error::{AocError, Result};
+use crate::puzzle::{solver, split_lines, Part, DEFAULT_YEAR};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn solve(day: u32, part: u8, input: &[u8]) -> Result<String> {
+    let solver = solver(DEFAULT_YEAR, day).ok_or_else(|| AocError::unsolvable(format!("Day {} is not solved yet", day)))?;
+    let part = Part::from_number(part).ok_or_else(|| AocError::unsolvable(format!("Part {} does not exist", part)))?;
+    let input = std::str::from_utf8(input).map_err(|e| AocError::parse(1, "", e.to_string()))?;
+    Ok((solver.solve)(part, split_lines(input))?.answer)
+}
+
+fn to_c_string(text: String) -> CString {
+    CString::new(text).unwrap_or_else(|e| CString::new(e.into_vec().into_iter().filter(|&b| b != 0).collect::<Vec<u8>>()).unwrap())
+}
+
+/// Solves `part` of `day` on the `len` bytes of UTF-8 text at `input`.
+///
+/// Returns the answer as a string to release with `aoc_free`, or null if the puzzle cannot be solved, in which case
+/// `aoc_last_error` tells why.
+///
+/// # Safety
+///
+/// `input` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(day: u32, part: u8, input: *const u8, len: usize) -> *mut c_char {
+    let input = if input.is_null() { &[] } else { slice::from_raw_parts(input, len) };
+    match solve(day, part, input) {
+        Ok(answer) => to_c_string(answer).into_raw(),
+        Err(error) => {
+            LAST_ERROR.with(|last| *last.borrow_mut() = Some(to_c_string(error.to_string())));
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Returns the error of the last call to `aoc_solve` that failed on this thread, or null if there is none.
+/// The message belongs to the library and stays valid until the next failure.
+#[no_mangle]
+pub extern "C" fn aoc_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr()))
+}
+
+/// Releases an answer returned by `aoc_solve`.
+///
+/// # Safety
+///
+/// `answer` must be null or have been returned by `aoc_solve`, and not have been released yet.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_free(answer: *mut c_char) {
+    if !answer.is_null() {
+        drop(CString::from_raw(answer));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+
+    use super::*;
+
+    fn call(day: u32, part: u8, input: &str) -> std::result::Result<String, String> {
+        unsafe {
+            let answer = aoc_solve(day, part, input.as_ptr(), input.len());
+            if answer.is_null() {
+                return Err(CStr::from_ptr(aoc_last_error()).to_string_lossy().into_owned());
+            }
+            let text = CStr::from_ptr(answer).to_string_lossy().into_owned();
+            aoc_free(answer);
+            Ok(text)
+        }
+    }
+
+    #[test]
+    fn solves_puzzle_given_as_bytes() {
+        assert_eq!(call(7, 2, include_str!("examples/2023/day07.txt")), Ok("5905".to_owned()));
+    }
+
+    #[test]
+    fn reports_why_puzzle_cannot_be_solved() {
+        assert_eq!(call(7, 3, ""), Err("no solution: Part 3 does not exist".to_owned()));
+    }
+}
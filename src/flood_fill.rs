@@ -0,0 +1,100 @@
+use std::collections::VecDeque;
+
+use crate::grid::Grid;
+
+/// Cells that count as adjacent to a cell.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Connectivity {
+    /// The cells above, right, below and left.
+    Four,
+    /// The eight cells around, diagonals included.
+    Eight,
+}
+
+fn adjacent<T>(grid: &Grid<T>, position: (usize, usize), connectivity: Connectivity) -> Box<dyn Iterator<Item=(usize, usize)> + '_> {
+    match connectivity {
+        Connectivity::Four => Box::new(grid.neighbors(position)),
+        Connectivity::Eight => Box::new(grid.surroundings(position)),
+    }
+}
+
+/// Positions reachable from `start`, in breadth-first order, by moving to adjacent cells whenever `can_pass` from the
+/// cell moved from to the cell moved to.
+pub fn flood_fill<T>(grid: &Grid<T>, start: (usize, usize), connectivity: Connectivity, can_pass: impl Fn(&T, &T) -> bool) -> Vec<(usize, usize)> {
+    let mut visited = grid.map(|_| false);
+    visited[start] = true;
+    let mut filled = Vec::new();
+    let mut queue = VecDeque::from([start]);
+    while let Some(position) = queue.pop_front() {
+        filled.push(position);
+        for next in adjacent(grid, position, connectivity) {
+            if !visited[next] && can_pass(&grid[position], &grid[next]) {
+                visited[next] = true;
+                queue.push_back(next);
+            }
+        }
+    }
+    filled
+}
+
+/// Cells of a grid labeled by connected component.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Components {
+    /// Component of each cell, numbered in the order of their first cell, row by row.
+    pub labels: Grid<usize>,
+    /// Number of cells of each component.
+    pub sizes: Vec<usize>,
+}
+
+impl Components {
+    pub fn count(&self) -> usize {
+        self.sizes.len()
+    }
+
+    pub fn positions_of(&self, label: usize) -> impl Iterator<Item=(usize, usize)> + '_ {
+        self.labels.cells().filter(move |&(_, &l)| l == label).map(|(position, _)| position)
+    }
+}
+
+/// Splits the grid into components of cells connected to each other as by [`flood_fill`]. `can_pass` should be
+/// symmetric for components to be well-defined.
+pub fn components<T>(grid: &Grid<T>, connectivity: Connectivity, can_pass: impl Fn(&T, &T) -> bool) -> Components {
+    let mut labels = grid.map(|_| usize::MAX);
+    let mut sizes = Vec::new();
+    for y in 0..grid.height() {
+        for x in 0..grid.width() {
+            if labels[(x, y)] != usize::MAX {
+                continue;
+            }
+            let component = flood_fill(grid, (x, y), connectivity, &can_pass);
+            component.iter().for_each(|&position| labels[position] = sizes.len());
+            sizes.push(component.len());
+        }
+    }
+    Components { labels, sizes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn garden() -> Grid<char> {
+        Grid::of_chars(&["AAB".to_owned(), "BAB".to_owned(), "BBA".to_owned()]).unwrap()
+    }
+
+    #[test]
+    fn fills_passable_cells() {
+        let open = |_: &char, to: &char| *to == 'A';
+        assert_eq!(flood_fill(&garden(), (0, 0), Connectivity::Four, open), vec![(0, 0), (1, 0), (1, 1)]);
+        assert_eq!(flood_fill(&garden(), (0, 0), Connectivity::Eight, open).len(), 4);
+    }
+
+    #[test]
+    fn labels_connected_components() {
+        let regions = components(&garden(), Connectivity::Four, |a, b| a == b);
+        assert_eq!(regions.sizes, vec![3, 2, 3, 1]);
+        assert_eq!(regions.labels.row(2), &[2, 2, 3]);
+        assert_eq!(regions.positions_of(1).collect::<Vec<_>>(), vec![(2, 0), (2, 1)]);
+        assert_eq!(components(&garden(), Connectivity::Eight, |a, b| a == b).count(), 2);
+    }
+}
@@ -0,0 +1,176 @@
+use num::Zero;
+
+use crate::linear::Rational;
+use crate::vec2::Vec2;
+
+/// A point of the plane with exact fractional coordinates.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash)]
+pub struct RationalPoint {
+    pub x: Rational,
+    pub y: Rational,
+}
+
+impl RationalPoint {
+    /// Whether the point lies in the square spanning from `min` to `max` on both axes, bounds included.
+    pub fn is_within(&self, min: Rational, max: Rational) -> bool {
+        (min..=max).contains(&self.x) && (min..=max).contains(&self.y)
+    }
+}
+
+/// Parameters `t` and `u` such that `p + t·d` and `q + u·e` are the same point, unless directions are parallel.
+fn crossing(p: Vec2, d: Vec2, q: Vec2, e: Vec2) -> Option<(Rational, Rational)> {
+    let denominator = cross(d, e);
+    if denominator.is_zero() {
+        return None;
+    }
+    let offset = q - p;
+    Some((Rational::new(cross(offset, e), denominator), Rational::new(cross(offset, d), denominator)))
+}
+
+fn point_along(origin: Vec2, direction: Vec2, t: Rational) -> RationalPoint {
+    let coordinate = |origin: isize, direction: isize| Rational::from(origin as i128) + t * Rational::from(direction as i128);
+    RationalPoint { x: coordinate(origin.x, direction.x), y: coordinate(origin.y, direction.y) }
+}
+
+/// An infinite line through `point` going along `direction`.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash)]
+pub struct Line {
+    pub point: Vec2,
+    pub direction: Vec2,
+}
+
+impl Line {
+    /// Single point both lines go through, none when they are parallel, even if they are the same line.
+    pub fn intersection(&self, other: &Line) -> Option<RationalPoint> {
+        let (t, _) = crossing(self.point, self.direction, other.point, other.direction)?;
+        Some(point_along(self.point, self.direction, t))
+    }
+}
+
+/// A half line starting at `origin` and going along `direction`, such as the future path of a moving point.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash)]
+pub struct Ray {
+    pub origin: Vec2,
+    pub direction: Vec2,
+}
+
+impl Ray {
+    /// Single point both rays go through, none when they are parallel.
+    pub fn intersection(&self, other: &Ray) -> Option<RationalPoint> {
+        let (t, u) = crossing(self.origin, self.direction, other.origin, other.direction)?;
+        (t >= Rational::zero() && u >= Rational::zero()).then(|| point_along(self.origin, self.direction, t))
+    }
+}
+
+/// The segment from `start` to `end`, both included.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash)]
+pub struct Segment {
+    pub start: Vec2,
+    pub end: Vec2,
+}
+
+impl Segment {
+    /// Single point both segments go through, none when they are parallel.
+    pub fn intersection(&self, other: &Segment) -> Option<RationalPoint> {
+        let (d, e) = (self.end - self.start, other.end - other.start);
+        let (t, u) = crossing(self.start, d, other.start, e)?;
+        let unit = Rational::zero()..=Rational::from(1);
+        (unit.contains(&t) && unit.contains(&u)).then(|| point_along(self.start, d, t))
+    }
+
+    /// Whether `point` lies on the segment, ends included.
+    pub fn contains(&self, point: Vec2) -> bool {
+        cross(self.end - self.start, point - self.start) == 0
+            && (self.start.x.min(self.end.x)..=self.start.x.max(self.end.x)).contains(&point.x)
+            && (self.start.y.min(self.end.y)..=self.start.y.max(self.end.y)).contains(&point.y)
+    }
+}
+
+fn cross(a: Vec2, b: Vec2) -> i128 {
+    a.x as i128 * b.y as i128 - a.y as i128 * b.x as i128
+}
+
+/// Where a point lies relative to a polygon.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash)]
+pub enum Containment {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/// Where `point` lies relative to the polygon with the given `vertices`, in either winding order. Counts how many times
+/// the edges wind around the point, so that it does not depend on the parity of crossings on rays grazing vertices.
+pub fn containment(vertices: &[Vec2], point: Vec2) -> Containment {
+    let mut winding = 0;
+    for (&start, &end) in vertices.iter().zip(vertices.iter().cycle().skip(1)) {
+        if (Segment { start, end }).contains(point) {
+            return Containment::Boundary;
+        }
+        let side = cross(end - start, point - start);
+        if start.y <= point.y && end.y > point.y && side > 0 {
+            winding += 1;
+        } else if start.y > point.y && end.y <= point.y && side < 0 {
+            winding -= 1;
+        }
+    }
+    if winding == 0 { Containment::Outside } else { Containment::Inside }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::polygon::interior_points;
+
+    use super::*;
+
+    fn hailstone(x: isize, y: isize, dx: isize, dy: isize) -> Ray {
+        Ray { origin: Vec2::new(x, y), direction: Vec2::new(dx, dy) }
+    }
+
+    #[test]
+    fn crosses_future_paths_of_hailstones() {
+        let a = hailstone(19, 13, -2, 1);
+        let crossing = a.intersection(&hailstone(18, 19, -1, -1)).unwrap();
+        assert_eq!(crossing, RationalPoint { x: Rational::new(43, 3), y: Rational::new(46, 3) });
+        assert!(crossing.is_within(Rational::from(7), Rational::from(27)));
+        assert_eq!(a.intersection(&hailstone(20, 19, 1, -5)), None);
+        assert_eq!(hailstone(18, 19, -1, -1).intersection(&hailstone(20, 25, -2, -2)), None);
+    }
+
+    #[test]
+    fn crosses_lines_anywhere() {
+        let backwards = Line { point: Vec2::new(20, 19), direction: Vec2::new(1, -5) };
+        let crossing = Line { point: Vec2::new(19, 13), direction: Vec2::new(-2, 1) }.intersection(&backwards).unwrap();
+        assert_eq!(crossing, RationalPoint { x: Rational::new(193, 9), y: Rational::new(106, 9) });
+    }
+
+    #[test]
+    fn crosses_segments_within_their_ends() {
+        let diagonal = Segment { start: Vec2::new(0, 0), end: Vec2::new(4, 4) };
+        let crossing = diagonal.intersection(&Segment { start: Vec2::new(0, 4), end: Vec2::new(4, 0) });
+        assert_eq!(crossing, Some(RationalPoint { x: Rational::from(2), y: Rational::from(2) }));
+        assert_eq!(diagonal.intersection(&Segment { start: Vec2::new(0, 4), end: Vec2::new(1, 3) }), None);
+        assert!(diagonal.intersection(&Segment { start: Vec2::new(4, 4), end: Vec2::new(5, 0) }).is_some());
+    }
+
+    #[test]
+    fn locates_points_relative_to_polygons() {
+        let square = [Vec2::new(0, 0), Vec2::new(4, 0), Vec2::new(4, 4), Vec2::new(0, 4)];
+        assert_eq!(containment(&square, Vec2::new(2, 2)), Containment::Inside);
+        assert_eq!(containment(&square, Vec2::new(4, 1)), Containment::Boundary);
+        assert_eq!(containment(&square, Vec2::new(0, 4)), Containment::Boundary);
+        assert_eq!(containment(&square, Vec2::new(5, 0)), Containment::Outside);
+        assert_eq!(containment(&square, Vec2::new(-1, 4)), Containment::Outside);
+    }
+
+    #[test]
+    fn agrees_with_picks_theorem_on_concave_polygons() {
+        let lagoon = [(0, 0), (6, 0), (6, 5), (4, 5), (4, 7), (6, 7), (6, 9), (1, 9), (1, 7), (0, 7), (0, 5), (2, 5), (2, 2), (0, 2)]
+            .map(|(x, y)| Vec2::new(x, y));
+        for polygon in [lagoon.to_vec(), lagoon.iter().rev().copied().collect()] {
+            let inside = (-1..=7).flat_map(|x| (-1..=10).map(move |y| Vec2::new(x, y)))
+                .filter(|&point| containment(&polygon, point) == Containment::Inside)
+                .count();
+            assert_eq!(inside, interior_points(&polygon));
+        }
+    }
+}
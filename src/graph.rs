@@ -0,0 +1,371 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use crate::priority_queue::PriorityQueue;
+
+/// Nodes met during a search, each with the index of the node it was reached from.
+struct Discovered<N> {
+    nodes: Vec<(N, usize)>,
+    indices: HashMap<N, usize>,
+}
+
+impl<N: Eq + Hash + Clone> Discovered<N> {
+    fn new(start: N) -> Discovered<N> {
+        Discovered { nodes: vec![(start.clone(), usize::MAX)], indices: HashMap::from([(start, 0)]) }
+    }
+
+    fn path_to(&self, mut index: usize) -> Vec<N> {
+        let mut path = Vec::new();
+        while index != usize::MAX {
+            let (node, parent) = &self.nodes[index];
+            path.push(node.clone());
+            index = *parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Shortest path, in number of edges, from `start` to the first node satisfying `is_goal`.
+pub fn bfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I, mut is_goal: impl FnMut(&N) -> bool) -> Option<Vec<N>>
+where N: Eq + Hash + Clone, I: IntoIterator<Item=N> {
+    let mut discovered = Discovered::new(start);
+    let mut queue = VecDeque::from([0]);
+    while let Some(index) = queue.pop_front() {
+        let node = discovered.nodes[index].0.clone();
+        if is_goal(&node) {
+            return Some(discovered.path_to(index));
+        }
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = discovered.indices.entry(next.clone()) {
+                entry.insert(discovered.nodes.len());
+                queue.push_back(discovered.nodes.len());
+                discovered.nodes.push((next, index));
+            }
+        }
+    }
+    None
+}
+
+/// Number of edges from `start` to every node reachable from it.
+pub fn bfs_distances<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where N: Eq + Hash + Clone, I: IntoIterator<Item=N> {
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = distances.entry(next.clone()) {
+                entry.insert(distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// Nodes reachable from `start` in depth-first order, `start` first.
+pub fn dfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> Vec<N>
+where N: Eq + Hash + Clone, I: IntoIterator<Item=N>, I::IntoIter: DoubleEndedIterator {
+    let mut visited = HashSet::new();
+    let mut order = Vec::new();
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+        stack.extend(successors(&node).into_iter().rev().filter(|next| !visited.contains(next)));
+        order.push(node);
+    }
+    order
+}
+
+/// First node satisfying `is_goal` met walking depth-first from `start`, in the order of [`dfs`], stopping there so that
+/// the walk ends even when the graph is unbounded past the goal.
+pub fn dfs_find<N, I>(start: N, mut successors: impl FnMut(&N) -> I, mut is_goal: impl FnMut(&N) -> bool) -> Option<N>
+where N: Eq + Hash + Clone, I: IntoIterator<Item=N>, I::IntoIter: DoubleEndedIterator {
+    let mut visited = HashSet::new();
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+        if is_goal(&node) {
+            return Some(node);
+        }
+        stack.extend(successors(&node).into_iter().rev().filter(|next| !visited.contains(next)));
+    }
+    None
+}
+
+/// Cheapest path from `start` to the first node satisfying `is_goal`, with its cost, exploring nodes in the order of
+/// their cost plus `heuristic`, which must never overestimate the cost left to reach a goal.
+pub fn astar<N, C, I>(start: N, mut successors: impl FnMut(&N) -> I, mut heuristic: impl FnMut(&N) -> C,
+                      mut is_goal: impl FnMut(&N) -> bool) -> Option<(Vec<N>, C)>
+where N: Eq + Hash + Clone, C: Ord + Copy + Default + Add<Output=C>, I: IntoIterator<Item=(N, C)> {
+    let mut discovered = Discovered::new(start.clone());
+    let mut costs = vec![C::default()];
+    let mut frontier = PriorityQueue::new();
+    frontier.push(0, heuristic(&start));
+    while let Some((index, _)) = frontier.pop() {
+        let cost = costs[index];
+        let node = discovered.nodes[index].0.clone();
+        if is_goal(&node) {
+            return Some((discovered.path_to(index), cost));
+        }
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            let next_index = match discovered.indices.entry(next.clone()) {
+                Entry::Vacant(entry) => {
+                    entry.insert(discovered.nodes.len());
+                    discovered.nodes.push((next.clone(), index));
+                    costs.push(next_cost);
+                    discovered.nodes.len() - 1
+                }
+                Entry::Occupied(entry) if next_cost < costs[*entry.get()] => {
+                    let next_index = *entry.get();
+                    discovered.nodes[next_index].1 = index;
+                    costs[next_index] = next_cost;
+                    next_index
+                }
+                Entry::Occupied(_) => continue,
+            };
+            frontier.push(next_index, next_cost + heuristic(&next));
+        }
+    }
+    None
+}
+
+/// Cheapest path from `start` to the first node satisfying `is_goal`, with its cost.
+pub fn dijkstra<N, C, I>(start: N, successors: impl FnMut(&N) -> I, is_goal: impl FnMut(&N) -> bool) -> Option<(Vec<N>, C)>
+where N: Eq + Hash + Clone, C: Ord + Copy + Default + Add<Output=C>, I: IntoIterator<Item=(N, C)> {
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Orders `nodes` so that every node comes before its successors, or returns a node that is part of a cycle.
+pub fn topological_sort<N, I>(nodes: &[N], mut successors: impl FnMut(&N) -> I) -> Result<Vec<N>, N>
+where N: Eq + Hash + Clone, I: IntoIterator<Item=N> {
+    let edges: HashMap<N, Vec<N>> = nodes.iter().map(|node| (node.clone(), successors(node).into_iter().collect())).collect();
+    let mut incoming: HashMap<&N, usize> = nodes.iter().map(|node| (node, 0)).collect();
+    for next in edges.values().flatten() {
+        *incoming.entry(next).or_default() += 1;
+    }
+    let mut ready: VecDeque<&N> = nodes.iter().filter(|node| incoming[node] == 0).collect();
+    let mut order = Vec::with_capacity(nodes.len());
+    while let Some(node) = ready.pop_front() {
+        order.push(node.clone());
+        for next in edges.get(node).into_iter().flatten() {
+            let count = incoming.get_mut(next).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                ready.push_back(next);
+            }
+        }
+    }
+    match incoming.into_iter().find(|&(_, count)| count > 0) {
+        Some((node, _)) => Err(node.clone()),
+        None => Ok(order),
+    }
+}
+
+/// Longest path from `start` to `goal` in a directed acyclic graph, with its cost, `None` when `goal` cannot be reached.
+/// `nodes` must list every node of the graph. Returns a node that is part of a cycle when the graph is not acyclic.
+pub fn longest_path<N, C, I>(nodes: &[N], start: N, goal: &N, mut edges: impl FnMut(&N) -> I) -> Result<Option<(Vec<N>, C)>, N>
+where N: Eq + Hash + Clone, C: Ord + Copy + Default + Add<Output=C>, I: IntoIterator<Item=(N, C)> {
+    let order = topological_sort(nodes, |node| edges(node).into_iter().map(|(next, _)| next).collect::<Vec<_>>())?;
+    let mut longest: HashMap<N, (C, Option<N>)> = HashMap::from([(start, (C::default(), None))]);
+    for node in &order {
+        let Some(&(cost, _)) = longest.get(node) else { continue };
+        for (next, step) in edges(node) {
+            if longest.get(&next).is_none_or(|&(known, _)| cost + step > known) {
+                longest.insert(next, (cost + step, Some(node.clone())));
+            }
+        }
+    }
+    let Some(&(cost, _)) = longest.get(goal) else { return Ok(None) };
+    let mut path = vec![goal.clone()];
+    while let Some((_, Some(previous))) = longest.get(path.last().unwrap()) {
+        path.push(previous.clone());
+    }
+    path.reverse();
+    Ok(Some((path, cost)))
+}
+
+/// Number of distinct paths from `start` to `goal` in a directed acyclic graph whose nodes are all listed in `nodes`.
+/// Returns a node that is part of a cycle when the graph is not acyclic.
+pub fn count_paths<N, I>(nodes: &[N], start: N, goal: &N, mut successors: impl FnMut(&N) -> I) -> Result<u64, N>
+where N: Eq + Hash + Clone, I: IntoIterator<Item=N> {
+    let order = topological_sort(nodes, |node| successors(node).into_iter().collect::<Vec<_>>())?;
+    let mut counts: HashMap<N, u64> = HashMap::from([(start, 1)]);
+    for node in &order {
+        let Some(&count) = counts.get(node) else { continue };
+        for next in successors(node) {
+            *counts.entry(next).or_default() += count;
+        }
+    }
+    Ok(counts.get(goal).copied().unwrap_or(0))
+}
+
+/// Lightest set of edges whose removal splits an undirected graph in two, by the Stoer-Wagner algorithm. Returns the
+/// weight of the cut with the nodes on one of its sides, `None` when the graph has fewer than two nodes.
+pub fn minimum_cut<N, C>(edges: &[(N, N, C)]) -> Option<(C, Vec<N>)>
+where N: Eq + Hash + Clone, C: Ord + Copy + Default + Add<Output=C> {
+    let mut indices: HashMap<&N, usize> = HashMap::new();
+    let mut nodes: Vec<&N> = Vec::new();
+    let mut index_of = |node| *indices.entry(node).or_insert_with(|| {
+        nodes.push(node);
+        nodes.len() - 1
+    });
+    let edges: Vec<(usize, usize, C)> = edges.iter().map(|(a, b, weight)| (index_of(a), index_of(b), *weight)).collect();
+    let mut adjacency: Vec<HashMap<usize, C>> = vec![HashMap::new(); nodes.len()];
+    for (a, b, weight) in edges.into_iter().filter(|(a, b, _)| a != b) {
+        let total = *adjacency[a].get(&b).unwrap_or(&C::default()) + weight;
+        adjacency[a].insert(b, total);
+        adjacency[b].insert(a, total);
+    }
+    let mut members: Vec<Vec<usize>> = (0..nodes.len()).map(|node| vec![node]).collect();
+    let mut active: Vec<usize> = (0..nodes.len()).collect();
+    let mut lightest: Option<C> = None;
+    let mut side = Vec::new();
+    while active.len() > 1 {
+        // Adds nodes one at a time, the most tightly connected to those already added first. The cut between the last
+        // one and the others is the lightest cut separating it from the one before, which can then be merged.
+        let mut queue = PriorityQueue::new();
+        active.iter().for_each(|&node| { queue.push(node, Reverse(C::default())); });
+        let mut added = vec![false; nodes.len()];
+        let (mut previous, mut last, mut cut) = (usize::MAX, usize::MAX, C::default());
+        while let Some((node, Reverse(weight))) = queue.pop() {
+            added[node] = true;
+            (previous, last, cut) = (last, node, weight);
+            for (&next, &weight) in adjacency[node].iter().filter(|(next, _)| !added[**next]) {
+                let Reverse(connection) = queue.priority(&next).unwrap_or(Reverse(C::default()));
+                queue.push(next, Reverse(connection + weight));
+            }
+        }
+        if lightest.is_none_or(|lightest| cut < lightest) {
+            lightest = Some(cut);
+            side = members[last].clone();
+        }
+        for (next, weight) in std::mem::take(&mut adjacency[last]) {
+            adjacency[next].remove(&last);
+            if next != previous {
+                let total = *adjacency[previous].get(&next).unwrap_or(&C::default()) + weight;
+                adjacency[previous].insert(next, total);
+                adjacency[next].insert(previous, total);
+            }
+        }
+        let merged = std::mem::take(&mut members[last]);
+        members[previous].extend(merged);
+        active.retain(|&node| node != last);
+    }
+    lightest.map(|cut| (cut, side.into_iter().map(|node| nodes[node].clone()).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edges(node: &char) -> Vec<(char, u32)> {
+        match node {
+            'A' => vec![('B', 7), ('C', 9), ('F', 14)],
+            'B' => vec![('C', 10), ('D', 15)],
+            'C' => vec![('D', 11), ('F', 2)],
+            'D' => vec![('E', 6)],
+            'F' => vec![('E', 9)],
+            _ => vec![],
+        }
+    }
+
+    fn successors(node: &char) -> Vec<char> {
+        edges(node).into_iter().map(|(next, _)| next).collect()
+    }
+
+    #[test]
+    fn finds_path_with_fewest_edges() {
+        assert_eq!(bfs('A', successors, |&node| node == 'E'), Some(vec!['A', 'F', 'E']));
+        assert_eq!(bfs('E', successors, |&node| node == 'A'), None);
+        assert_eq!(bfs_distances('A', successors)[&'D'], 2);
+    }
+
+    #[test]
+    fn walks_depth_first() {
+        assert_eq!(dfs('A', successors), vec!['A', 'B', 'C', 'D', 'E', 'F']);
+        assert_eq!(dfs_find('A', successors, |&node| node == 'D'), Some('D'));
+        assert_eq!(dfs_find('E', successors, |&node| node == 'A'), None);
+    }
+
+    #[test]
+    fn stops_walking_depth_first_at_the_goal() {
+        assert_eq!(dfs_find(0_u64, |&n| [n + 1], |&n| n == 1000), Some(1000));
+    }
+
+    #[test]
+    fn finds_cheapest_path() {
+        assert_eq!(dijkstra('A', edges, |&node| node == 'E'), Some((vec!['A', 'C', 'F', 'E'], 20)));
+    }
+
+    #[test]
+    fn finds_cheapest_path_on_a_grid_guided_by_distance() {
+        let walls = [(1, 0), (1, 1), (1, 2), (3, 1), (3, 2), (3, 3)];
+        let neighbors = |&(x, y): &(i32, i32)| [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)].into_iter()
+            .filter(|&(x, y)| (0..5).contains(&x) && (0..4).contains(&y) && !walls.contains(&(x, y)))
+            .map(|next| (next, 1))
+            .collect::<Vec<_>>();
+        let (path, cost) = astar((0, 0), neighbors, |&(x, y)| (4 - x).abs() + (3 - y).abs(), |&node| node == (4, 3)).unwrap();
+        assert_eq!(cost, 13);
+        assert_eq!(path.len(), 14);
+        assert_eq!(dijkstra((0, 0), neighbors, |&node| node == (4, 3)).map(|(_, cost)| cost), Some(13));
+    }
+
+    #[test]
+    fn sorts_nodes_topologically() {
+        let order = topological_sort(&['A', 'B', 'C', 'D', 'E', 'F'], successors).unwrap();
+        let position = |node| order.iter().position(|&n| n == node).unwrap();
+        assert!(['A', 'B', 'C', 'D', 'E', 'F'].iter().all(|node| successors(node).iter().all(|&next| position(*node) < position(next))));
+        assert!(topological_sort(&['A', 'B'], |&node| if node == 'A' { vec!['B'] } else { vec!['A'] }).is_err());
+    }
+
+    #[test]
+    fn finds_longest_path_in_acyclic_graphs() {
+        let nodes = ['A', 'B', 'C', 'D', 'E', 'F'];
+        assert_eq!(longest_path(&nodes, 'A', &'E', edges), Ok(Some((vec!['A', 'B', 'C', 'D', 'E'], 34))));
+        assert_eq!(longest_path(&nodes, 'C', &'B', edges), Ok(None));
+        assert!(longest_path(&['A', 'B'], 'A', &'B', |&node| if node == 'A' { vec![('B', 1)] } else { vec![('A', 1)] }).is_err());
+    }
+
+    #[test]
+    fn cuts_graphs_along_their_lightest_edges() {
+        let edges = [(1, 2, 2), (1, 5, 3), (2, 3, 3), (2, 5, 2), (2, 6, 2), (3, 4, 4), (3, 7, 2), (4, 7, 2), (4, 8, 2), (5, 6, 3), (6, 7, 1), (7, 8, 3)];
+        let (cut, mut side) = minimum_cut(&edges).unwrap();
+        side.sort();
+        assert_eq!(cut, 4);
+        assert!(side == vec![3, 4, 7, 8] || side == vec![1, 2, 5, 6]);
+        assert_eq!(minimum_cut(&[(1, 2, 5), (3, 4, 1)]).map(|(cut, _)| cut), Some(0));
+        assert_eq!(minimum_cut::<u8, u8>(&[]), None);
+    }
+
+    #[test]
+    fn splits_wiring_diagram_in_two_by_cutting_three_wires() {
+        let diagram = ["jqt: rhn xhk nvd", "rsh: frs pzl lsr", "xhk: hfx", "cmg: qnr nvd lhk bvb", "rhn: xhk bvb hfx", "bvb: xhk hfx",
+            "pzl: lsr hfx nvd", "qnr: nvd", "ntq: jqt hfx bvb xhk", "nvd: lhk", "lsr: lhk", "rzs: qnr cmg lsr rsh", "frs: qnr lhk lsr"];
+        let wires: Vec<(&str, &str, usize)> = diagram.iter()
+            .flat_map(|line| {
+                let (component, others) = line.split_once(": ").unwrap();
+                others.split(' ').map(move |other| (component, other, 1))
+            })
+            .collect();
+        let (cut, side) = minimum_cut(&wires).unwrap();
+        assert_eq!(cut, 3);
+        assert_eq!(side.len() * (15 - side.len()), 54);
+    }
+
+    #[test]
+    fn counts_paths_in_acyclic_graphs() {
+        let nodes = ['A', 'B', 'C', 'D', 'E', 'F'];
+        assert_eq!(count_paths(&nodes, 'A', &'E', successors), Ok(6));
+        assert_eq!(count_paths(&nodes, 'D', &'E', successors), Ok(1));
+        assert_eq!(count_paths(&nodes, 'E', &'A', successors), Ok(0));
+    }
+}
@@ -0,0 +1,275 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+use crate::error::{AocError, Result};
+
+const SIDES: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const AROUND: [(isize, isize); 8] = [(-1, -1), (0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0)];
+
+/// A cell of a grid drawn with one character per cell.
+pub trait FromChar: Sized {
+    fn from_char(c: char) -> std::result::Result<Self, String>;
+}
+
+impl FromChar for char {
+    fn from_char(c: char) -> std::result::Result<char, String> {
+        Ok(c)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, cells: Vec<T>) -> Grid<T> {
+        assert!(width > 0 && cells.len().is_multiple_of(width), "{} cells do not fill rows of {}", cells.len(), width);
+        Grid { width, height: cells.len() / width, cells }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T> where T: Clone {
+        Grid { width, height, cells: vec![value; width * height] }
+    }
+
+    /// Grid of the cells drawn by `lines`, one character per cell.
+    pub fn parse(lines: &[String]) -> Result<Grid<T>> where T: FromChar {
+        Grid::parse_with(lines, T::from_char)
+    }
+
+    /// Grid of the cells drawn by `lines`, `cell` telling which cell each character stands for.
+    pub fn parse_with(lines: &[String], cell: impl Fn(char) -> std::result::Result<T, String>) -> Result<Grid<T>> {
+        let width = lines.first().map(|line| line.chars().count()).ok_or_else(|| AocError::parse(1, "", "empty grid"))?;
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (index, line) in lines.iter().enumerate() {
+            if line.chars().count() != width {
+                return Err(AocError::parse(index + 1, line, format!("expected {} columns", width)));
+            }
+            for c in line.chars() {
+                cells.push(cell(c).map_err(|reason| AocError::parse(index + 1, line, reason))?);
+            }
+        }
+        Ok(Grid { width, height: lines.len(), cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        (0..self.width as isize).contains(&x) && (0..self.height as isize).contains(&y)
+    }
+
+    pub fn get(&self, x: isize, y: isize) -> Option<&T> {
+        self.contains(x, y).then(|| &self.cells[y as usize * self.width + x as usize])
+    }
+
+    pub fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+        if self.contains(x, y) { Some(&mut self.cells[y as usize * self.width + x as usize]) } else { None }
+    }
+
+    fn offsets<'a>(&'a self, (x, y): (usize, usize), offsets: &'a [(isize, isize)]) -> impl Iterator<Item=(usize, usize)> + 'a {
+        offsets.iter()
+            .map(move |&(dx, dy)| (x as isize + dx, y as isize + dy))
+            .filter(|&(x, y)| self.contains(x, y))
+            .map(|(x, y)| (x as usize, y as usize))
+    }
+
+    /// Positions above, right, below and left of `position` that are in the grid.
+    pub fn neighbors(&self, position: (usize, usize)) -> impl Iterator<Item=(usize, usize)> + '_ {
+        self.offsets(position, &SIDES)
+    }
+
+    /// Positions of the eight cells around `position` that are in the grid, diagonals included.
+    pub fn surroundings(&self, position: (usize, usize)) -> impl Iterator<Item=(usize, usize)> + '_ {
+        self.offsets(position, &AROUND)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item=&[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item=&T> {
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item=impl Iterator<Item=&T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn cells(&self) -> impl Iterator<Item=((usize, usize), &T)> {
+        self.cells.iter().enumerate().map(|(offset, cell)| ((offset % self.width, offset / self.width), cell))
+    }
+
+    pub fn positions_of<'a>(&'a self, value: &'a T) -> impl Iterator<Item=(usize, usize)> + 'a where T: PartialEq {
+        self.cells().filter(move |(_, cell)| *cell == value).map(|(position, _)| position)
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+
+    fn rearranged(&self, width: usize, height: usize, source: impl Fn(usize, usize) -> (usize, usize)) -> Grid<T> where T: Clone {
+        let cells = (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).map(|(x, y)| self[source(x, y)].clone()).collect();
+        Grid { width, height, cells }
+    }
+
+    /// Grid mirrored along its diagonal, rows becoming columns.
+    pub fn transpose(&self) -> Grid<T> where T: Clone {
+        self.rearranged(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// Grid turned a quarter clockwise.
+    pub fn rotate_right(&self) -> Grid<T> where T: Clone {
+        self.rearranged(self.height, self.width, |x, y| (y, self.height - 1 - x))
+    }
+
+    /// Grid turned a quarter counterclockwise.
+    pub fn rotate_left(&self) -> Grid<T> where T: Clone {
+        self.rearranged(self.height, self.width, |x, y| (self.width - 1 - y, x))
+    }
+
+    /// Grid mirrored left to right.
+    pub fn flip_horizontal(&self) -> Grid<T> where T: Clone {
+        self.rearranged(self.width, self.height, |x, y| (self.width - 1 - x, y))
+    }
+
+    /// Grid mirrored top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T> where T: Clone {
+        self.rearranged(self.width, self.height, |x, y| (x, self.height - 1 - y))
+    }
+
+    /// Number of rows above the first horizontal line that mirrors the rows on both of its sides, up to the shortest
+    /// side, with exactly `mismatches` cells differing from their reflection.
+    pub fn reflection_row(&self, mismatches: usize) -> Option<usize> where T: PartialEq {
+        (1..self.height).find(|&line| {
+            let pairs = (0..line).rev().zip(line..self.height);
+            pairs.map(|(above, below)| self.row(above).iter().zip(self.row(below)).filter(|(a, b)| a != b).count()).sum::<usize>() == mismatches
+        })
+    }
+
+    /// Number of columns left of the first vertical line that mirrors the columns on both of its sides, with exactly
+    /// `mismatches` cells differing from their reflection.
+    pub fn reflection_column(&self, mismatches: usize) -> Option<usize> where T: PartialEq {
+        (1..self.width).find(|&line| {
+            let pairs = (0..line).rev().zip(line..self.width);
+            pairs.map(|(left, right)| self.column(left).zip(self.column(right)).filter(|(a, b)| a != b).count()).sum::<usize>() == mismatches
+        })
+    }
+}
+
+impl Grid<char> {
+    pub fn of_chars(lines: &[String]) -> Result<Grid<char>> {
+        Grid::parse(lines)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width, "column {} out of a grid of width {}", x, self.width);
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width, "column {} out of a grid of width {}", x, self.width);
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            row.iter().try_for_each(|cell| write!(f, "{}", cell))?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(input: &[&str]) -> Vec<String> {
+        input.iter().map(|line| line.to_string()).collect()
+    }
+
+    fn example() -> Grid<char> {
+        Grid::of_chars(&lines(&["467..", "...*.", "..35."])).unwrap()
+    }
+
+    #[test]
+    fn indexes_cells_by_column_and_row() {
+        let mut grid = example();
+        assert_eq!((grid.width(), grid.height()), (5, 3));
+        assert_eq!(grid[(3, 1)], '*');
+        assert_eq!(grid.get(-1, 0), None);
+        assert_eq!(grid.get(4, 3), None);
+        grid[(3, 1)] = '#';
+        assert_eq!(grid.get(3, 1), Some(&'#'));
+    }
+
+    #[test]
+    fn lists_neighbors_inside_the_grid() {
+        let grid = example();
+        assert_eq!(grid.neighbors((0, 0)).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+        assert_eq!(grid.surroundings((4, 2)).collect::<Vec<_>>(), vec![(3, 1), (4, 1), (3, 2)]);
+        assert_eq!(grid.surroundings((3, 1)).count(), 8);
+    }
+
+    #[test]
+    fn iterates_rows_and_columns() {
+        let grid = example();
+        assert_eq!(grid.row(2), &['.', '.', '3', '5', '.']);
+        assert_eq!(grid.column(2).collect::<String>(), "7.3");
+        assert_eq!(grid.columns().map(|column| column.collect::<String>()).collect::<Vec<_>>(), vec!["4..", "6..", "7.3", ".*5", "..."]);
+        assert_eq!(grid.positions_of(&'*').collect::<Vec<_>>(), vec![(3, 1)]);
+    }
+
+    #[test]
+    fn displays_rows_on_their_own_lines() {
+        assert_eq!(example().map(|&c| if c == '.' { ' ' } else { c }).to_string(), "467  \n   * \n  35 \n");
+    }
+
+    #[test]
+    fn rotates_and_flips() {
+        let grid = Grid::of_chars(&lines(&["ab", "cd", "ef"])).unwrap();
+        assert_eq!(grid.transpose().to_string(), "ace\nbdf\n");
+        assert_eq!(grid.rotate_right().to_string(), "eca\nfdb\n");
+        assert_eq!(grid.rotate_left().to_string(), "bdf\nace\n");
+        assert_eq!(grid.flip_horizontal().to_string(), "ba\ndc\nfe\n");
+        assert_eq!(grid.flip_vertical().to_string(), "ef\ncd\nab\n");
+        assert_eq!(grid.rotate_right().rotate_right().rotate_right().rotate_right(), grid);
+    }
+
+    #[test]
+    fn finds_reflection_lines() {
+        let pattern = Grid::of_chars(&lines(&["#.##..##.", "..#.##.#.", "##......#", "##......#", "..#.##.#.", "..##..##.", "#.#.##.#."])).unwrap();
+        assert_eq!(pattern.reflection_column(0), Some(5));
+        assert_eq!(pattern.reflection_row(0), None);
+        assert_eq!(pattern.reflection_row(1), Some(3));
+        assert_eq!(pattern.transpose().reflection_row(0), Some(5));
+    }
+
+    #[test]
+    fn reports_ragged_or_invalid_rows() {
+        assert_eq!(Grid::of_chars(&lines(&["467..", "..."])).unwrap_err().to_string(), r#"line 2: expected 5 columns in "...""#);
+        let digits = Grid::parse_with(&lines(&["12", "3x"]), |c| c.to_digit(10).ok_or(format!("{:?} is not a digit", c)));
+        assert_eq!(digits.unwrap_err().to_string(), r#"line 2: 'x' is not a digit in "3x""#);
+    }
+}
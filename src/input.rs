@@ -1,18 +1,238 @@
+use std::fs;
 use std::fs::read_to_string;
+use std::io;
+use std::io::BufRead;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-pub fn read_lines(filename: String) -> Vec<String> {
-    read_to_string(filename)
-        .unwrap()
-        .lines()
-        .map(String::from)
-        .collect()
+use crate::config::config;
+use crate::crypt::{decrypt, encrypt, passphrase};
+use crate::error::Result;
+use crate::fetch::{download_input, download_puzzle, extract_example, is_transport_error};
+use crate::puzzle::{split_blocks, split_lines};
+
+pub fn read_lines(filename: String) -> Result<Vec<String>> {
+    Ok(split_lines(&read_to_string(filename)?))
+}
+
+pub fn input_path(year: u32, day: u32) -> String {
+    format!("{}/{}/day{:0>2}.txt", config().inputs, year, day)
+}
+
+pub fn encrypted_input_path(year: u32, day: u32) -> String {
+    format!("{}.enc", input_path(year, day))
+}
+
+pub fn users_path(year: u32) -> String {
+    format!("{}/{}/users", config().inputs, year)
+}
+
+pub fn user_input_path(year: u32, user: &str, day: u32) -> String {
+    format!("{}/{}/day{:0>2}.txt", users_path(year), user, day)
+}
+
+pub fn user_answers_path(year: u32, user: &str) -> String {
+    format!("{}/{}/answers.toml", users_path(year), user)
+}
+
+pub fn example_path(year: u32, day: u32) -> String {
+    format!("{}/{}/day{:0>2}.txt", config().examples, year, day)
+}
+
+fn metadata_path(year: u32, day: u32) -> String {
+    format!("{}/{}/day{:0>2}.meta", config().inputs, year, day)
+}
+
+#[derive(PartialEq, Debug)]
+pub struct CacheMetadata {
+    pub fetched_at: SystemTime,
+    pub size: u64,
+}
+
+impl CacheMetadata {
+    fn new(size: u64) -> CacheMetadata {
+        CacheMetadata { fetched_at: SystemTime::now(), size }
+    }
+
+    fn parse(metadata: &str) -> Option<CacheMetadata> {
+        let mut fetched_at = None;
+        let mut size = None;
+        for line in metadata.lines() {
+            match line.split_once('=') {
+                Some(("fetched_at", secs)) => fetched_at = secs.parse().ok().map(|s| UNIX_EPOCH + Duration::from_secs(s)),
+                Some(("size", bytes)) => size = bytes.parse().ok(),
+                _ => {}
+            }
+        }
+        Some(CacheMetadata { fetched_at: fetched_at?, size: size? })
+    }
+
+    fn format(&self) -> String {
+        let secs = self.fetched_at.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        format!("fetched_at={}\nsize={}\n", secs, self.size)
+    }
+
+    fn of_file(path: &str) -> io::Result<CacheMetadata> {
+        let metadata = fs::metadata(path)?;
+        Ok(CacheMetadata { fetched_at: metadata.modified()?, size: metadata.len() })
+    }
+
+    pub fn age(&self) -> Duration {
+        SystemTime::now().duration_since(self.fetched_at).unwrap_or_default()
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub enum CacheStatus {
+    Hit,
+    Downloaded,
+    Decrypted,
+    Offline,
+}
+
+pub struct CachedInput {
+    pub path: String,
+    pub metadata: CacheMetadata,
+    pub status: CacheStatus,
 }
 
-pub fn daily_input(day: u32) -> Vec<String> {
-    read_lines(format!("src/inputs/day{:0>2}.txt", day))
+fn read_cache(year: u32, day: u32, status: CacheStatus) -> io::Result<CachedInput> {
+    let path = input_path(year, day);
+    let metadata = match read_to_string(metadata_path(year, day)).ok().and_then(|m| CacheMetadata::parse(&m)) {
+        Some(metadata) => metadata,
+        None => CacheMetadata::of_file(&path)?,
+    };
+    Ok(CachedInput { path, metadata, status })
 }
 
-pub fn daily_example(day: u32) -> Vec<String> {
-    read_lines(format!("src/examples/day{:0>2}.txt", day))
+fn write_cache(year: u32, day: u32, input: &str, status: CacheStatus) -> io::Result<CachedInput> {
+    let path = input_path(year, day);
+    let metadata = CacheMetadata::new(input.len() as u64);
+    if let Some(directory) = Path::new(&path).parent() {
+        fs::create_dir_all(directory)?;
+    }
+    fs::write(&path, input)?;
+    fs::write(metadata_path(year, day), metadata.format())?;
+    Ok(CachedInput { path, metadata, status })
 }
 
+fn decrypt_input(year: u32, day: u32) -> io::Result<Option<String>> {
+    match (fs::read(encrypted_input_path(year, day)), passphrase()) {
+        (Ok(data), Some(key)) => decrypt(&data, &key).map(Some),
+        _ => Ok(None),
+    }
+}
+
+pub fn encrypt_input(year: u32, day: u32) -> io::Result<String> {
+    let key = passphrase().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "AOC_INPUT_KEY is not set"))?;
+    let path = encrypted_input_path(year, day);
+    fs::write(&path, encrypt(&read_to_string(input_path(year, day))?, &key))?;
+    Ok(path)
+}
+
+pub fn cache_input(year: u32, day: u32, refresh: bool) -> io::Result<CachedInput> {
+    let is_cached = fs::metadata(input_path(year, day)).is_ok_and(|metadata| metadata.len() > 0);
+    if is_cached && !refresh {
+        return read_cache(year, day, CacheStatus::Hit);
+    }
+    if !is_cached {
+        if let Some(input) = decrypt_input(year, day)? {
+            return write_cache(year, day, &input, CacheStatus::Decrypted);
+        }
+    }
+    match download_input(year, day) {
+        Ok(input) => {
+            let cached = write_cache(year, day, &input, CacheStatus::Downloaded)?;
+            if passphrase().is_some() {
+                encrypt_input(year, day)?;
+            }
+            Ok(cached)
+        }
+        Err(error) if is_cached && is_transport_error(&error) => read_cache(year, day, CacheStatus::Offline),
+        Err(error) => Err(error),
+    }
+}
+
+pub fn cache_example(year: u32, day: u32, refresh: bool) -> io::Result<(String, CacheStatus)> {
+    let path = example_path(year, day);
+    if fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0) && !refresh {
+        return Ok((path, CacheStatus::Hit));
+    }
+    let example = extract_example(&download_puzzle(year, day)?)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no example in puzzle page"))?;
+    if let Some(directory) = Path::new(&path).parent() {
+        fs::create_dir_all(directory)?;
+    }
+    fs::write(&path, example)?;
+    Ok((path, CacheStatus::Downloaded))
+}
+
+pub fn daily_input(year: u32, day: u32) -> Result<Vec<String>> {
+    #[cfg(feature = "embedded-inputs")]
+    if let Some(input) = crate::embedded::embedded_input(year, day) {
+        return Ok(split_lines(input));
+    }
+    read_lines(cache_input(year, day, false)?.path)
+}
+
+pub fn daily_example(year: u32, day: u32) -> Result<Vec<String>> {
+    read_lines(example_path(year, day))
+}
+
+/// Daily input split into blocks of lines separated by blank lines.
+pub fn daily_input_blocks(year: u32, day: u32) -> Result<Vec<Vec<String>>> {
+    Ok(split_blocks(&daily_input(year, day)?))
+}
+
+pub fn daily_example_blocks(year: u32, day: u32) -> Result<Vec<Vec<String>>> {
+    Ok(split_blocks(&daily_example(year, day)?))
+}
+
+pub fn users(year: u32) -> Result<Vec<String>> {
+    let mut users = Vec::new();
+    for entry in fs::read_dir(users_path(year))? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            users.extend(entry.file_name().to_str().map(str::to_owned));
+        }
+    }
+    users.sort();
+    Ok(users)
+}
+
+pub fn stdin_input() -> Result<Vec<String>> {
+    Ok(io::stdin().lock().lines().collect::<io::Result<_>>()?)
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum InputSource {
+    Daily,
+    Stdin,
+    File(String),
+}
+
+impl InputSource {
+    pub fn read(&self, year: u32, day: u32) -> Result<Vec<String>> {
+        match self {
+            InputSource::Daily => daily_input(year, day),
+            InputSource::Stdin => stdin_input(),
+            InputSource::File(path) => read_lines(path.to_owned()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_back_cache_metadata() {
+        let metadata = CacheMetadata { fetched_at: UNIX_EPOCH + Duration::from_secs(1701406800), size: 21864 };
+        assert_eq!(CacheMetadata::parse(&metadata.format()), Some(metadata));
+    }
+
+    #[test]
+    fn ignores_incomplete_cache_metadata() {
+        assert_eq!(CacheMetadata::parse("size=21864\n"), None);
+    }
+}
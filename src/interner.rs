@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+
+/// Labels numbered `0, 1, 2…` in the order they are first met, so that named entities can be stored and compared as
+/// small integers and their names looked up again when needed.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct Interner {
+    ids: HashMap<String, usize>,
+    labels: Vec<String>,
+}
+
+impl Interner {
+    pub fn new() -> Interner {
+        Interner::default()
+    }
+
+    /// Id of `label`, numbering it after the labels already interned when it is new.
+    pub fn intern(&mut self, label: &str) -> usize {
+        if let Some(&id) = self.ids.get(label) {
+            return id;
+        }
+        let id = self.labels.len();
+        self.ids.insert(label.to_owned(), id);
+        self.labels.push(label.to_owned());
+        id
+    }
+
+    /// Id of `label` if it was interned.
+    pub fn id(&self, label: &str) -> Option<usize> {
+        self.ids.get(label).copied()
+    }
+
+    pub fn label(&self, id: usize) -> &str {
+        &self.labels[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Labels ordered by id.
+    pub fn labels(&self) -> impl Iterator<Item=&str> {
+        self.labels.iter().map(String::as_str)
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for Interner {
+    fn from_iter<I: IntoIterator<Item=S>>(labels: I) -> Interner {
+        let mut interner = Interner::new();
+        labels.into_iter().for_each(|label| { interner.intern(label.as_ref()); });
+        interner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_labels_in_order_of_appearance() {
+        let mut labels: Interner = ["AAA", "BBB", "AAA"].into_iter().collect();
+        assert_eq!(labels.len(), 2);
+        assert_eq!(labels.intern("ZZZ"), 2);
+        assert_eq!(labels.intern("BBB"), 1);
+        assert_eq!((labels.id("AAA"), labels.id("CCC")), (Some(0), None));
+        assert_eq!(labels.label(2), "ZZZ");
+        assert_eq!(labels.labels().collect::<Vec<_>>(), vec!["AAA", "BBB", "ZZZ"]);
+    }
+}
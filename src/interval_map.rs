@@ -0,0 +1,113 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// Values attached to disjoint ranges of keys, each range stored by its start with its end and value.
+#[derive(PartialEq, Eq, Debug, Clone, Hash)]
+pub struct IntervalMap<K, V> {
+    intervals: BTreeMap<K, (K, V)>,
+}
+
+impl<K: Ord + Copy, V: Clone> IntervalMap<K, V> {
+    pub fn new() -> IntervalMap<K, V> {
+        IntervalMap { intervals: BTreeMap::new() }
+    }
+
+    /// Value of the range containing `point`.
+    pub fn get(&self, point: K) -> Option<&V> {
+        self.interval(point).map(|(_, value)| value)
+    }
+
+    /// Range containing `point`, with its value.
+    pub fn interval(&self, point: K) -> Option<(Range<K>, &V)> {
+        let (&start, (end, value)) = self.intervals.range(..=point).next_back()?;
+        (point < *end).then_some((start..*end, value))
+    }
+
+    /// Attaches `value` to the keys of `range`, replacing what the keys were attached to, parts of ranges sticking out
+    /// of it keeping their values.
+    pub fn insert_range(&mut self, range: Range<K>, value: V) {
+        if range.is_empty() {
+            return;
+        }
+        self.split(range.start);
+        self.split(range.end);
+        let covered: Vec<K> = self.intervals.range(range.start..range.end).map(|(&start, _)| start).collect();
+        covered.iter().for_each(|start| { self.intervals.remove(start); });
+        self.intervals.insert(range.start, (range.end, value));
+    }
+
+    /// Splits the range containing `point` in two, both keeping its value, so that a range starts at `point`.
+    pub fn split(&mut self, point: K) {
+        let Some((range, value)) = self.interval(point) else { return };
+        if range.start == point {
+            return;
+        }
+        let value = value.clone();
+        self.intervals.insert(range.start, (point, value.clone()));
+        self.intervals.insert(point, (range.end, value));
+    }
+
+    /// Ranges in increasing order with their values.
+    pub fn iter(&self) -> impl Iterator<Item=(Range<K>, &V)> {
+        self.intervals.iter().map(|(&start, (end, value))| (start..*end, value))
+    }
+
+    /// Number of ranges.
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+}
+
+impl<K: Ord + Copy, V: Clone> Default for IntervalMap<K, V> {
+    fn default() -> IntervalMap<K, V> {
+        IntervalMap::new()
+    }
+}
+
+impl<K: Ord + Copy, V: Clone> FromIterator<(Range<K>, V)> for IntervalMap<K, V> {
+    /// Map of the ranges inserted in turn, later ranges replacing earlier ones where they overlap.
+    fn from_iter<I: IntoIterator<Item=(Range<K>, V)>>(ranges: I) -> IntervalMap<K, V> {
+        let mut map = IntervalMap::new();
+        ranges.into_iter().for_each(|(range, value)| map.insert_range(range, value));
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn looks_up_values_of_ranges() {
+        let soils: IntervalMap<i64, i64> = [(98..100, -48), (50..98, 2)].into_iter().collect();
+        assert_eq!(soils.get(79), Some(&2));
+        assert_eq!(soils.get(99), Some(&-48));
+        assert_eq!(soils.get(100), None);
+        assert_eq!(soils.get(13), None);
+        assert_eq!(soils.interval(60), Some((50..98, &2)));
+    }
+
+    #[test]
+    fn replaces_overlapped_parts_of_ranges() {
+        let mut routes: IntervalMap<u16, &str> = [(1..4001, "in")].into_iter().collect();
+        routes.insert_range(1..1351, "px");
+        routes.insert_range(1000..2000, "qqz");
+        routes.insert_range(3000..3000, "A");
+        assert_eq!(routes.iter().collect::<Vec<_>>(), vec![(1..1000, &"px"), (1000..2000, &"qqz"), (2000..4001, &"in")]);
+    }
+
+    #[test]
+    fn splits_ranges_keeping_their_values() {
+        let mut map: IntervalMap<i32, char> = [(0..10, 'a')].into_iter().collect();
+        map.split(4);
+        map.split(4);
+        map.split(0);
+        map.split(20);
+        assert_eq!(map.iter().collect::<Vec<_>>(), vec![(0..4, &'a'), (4..10, &'a')]);
+        assert_eq!(map.len(), 2);
+    }
+}
@@ -1,6 +1,79 @@
 extern crate core;
 
-mod input;
+pub mod beam;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod calendar;
+pub mod compare;
+pub mod counter;
+pub mod cycle;
+pub mod config;
+pub mod constrained_path;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod crypt;
+pub mod dot;
+#[cfg(feature = "embedded-inputs")]
+pub mod embedded;
+pub mod error;
+pub mod flood_fill;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod interner;
+pub mod interval_map;
+pub mod linear;
+pub mod math;
+pub mod memo;
+pub mod memory;
+pub mod parse;
+pub mod piecewise_map;
+pub mod point3;
+pub mod polygon;
+pub mod priority_queue;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod input;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod fetch;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod submit;
+pub mod puzzle;
+pub mod progress;
+pub mod range_set;
+pub mod rle;
+pub mod render;
+pub mod answers;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod verify;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod bench;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod runner;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod results;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod summary;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod profile;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod dashboard;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod repl;
+pub mod scaffold;
+pub mod search;
+pub mod small_set;
+pub mod sparse_grid;
+pub mod svg;
+pub mod union_find;
+pub mod validate;
+pub mod vec2;
+pub mod zobrist;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod ffi;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "cargo-aoc")]
+mod cargo_aoc;
 mod day01;
 mod day02;
 mod day03;
@@ -8,4 +81,17 @@ mod day04;
 mod day05;
 mod day06;
 mod day07;
-mod day08;
\ No newline at end of file
+mod day08;
+mod day09;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+mod day17;
+mod day18;
+
+#[cfg(feature = "cargo-aoc")]
+aoc_runner_derive::aoc_lib! { year = 2023 }
@@ -0,0 +1,191 @@
+use std::ops::Mul;
+
+use num::rational::Ratio;
+use num::traits::NumOps;
+use num::{One, Zero};
+
+/// Exact fraction of 128 bits integers.
+pub type Rational = Ratio<i128>;
+
+/// Solution of the square system `coefficients · x = constants` by Gauss-Jordan elimination, exact as long as the
+/// values are, such as [`Rational`] or [`num::BigRational`] ones. Returns `None` when the system has no unique solution.
+pub fn solve<F>(coefficients: &[Vec<F>], constants: &[F]) -> Option<Vec<F>>
+where F: Clone + PartialEq + Zero + One + NumOps {
+    let size = constants.len();
+    assert!(coefficients.len() == size && coefficients.iter().all(|row| row.len() == size), "expected a {0}x{0} system", size);
+    let mut rows: Vec<Vec<F>> = coefficients.iter().zip(constants)
+        .map(|(row, constant)| row.iter().chain([constant]).cloned().collect())
+        .collect();
+    for column in 0..size {
+        let pivot = (column..size).find(|&row| !rows[row][column].is_zero())?;
+        rows.swap(column, pivot);
+        let pivot = rows[column][column].clone();
+        rows[column].iter_mut().for_each(|value| *value = value.clone() / pivot.clone());
+        let pivot_row = rows[column].clone();
+        for (_, row) in rows.iter_mut().enumerate().filter(|(index, row)| *index != column && !row[column].is_zero()) {
+            let factor = row[column].clone();
+            for (value, pivot_value) in row.iter_mut().zip(&pivot_row).skip(column) {
+                *value = value.clone() - factor.clone() * pivot_value.clone();
+            }
+        }
+    }
+    Some(rows.into_iter().map(|mut row| row.swap_remove(size)).collect())
+}
+
+/// Solution of a square system of integer equations, as fractions.
+pub fn solve_integers(coefficients: &[Vec<i128>], constants: &[i128]) -> Option<Vec<Rational>> {
+    let coefficients: Vec<Vec<Rational>> = coefficients.iter().map(|row| row.iter().map(|&n| Rational::from(n)).collect()).collect();
+    let constants: Vec<Rational> = constants.iter().map(|&n| Rational::from(n)).collect();
+    solve(&coefficients, &constants)
+}
+
+/// A square matrix of integers, small enough for exact determinants.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash)]
+pub struct Matrix<const N: usize>(pub [[i128; N]; N]);
+
+impl<const N: usize> Matrix<N> {
+    pub fn identity() -> Matrix<N> {
+        let mut rows = [[0; N]; N];
+        (0..N).for_each(|i| rows[i][i] = 1);
+        Matrix(rows)
+    }
+
+    /// Determinant by Bareiss' fraction-free elimination, whose divisions are all exact.
+    pub fn determinant(&self) -> i128 {
+        let mut rows = self.0;
+        let (mut sign, mut previous_pivot) = (1, 1);
+        for k in 0..N.saturating_sub(1) {
+            if rows[k][k] == 0 {
+                let Some(pivot) = (k + 1..N).find(|&row| rows[row][k] != 0) else { return 0 };
+                rows.swap(k, pivot);
+                sign = -sign;
+            }
+            let pivot_row = rows[k];
+            for row in rows.iter_mut().skip(k + 1) {
+                let factor = row[k];
+                for (value, &pivot_value) in row.iter_mut().zip(&pivot_row).skip(k + 1) {
+                    *value = (*value * pivot_row[k] - factor * pivot_value) / previous_pivot;
+                }
+            }
+            previous_pivot = pivot_row[k];
+        }
+        if N == 0 { 1 } else { sign * rows[N - 1][N - 1] }
+    }
+
+    /// Cofactor of the entry at `row` and `column`: the signed determinant of the matrix without them, computed as
+    /// the determinant of the matrix whose `row` is replaced by the unit vector along `column`.
+    pub fn cofactor(&self, row: usize, column: usize) -> i128 {
+        let mut replaced = *self;
+        replaced.0[row] = [0; N];
+        replaced.0[row][column] = 1;
+        replaced.determinant()
+    }
+
+    /// Transpose of the matrix of cofactors, so that `self * adjugate` is the identity times the determinant.
+    pub fn adjugate(&self) -> Matrix<N> {
+        let mut adjugate = [[0; N]; N];
+        for (column, adjugate_row) in adjugate.iter_mut().enumerate() {
+            for (row, value) in adjugate_row.iter_mut().enumerate() {
+                *value = self.cofactor(row, column);
+            }
+        }
+        Matrix(adjugate)
+    }
+
+    /// Exact inverse as fractions, `None` for singular matrices.
+    pub fn inverse(&self) -> Option<[[Rational; N]; N]> {
+        let determinant = self.determinant();
+        if determinant == 0 {
+            return None;
+        }
+        Some(self.adjugate().0.map(|row| row.map(|value| Rational::new(value, determinant))))
+    }
+
+    /// Solution of `self · x = constants` by Cramer's rule, `None` when the matrix is singular.
+    pub fn solve(&self, constants: [i128; N]) -> Option<[Rational; N]> {
+        let determinant = self.determinant();
+        if determinant == 0 {
+            return None;
+        }
+        Some(std::array::from_fn(|column| {
+            let mut replaced = *self;
+            replaced.0.iter_mut().zip(constants).for_each(|(row, constant)| row[column] = constant);
+            Rational::new(replaced.determinant(), determinant)
+        }))
+    }
+
+    pub fn transpose(&self) -> Matrix<N> {
+        Matrix(std::array::from_fn(|row| std::array::from_fn(|column| self.0[column][row])))
+    }
+}
+
+impl<const N: usize> Mul for Matrix<N> {
+    type Output = Matrix<N>;
+
+    fn mul(self, other: Matrix<N>) -> Matrix<N> {
+        Matrix(std::array::from_fn(|row| std::array::from_fn(|column| (0..N).map(|k| self.0[row][k] * other.0[k][column]).sum())))
+    }
+}
+
+impl<const N: usize> Mul<[i128; N]> for Matrix<N> {
+    type Output = [i128; N];
+
+    fn mul(self, vector: [i128; N]) -> [i128; N] {
+        self.0.map(|row| row.iter().zip(vector).map(|(value, component)| value * component).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::{BigInt, BigRational};
+
+    use super::*;
+
+    #[test]
+    fn solves_systems_exactly() {
+        let solution = solve_integers(&[vec![2, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]], &[8, -11, -3]);
+        assert_eq!(solution, Some(vec![Rational::from(2), Rational::from(3), Rational::from(-1)]));
+        let solution = solve_integers(&[vec![3, 0], vec![0, 2]], &[1, 1]).unwrap();
+        assert_eq!(solution, vec![Rational::new(1, 3), Rational::new(1, 2)]);
+    }
+
+    #[test]
+    fn rejects_singular_systems() {
+        assert_eq!(solve_integers(&[vec![1, 2], vec![2, 4]], &[3, 6]), None);
+    }
+
+    #[test]
+    fn computes_determinants_exactly() {
+        assert_eq!(Matrix([[2, -3, 1], [2, 0, -1], [1, 4, 5]]).determinant(), 49);
+        assert_eq!(Matrix([[0, 1], [1, 0]]).determinant(), -1);
+        assert_eq!(Matrix([[1, 2, 3], [4, 5, 6], [7, 8, 9]]).determinant(), 0);
+        assert_eq!(Matrix::<4>::identity().determinant(), 1);
+        assert_eq!(Matrix::<0>([]).determinant(), 1);
+    }
+
+    #[test]
+    fn inverts_through_the_adjugate() {
+        let matrix = Matrix([[2, -3, 1], [2, 0, -1], [1, 4, 5]]);
+        let adjugate = matrix.adjugate();
+        assert_eq!(adjugate, Matrix([[4, 19, 3], [-11, 9, 4], [8, -11, 6]]));
+        assert_eq!(matrix * adjugate, Matrix([[49, 0, 0], [0, 49, 0], [0, 0, 49]]));
+        assert_eq!(matrix.inverse().unwrap()[0], [Rational::new(4, 49), Rational::new(19, 49), Rational::new(3, 49)]);
+        assert_eq!(Matrix([[1, 2], [2, 4]]).inverse(), None);
+    }
+
+    #[test]
+    fn solves_systems_by_cramers_rule() {
+        let matrix = Matrix([[2, 1, -1], [-3, -1, 2], [-2, 1, 2]]);
+        assert_eq!(matrix.solve([8, -11, -3]), Some([Rational::from(2), Rational::from(3), Rational::from(-1)]));
+        assert_eq!(matrix * [2, 3, -1], [8, -11, -3]);
+        assert_eq!(matrix.transpose().0[0], [2, -3, -2]);
+    }
+
+    #[test]
+    fn solves_systems_of_big_fractions() {
+        let big = |n: i128| BigRational::from(BigInt::from(n));
+        let huge = 10_i128.pow(30);
+        let solution = solve(&[vec![big(huge), big(huge)], vec![big(huge), big(-huge)]], &[big(4 * huge), big(2 * huge)]);
+        assert_eq!(solution, Some(vec![big(3), big(1)]));
+    }
+}
@@ -0,0 +1,408 @@
+use std::fs;
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use tracing::Level;
+
+use aoc2023::answers::Answers;
+use aoc2023::bench::{bench, format_duration, format_table};
+use aoc2023::calendar::{format_countdown, next_puzzle, puzzle_of_the_day, unlock_time};
+use aoc2023::config::{set_config, Config};
+use aoc2023::dashboard::dashboard;
+use aoc2023::input::{cache_example, cache_input, encrypt_input, daily_example, daily_input, users, CacheStatus, InputSource};
+use aoc2023::profile::{flamegraph_path, profile};
+use aoc2023::progress::{set_reporter, Reporter, Task};
+use aoc2023::puzzle::{solver, solvers_of, Part};
+use aoc2023::repl::repl_on_terminal;
+use aoc2023::results::generate_results;
+use aoc2023::runner::{run_all, PartRun, Repetitions, DEFAULT_BUDGET};
+use aoc2023::scaffold::scaffold;
+use aoc2023::submit::submit;
+use aoc2023::summary::Summary;
+use aoc2023::verify::{verify, verify_example, verify_user, Verdict, Verification};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2023 solutions")]
+struct Cli {
+    /// Advent of Code edition of the puzzles, 2023 unless set in aoc.toml or AOC_YEAR
+    #[arg(long, global = true)]
+    year: Option<u32>,
+    /// Number of threads solving days in parallel, one per core unless set in aoc.toml or AOC_THREADS
+    #[arg(long, global = true)]
+    threads: Option<usize>,
+    /// Log progress and timings to standard error, -vv for more details
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    verbose: u8,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve both parts of a day's puzzle, or of every day
+    Run {
+        #[arg(long, required_unless_present_any = ["all", "today"])]
+        day: Option<u32>,
+        /// Run every solved day
+        #[arg(long, conflicts_with = "day")]
+        all: bool,
+        /// Run the puzzle of the current day in EST
+        #[arg(long, conflicts_with_all = ["day", "all"])]
+        today: bool,
+        /// Wait for the next puzzle to unlock, download its input and run it
+        #[arg(long, requires = "today", conflicts_with_all = ["stdin", "input"])]
+        wait: bool,
+        /// Run days concurrently on a thread pool
+        #[arg(long, requires = "all")]
+        parallel: bool,
+        /// Read the puzzle input from standard input
+        #[arg(long, requires = "day")]
+        stdin: bool,
+        /// Read the puzzle input from this file instead of the inputs directory
+        #[arg(long, value_name = "PATH", requires = "day", conflicts_with = "stdin")]
+        input: Option<String>,
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        /// Seconds each part may run before it is reported as over budget
+        #[arg(long, value_name = "SECONDS", default_value_t = DEFAULT_BUDGET.as_secs())]
+        budget: u64,
+        /// Solve each part this many times and report the median and standard deviation of its solving time
+        #[arg(long, default_value_t = 1)]
+        runs: usize,
+        /// Solve each part this many times before measuring
+        #[arg(long, default_value_t = 0)]
+        warmup: usize,
+    },
+    /// Download the puzzle input of a day using the AOC_SESSION cookie
+    Fetch {
+        day: u32,
+        /// Download the input again even if it is already cached
+        #[arg(long)]
+        refresh: bool,
+        /// Extract the example from the puzzle page instead of downloading the input
+        #[arg(long)]
+        example: bool,
+    },
+    /// Encrypt cached puzzle inputs with AOC_INPUT_KEY so that they can be committed
+    Encrypt {
+        /// Only encrypt the input of this day
+        #[arg(long)]
+        day: Option<u32>,
+    },
+    /// Submit the answer to a part of a day's puzzle, solving it if no answer is given
+    Submit {
+        day: u32,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        answer: Option<String>,
+    },
+    /// Run every solver and compare results with the answers registered in answers.toml
+    Verify {
+        /// Check each day against its example answers first and stop at the first wrong one
+        #[arg(long)]
+        examples_first: bool,
+        /// Check the inputs and answers of every user in <inputs>/<year>/users instead of the personal ones
+        #[arg(long, conflicts_with = "examples_first")]
+        users: bool,
+    },
+    /// Time parsing and both parts of each day's solution
+    Bench {
+        /// Only benchmark this day
+        #[arg(long)]
+        day: Option<u32>,
+        /// Runs discarded before measuring
+        #[arg(long, default_value_t = 3)]
+        warmup: usize,
+        /// Measured runs
+        #[arg(long, alias = "runs", default_value_t = 10)]
+        iterations: usize,
+    },
+    /// Solve days having alternative implementations with each of them, checking they agree and comparing their speed
+    Compare {
+        /// Only compare the implementations of this day
+        #[arg(long)]
+        day: Option<u32>,
+    },
+    /// Run a day's solver under a sampling profiler and write a flamegraph
+    Profile {
+        #[arg(long)]
+        day: u32,
+        /// Only profile this part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Solve the puzzle this many times to collect more samples
+        #[arg(long, default_value_t = 1)]
+        iterations: usize,
+        /// Write the SVG to this file instead of flamegraph-<year>-day<NN>.svg
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Generate a markdown table of every day's answers and runtime
+    Results {
+        /// Write the table to this file instead of printing it
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Show a live table of every day's answers and runtime, re-running a day on demand
+    Dashboard {
+        /// Seconds each part may run before it is reported as over budget
+        #[arg(long, value_name = "SECONDS", default_value_t = DEFAULT_BUDGET.as_secs())]
+        budget: u64,
+    },
+    /// Parse a day's input and answer queries about the parsed puzzle
+    Repl {
+        #[arg(long)]
+        day: u32,
+        /// Parse the example instead of the puzzle input
+        #[arg(long)]
+        example: bool,
+    },
+    /// Create the source, example and input files of a new day and register its solver
+    Scaffold { day: u32 },
+}
+
+fn fail(message: String) -> ! {
+    eprintln!("{}", message);
+    process::exit(1)
+}
+
+struct ProgressBars(MultiProgress);
+
+impl Reporter for ProgressBars {
+    fn start(&self, label: String, total: u64) -> Box<dyn Task> {
+        let style = ProgressStyle::with_template("{msg} [{bar:40}] {percent}% ETA {eta}").unwrap().progress_chars("=> ");
+        Box::new(ProgressTask(self.0.add(ProgressBar::new(total).with_style(style).with_message(label))))
+    }
+}
+
+struct ProgressTask(ProgressBar);
+
+impl Task for ProgressTask {
+    fn advance(&self, amount: u64) {
+        self.0.inc(amount)
+    }
+
+    fn finish(&self) {
+        self.0.finish_and_clear()
+    }
+}
+
+fn describe(verification: &Verification) -> String {
+    match &verification.verdict {
+        Verdict::Correct => format!("{} ok", verification.answer),
+        Verdict::Wrong { expected } => format!("{} WRONG, expected {}", verification.answer, expected),
+        Verdict::Unknown => format!("{} unknown", verification.answer),
+    }
+}
+
+fn init_logging(verbosity: u8) {
+    let level = match verbosity {
+        0 => Level::WARN,
+        1 => Level::DEBUG,
+        _ => Level::TRACE,
+    };
+    tracing_subscriber::fmt().with_max_level(level).with_writer(std::io::stderr).without_time().init();
+}
+
+fn main() {
+    let cli = Cli::parse();
+    init_logging(cli.verbose);
+    let mut config = Config::load().unwrap_or_else(|error| fail(error));
+    config.year = cli.year.unwrap_or(config.year);
+    config.threads = cli.threads.or(config.threads);
+    if let Some(threads) = config.threads {
+        rayon::ThreadPoolBuilder::new().num_threads(threads).build_global().unwrap_or_else(|e| fail(format!("Could not start {} threads: {}", threads, e)));
+    }
+    let year = config.year;
+    set_config(config);
+    match cli.command {
+        Command::Run { day, all, today, wait, parallel, stdin, input, format, budget, runs, warmup } => {
+            set_reporter(ProgressBars(MultiProgress::new()));
+            let (year, day) = match (today, wait) {
+                (true, true) => {
+                    let (year, day) = next_puzzle(SystemTime::now());
+                    (year, Some(day))
+                }
+                (true, false) => {
+                    let (year, day) = puzzle_of_the_day(SystemTime::now()).unwrap_or_else(|| fail("No puzzle unlocked today".to_owned()));
+                    (year, Some(day))
+                }
+                (false, _) => (year, day),
+            };
+            let solvers = match day {
+                Some(day) => vec![solver(year, day).unwrap_or_else(|| fail(format!("Day {} of {} is not solved yet", day, year)))],
+                None => solvers_of(year),
+            };
+            if let (true, Some(day)) = (wait, day) {
+                let unlock = unlock_time(year, day);
+                if let Ok(remaining) = unlock.duration_since(SystemTime::now()) {
+                    println!("Waiting {} for day {} of {} to unlock", format_countdown(remaining), day, year);
+                    thread::sleep(remaining);
+                }
+                cache_input(year, day, false).unwrap_or_else(|e| fail(format!("Could not fetch input of day {}: {}", day, e)));
+            }
+            let start = Instant::now();
+            let source = match (stdin, input) {
+                (true, _) => InputSource::Stdin,
+                (false, Some(path)) => InputSource::File(path),
+                (false, None) => InputSource::Daily,
+            };
+            let runs = run_all(&solvers, &source, parallel, Duration::from_secs(budget), Repetitions::new(warmup, runs));
+            let wall_time = start.elapsed();
+            for part_run in &runs {
+                match format {
+                    Format::Text => println!("{}", part_run.to_text()),
+                    Format::Json => println!("{}", part_run.to_json()),
+                }
+            }
+            if let (Format::Text, true) = (format, all) {
+                print!("{}", Summary::of(&runs, wall_time).to_text());
+            } else if let Format::Text = format {
+                let cpu_time = runs.iter().map(PartRun::runtime).sum();
+                println!("{} parts solved in {} ({} of solving time)", runs.iter().filter(|r| r.solution.is_ok()).count(), format_duration(wall_time), format_duration(cpu_time));
+            }
+        }
+        Command::Fetch { day, refresh, example: true } => match cache_example(year, day, refresh) {
+            Ok((path, CacheStatus::Hit)) => println!("Day {} example at {} (cached)", day, path),
+            Ok((path, _)) => println!("Day {} example extracted to {}", day, path),
+            Err(error) => fail(format!("Could not fetch example of day {}: {}", day, error)),
+        },
+        Command::Fetch { day, refresh, example: false } => match cache_input(year, day, refresh) {
+            Ok(cached) => {
+                let status = match cached.status {
+                    CacheStatus::Hit => "cached",
+                    CacheStatus::Downloaded => "downloaded",
+                    CacheStatus::Decrypted => "decrypted",
+                    CacheStatus::Offline => "offline, using cached copy",
+                };
+                println!("Day {} input at {} ({}, {} bytes, fetched {}s ago)",
+                         day, cached.path, status, cached.metadata.size, cached.metadata.age().as_secs());
+            }
+            Err(error) => fail(format!("Could not fetch input of day {}: {}", day, error)),
+        },
+        Command::Encrypt { day } => {
+            let days = match day {
+                Some(day) => vec![day],
+                None => solvers_of(year).iter().map(|solver| solver.day).collect(),
+            };
+            for day in days {
+                match encrypt_input(year, day) {
+                    Ok(path) => println!("Day {} input encrypted to {}", day, path),
+                    Err(error) => fail(format!("Could not encrypt input of day {}: {}", day, error)),
+                }
+            }
+        }
+        Command::Submit { day, part, answer } => {
+            set_reporter(ProgressBars(MultiProgress::new()));
+            let answer = answer.unwrap_or_else(|| match solver(year, day) {
+                Some(solver) => daily_input(year, day)
+                    .and_then(|input| (solver.solve)(Part::from_number(part).unwrap(), input))
+                    .unwrap_or_else(|e| fail(format!("Could not solve day {} part {}: {}", day, part, e)))
+                    .answer,
+                None => fail(format!("Day {} of {} is not solved yet", day, year)),
+            });
+            match submit(year, day, part, &answer) {
+                Ok(outcome) => println!("Day {} part {}: {} is {}", day, part, answer, outcome.describe()),
+                Err(error) => fail(format!("Could not submit answer of day {} part {}: {}", day, part, error)),
+            }
+        }
+        Command::Verify { examples_first: _, users: true } => {
+            let mut failures = 0;
+            for user in users(year).unwrap_or_else(|e| fail(format!("Could not list users: {}", e))) {
+                for solver in solvers_of(year) {
+                    let verifications = verify_user(&solver, &user).unwrap_or_else(|e| fail(format!("Could not solve day {} of {}: {}", solver.day, user, e)));
+                    for verification in verifications.into_iter().flatten() {
+                        if verification.is_wrong() { failures += 1 }
+                        println!("Day {:0>2} part {} of {}: {}", verification.day, verification.part, user, describe(&verification));
+                    }
+                }
+            }
+            if failures > 0 {
+                fail(format!("{} wrong answer(s)", failures))
+            }
+        }
+        Command::Verify { examples_first, users: false } => {
+            let answers = Answers::load().unwrap_or_else(|e| fail(format!("Could not read answers: {}", e)));
+            let mut failures = 0;
+            for solver in solvers_of(year) {
+                if examples_first {
+                    let examples = verify_example(&solver).unwrap_or_else(|e| fail(format!("Could not solve example of day {}: {}", solver.day, e)));
+                    for verification in examples.iter().flatten() {
+                        println!("Day {:0>2} part {} example: {}", verification.day, verification.part, describe(verification));
+                    }
+                    if examples.iter().flatten().any(Verification::is_wrong) {
+                        fail(format!("Wrong example answer of day {}, real input not solved", solver.day))
+                    }
+                }
+                let verifications = verify(&solver, &answers).unwrap_or_else(|e| fail(format!("Could not solve day {}: {}", solver.day, e)));
+                for verification in verifications {
+                    if verification.is_wrong() { failures += 1 }
+                    println!("Day {:0>2} part {}: {}", verification.day, verification.part, describe(&verification));
+                }
+            }
+            if failures > 0 {
+                fail(format!("{} wrong answer(s)", failures))
+            }
+        }
+        Command::Bench { day, warmup, iterations } => {
+            let benchmarks: Vec<_> = solvers_of(year).iter()
+                .filter(|solver| day.is_none_or(|day| solver.day == day))
+                .map(|solver| bench(solver, warmup, iterations.max(1))
+                    .unwrap_or_else(|e| fail(format!("Could not benchmark day {}: {}", solver.day, e))))
+                .collect();
+            print!("{}", format_table(&benchmarks));
+        }
+        Command::Compare { day } => {
+            let comparisons: Vec<_> = solvers_of(year).iter()
+                .filter(|solver| day.is_none_or(|day| solver.day == day))
+                .flat_map(|solver| daily_input(solver.year, solver.day).and_then(|input| (solver.compare)(input))
+                    .unwrap_or_else(|e| fail(format!("Could not compare implementations of day {}: {}", solver.day, e))))
+                .collect();
+            comparisons.iter().for_each(|comparison| print!("{}", comparison.to_text()));
+            let disagreements = comparisons.iter().filter(|comparison| !comparison.agrees()).count();
+            if disagreements > 0 {
+                fail(format!("{} part(s) with implementations that disagree", disagreements))
+            }
+        }
+        Command::Profile { day, part, iterations, output } => {
+            let solver = solver(year, day).unwrap_or_else(|| fail(format!("Day {} of {} is not solved yet", day, year)));
+            let parts = match part {
+                Some(part) => vec![Part::from_number(part).unwrap()],
+                None => Part::both().to_vec(),
+            };
+            let output = output.unwrap_or_else(|| flamegraph_path(year, day));
+            match profile(&solver, &parts, iterations.max(1), &output) {
+                Ok(samples) => println!("Flamegraph of {} samples written to {}", samples, output),
+                Err(error) => fail(format!("Could not profile day {}: {}", day, error)),
+            }
+        }
+        Command::Results { output } => {
+            let table = generate_results(&solvers_of(year));
+            match output {
+                Some(path) => fs::write(&path, table).unwrap_or_else(|e| fail(format!("Could not write {}: {}", path, e))),
+                None => print!("{}", table),
+            }
+        }
+        Command::Dashboard { budget } => dashboard(solvers_of(year), Duration::from_secs(budget))
+            .unwrap_or_else(|e| fail(format!("Could not show dashboard: {}", e))),
+        Command::Repl { day, example } => {
+            let solver = solver(year, day).unwrap_or_else(|| fail(format!("Day {} of {} is not solved yet", day, year)));
+            let input = if example { daily_example(year, day) } else { daily_input(year, day) };
+            input.and_then(|input| repl_on_terminal(&solver, input))
+                .unwrap_or_else(|e| fail(format!("Could not explore day {}: {}", day, e)))
+        }
+        Command::Scaffold { day } => match scaffold(year, day) {
+            Ok(files) => files.iter().for_each(|file| println!("Created {}", file)),
+            Err(error) => fail(format!("Could not scaffold day {}: {}", day, error)),
+        },
+    }
+}
@@ -0,0 +1,245 @@
+use std::cmp::Ordering;
+use std::ops::{Neg, RangeInclusive};
+
+use num::integer::Roots;
+use num::{BigInt, CheckedMul, Integer, One};
+
+/// Greatest common divisor of all `numbers`, 0 when there are none.
+pub fn gcd_of<T: Integer + Copy>(numbers: impl IntoIterator<Item=T>) -> T {
+    numbers.into_iter().fold(T::zero(), |gcd, n| gcd.gcd(&n))
+}
+
+/// Least common multiple of all `numbers`, 1 when there are none.
+pub fn lcm_of<T: Integer + Copy>(numbers: impl IntoIterator<Item=T>) -> T {
+    numbers.into_iter().fold(T::one(), |lcm, n| lcm.lcm(&n))
+}
+
+/// Least common multiple of all `numbers`, `None` when it overflows `T`.
+pub fn checked_lcm_of<T: Integer + CheckedMul + Copy>(numbers: impl IntoIterator<Item=T>) -> Option<T> {
+    numbers.into_iter().try_fold(T::one(), |lcm, n| {
+        if n.is_zero() { return Some(T::zero()); }
+        (lcm / lcm.gcd(&n)).checked_mul(&n)
+    })
+}
+
+/// Least common multiple of all `numbers` as a big integer, which never overflows.
+pub fn big_lcm_of<T: Into<BigInt>>(numbers: impl IntoIterator<Item=T>) -> BigInt {
+    numbers.into_iter().fold(BigInt::one(), |lcm, n| lcm.lcm(&n.into()))
+}
+
+/// Product of all `numbers`, `None` when it overflows `T`.
+pub fn checked_product<T: CheckedMul + One>(numbers: impl IntoIterator<Item=T>) -> Option<T> {
+    numbers.into_iter().try_fold(T::one(), |product, n| product.checked_mul(&n))
+}
+
+/// Compares the products of `left` and `right` as big integers, whatever their size.
+pub fn compare_products<T: Into<BigInt> + Copy>(left: &[T], right: &[T]) -> Ordering {
+    let product = |numbers: &[T]| numbers.iter().fold(BigInt::one(), |product, &n| product * n.into());
+    product(left).cmp(&product(right))
+}
+
+/// Greatest common divisor `g` of `a` and `b` with coefficients such that `a * x + b * y == g`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    if old_r < 0 { (-old_r, -old_x, -old_y) } else { (old_r, old_x, old_y) }
+}
+
+/// Combines congruences `n ≡ residue (mod modulus)` with the Chinese remainder theorem, moduli not having to be
+/// coprime. Returns the smallest non-negative solution with the modulus of all solutions, or `None` when the
+/// congruences contradict each other.
+pub fn chinese_remainder(congruences: impl IntoIterator<Item=(i128, i128)>) -> Option<(i128, i128)> {
+    congruences.into_iter().try_fold((0, 1), |(residue, modulus), (r, m)| {
+        let (gcd, x, _) = extended_gcd(modulus, m);
+        if (r - residue) % gcd != 0 {
+            return None;
+        }
+        let combined = modulus / gcd * m;
+        let step = ((r - residue) / gcd % (m / gcd)) * x % (m / gcd);
+        Some(((residue + modulus * step).rem_euclid(combined), combined))
+    })
+}
+
+/// `base` to the power `exponent`, modulo `modulus`.
+pub fn mod_pow(base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let modulus = modulus as u128;
+    let (mut base, mut result) = (base as u128 % modulus, 1 % modulus);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    result as u64
+}
+
+/// Number `x` in `0..modulus` such that `a * x ≡ 1 (mod modulus)`, if `a` and `modulus` are coprime.
+pub fn mod_inverse(a: i128, modulus: i128) -> Option<i128> {
+    let (gcd, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (gcd == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Value at `x` of the polynomial of lowest degree going through `samples` taken at `x = 0, 1, 2…`, by Newton's
+/// forward differences. Extrapolates sequences whose differences end up constant, or evaluates the quadratic fitting
+/// three terms of a sequence growing quadratically, `x` being negative to look back before the first sample.
+pub fn extrapolate(samples: &[i128], x: i128) -> i128 {
+    let mut differences = samples.to_vec();
+    let (mut value, mut binomial) = (0, 1);
+    for k in 0..samples.len() {
+        value += binomial * differences[0];
+        binomial = binomial * (x - k as i128) / (k as i128 + 1);
+        differences = differences.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    }
+    value
+}
+
+/// The polynomial `a·x² + b·x + c` over integers, `a` not being zero.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash)]
+pub struct Quadratic {
+    pub a: i128,
+    pub b: i128,
+    pub c: i128,
+}
+
+impl Quadratic {
+    pub fn at(&self, x: i128) -> i128 {
+        (self.a * x + self.b) * x + self.c
+    }
+
+    pub fn discriminant(&self) -> i128 {
+        self.b * self.b - 4 * self.a * self.c
+    }
+
+    /// Floors of the real roots, smallest first, computed exactly with integer square roots. `None` when there is no
+    /// real root.
+    pub fn root_floors(&self) -> Option<(i128, i128)> {
+        let discriminant = self.discriminant();
+        if discriminant < 0 {
+            return None;
+        }
+        let upward = if self.a < 0 { -*self } else { *self };
+        let Quadratic { a, b, .. } = upward;
+        let root = discriminant.sqrt();
+        // Roots lie within half a unit past the approximations from the floored square root, and the polynomial tells
+        // on which side of a root each integer is.
+        let lower = (-b - root - 1).div_euclid(2 * a);
+        let lower = if 2 * a * (lower + 1) <= -b && upward.at(lower + 1) >= 0 { lower + 1 } else { lower };
+        let upper = (-b + root).div_euclid(2 * a);
+        let upper = if 2 * a * (upper + 1) >= -b && upward.at(upper + 1) <= 0 { upper + 1 } else { upper };
+        Some((lower, upper))
+    }
+
+    /// Integers strictly between the real roots, where the polynomial has the opposite sign of `a`, `None` when there
+    /// are none.
+    pub fn between_roots(&self) -> Option<RangeInclusive<i128>> {
+        let (lower, upper) = self.root_floors()?;
+        let (start, end) = (lower + 1, if self.at(upper) == 0 { upper - 1 } else { upper });
+        (start <= end).then_some(start..=end)
+    }
+
+    /// Number of integers of `interval` strictly between the real roots.
+    pub fn count_between_roots(&self, interval: RangeInclusive<i128>) -> usize {
+        let Some(roots) = self.between_roots() else { return 0 };
+        let (start, end) = (*roots.start().max(interval.start()), *roots.end().min(interval.end()));
+        if start > end { 0 } else { (end - start + 1) as usize }
+    }
+}
+
+impl Neg for Quadratic {
+    type Output = Quadratic;
+
+    fn neg(self) -> Quadratic {
+        Quadratic { a: -self.a, b: -self.b, c: -self.c }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folds_gcd_and_lcm_over_numbers() {
+        assert_eq!(gcd_of([12_u64, 18, 30]), 6);
+        assert_eq!(lcm_of([2_usize, 3, 4]), 12);
+        assert_eq!(lcm_of(Vec::<usize>::new()), 1);
+    }
+
+    #[test]
+    fn detects_overflowing_lcm_and_products() {
+        assert_eq!(checked_lcm_of([4_u8, 6, 10]), Some(60));
+        assert_eq!(checked_lcm_of([16_u8, 17]), None);
+        assert_eq!(checked_lcm_of([3_u8, 0]), Some(0));
+        assert_eq!(checked_product([2_i64, -3, 7]), Some(-42));
+        assert_eq!(checked_product([u64::MAX, 2]), None);
+    }
+
+    #[test]
+    fn computes_big_lcm_and_compares_big_products() {
+        let primes = [1_000_000_007_u64, 998_244_353, 1_000_000_009];
+        assert_eq!(big_lcm_of(primes).to_string(), "998244368971909710889394239");
+        assert_eq!(compare_products(&[i128::MAX, 3], &[i128::MAX, 2]), Ordering::Greater);
+        assert_eq!(compare_products(&[i128::MAX, -1], &[i128::MIN]), Ordering::Greater);
+        assert_eq!(compare_products::<i64>(&[], &[1]), Ordering::Equal);
+    }
+
+    #[test]
+    fn finds_bezout_coefficients() {
+        let (gcd, x, y) = extended_gcd(240, 46);
+        assert_eq!(gcd, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+    }
+
+    #[test]
+    fn computes_modular_powers_and_inverses() {
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(3, 1_000_000_000_000, 1), 0);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn extrapolates_polynomial_sequences() {
+        assert_eq!(extrapolate(&[0, 3, 6, 9, 12, 15], 6), 18);
+        assert_eq!(extrapolate(&[10, 13, 16, 21, 30, 45], 6), 68);
+        assert_eq!(extrapolate(&[10, 13, 16, 21, 30, 45], -1), 5);
+        assert_eq!(extrapolate(&[3, 5, 9], 10), 3 + 10 + 10 * 10);
+    }
+
+    #[test]
+    fn floors_roots_of_quadratics_exactly() {
+        assert_eq!(Quadratic { a: 1, b: -7, c: 9 }.root_floors(), Some((1, 5)));
+        assert_eq!(Quadratic { a: -1, b: 7, c: -9 }.root_floors(), Some((1, 5)));
+        assert_eq!(Quadratic { a: 1, b: -30, c: 200 }.root_floors(), Some((10, 20)));
+        assert_eq!(Quadratic { a: 2, b: 0, c: -1 }.root_floors(), Some((-1, 0)));
+        assert_eq!(Quadratic { a: 1, b: 0, c: 1 }.root_floors(), None);
+        let huge = 10_i128.pow(15);
+        assert_eq!(Quadratic { a: 1, b: 0, c: -(huge * huge + 1) }.root_floors(), Some((-huge - 1, huge)));
+    }
+
+    #[test]
+    fn counts_integers_between_roots() {
+        let race = |time: i128, record: i128| Quadratic { a: -1, b: time, c: -record };
+        assert_eq!(race(7, 9).between_roots(), Some(2..=5));
+        assert_eq!(race(30, 200).between_roots(), Some(11..=19));
+        assert_eq!(race(4, 4).between_roots(), None);
+        assert_eq!(race(71530, 940200).count_between_roots(0..=71530), 71503);
+        assert_eq!(race(30, 200).count_between_roots(15..=100), 5);
+        assert_eq!(race(3, 5).count_between_roots(0..=3), 0);
+    }
+
+    #[test]
+    fn combines_congruences() {
+        assert_eq!(chinese_remainder([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(chinese_remainder([(3, 4), (1, 6)]), Some((7, 12)));
+        assert_eq!(chinese_remainder([(0, 4), (1, 6)]), None);
+    }
+}
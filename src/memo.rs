@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Values of a function already computed, by argument.
+#[derive(Debug, Clone)]
+pub struct Cache<K, V> {
+    values: HashMap<K, V>,
+    hits: usize,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Cache<K, V> {
+    pub fn new() -> Cache<K, V> {
+        Cache { values: HashMap::new(), hits: 0 }
+    }
+
+    /// Value of `f` for `key`, computed at most once. `f` is given a function to look up the values of other keys
+    /// through the cache, so that recursive solutions only explore each subproblem once.
+    pub fn get_or_compute(&mut self, key: K, f: &impl Fn(&mut dyn FnMut(K) -> V, K) -> V) -> V {
+        if let Some(value) = self.values.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+        let value = f(&mut |next| self.get_or_compute(next, f), key.clone());
+        self.values.insert(key, value.clone());
+        value
+    }
+
+    /// Number of values computed so far.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Number of lookups answered without computing.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.hits = 0;
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Default for Cache<K, V> {
+    fn default() -> Cache<K, V> {
+        Cache::new()
+    }
+}
+
+/// Memoized version of the recursive function `f`, which calls itself through the function it is given.
+pub fn memoize<K, V>(f: impl Fn(&mut dyn FnMut(K) -> V, K) -> V) -> impl FnMut(K) -> V
+where K: Eq + Hash + Clone, V: Clone {
+    let mut cache = Cache::new();
+    move |key| cache.get_or_compute(key, &f)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    fn fibonacci(fib: &mut dyn FnMut(u64) -> u64, n: u64) -> u64 {
+        if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+    }
+
+    #[test]
+    fn computes_each_value_once() {
+        let calls = Cell::new(0);
+        let mut cache = Cache::new();
+        let counted = |fib: &mut dyn FnMut(u64) -> u64, n| {
+            calls.set(calls.get() + 1);
+            fibonacci(fib, n)
+        };
+        assert_eq!(cache.get_or_compute(90, &counted), 2_880_067_194_370_816_120);
+        assert_eq!((calls.get(), cache.len(), cache.hits()), (91, 91, 88));
+        assert_eq!(cache.get_or_compute(50, &counted), 12_586_269_025);
+        assert_eq!(calls.get(), 91);
+    }
+
+    #[test]
+    fn memoizes_recursive_functions() {
+        let mut paths = memoize(|paths: &mut dyn FnMut((u64, u64)) -> u64, (x, y)| {
+            if x == 0 || y == 0 { 1 } else { paths((x - 1, y)) + paths((x, y - 1)) }
+        });
+        assert_eq!(paths((16, 16)), 601_080_390);
+    }
+}
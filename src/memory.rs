@@ -0,0 +1,106 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+
+#[derive(PartialEq, Debug, Copy, Clone, Default)]
+pub struct Allocations {
+    pub count: u64,
+    pub bytes: u64,
+    pub peak: u64,
+}
+
+// Counters shared by every thread, so that allocations of the worker threads a solver spreads its work over count too.
+static COUNT: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+static CURRENT: AtomicI64 = AtomicI64::new(0);
+
+fn record(size: usize) {
+    COUNT.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size as u64, Ordering::Relaxed);
+    let current = CURRENT.fetch_add(size as i64, Ordering::Relaxed) + size as i64;
+    PEAK.fetch_max(current.max(0) as u64, Ordering::Relaxed);
+}
+
+fn release(size: usize) {
+    CURRENT.fetch_sub(size as i64, Ordering::Relaxed);
+}
+
+fn reset() {
+    for counter in [&COUNT, &BYTES, &PEAK] {
+        counter.store(0, Ordering::Relaxed);
+    }
+    CURRENT.store(0, Ordering::Relaxed);
+}
+
+fn allocations() -> Allocations {
+    Allocations { count: COUNT.load(Ordering::Relaxed), bytes: BYTES.load(Ordering::Relaxed), peak: PEAK.load(Ordering::Relaxed) }
+}
+
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        release(layout.size());
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Allocations made by all threads while `f` runs, counters being reset beforehand: parts measured at the same time
+/// count each other's allocations.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Allocations>) {
+    if !cfg!(feature = "alloc-stats") {
+        return (f(), None);
+    }
+    reset();
+    let result = f();
+    (result, Some(allocations()))
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{}B", bytes),
+        1024..=1_048_575 => format!("{:.1}KiB", bytes as f64 / 1024.0),
+        _ => format!("{:.1}MiB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(not(feature = "alloc-stats"), ignore = "requires the counting allocator")]
+    fn counts_allocations_of_measured_code() {
+        let (_, allocations) = measure(|| {
+            let first = vec![0_u8; 1000];
+            drop(first);
+            vec![0_u8; 600]
+        });
+        // Tests running alongside allocate too, so only lower bounds hold.
+        let allocations = allocations.unwrap();
+        assert!(allocations.count >= 2 && allocations.bytes >= 1600 && allocations.peak >= 1000, "{:?}", allocations);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "alloc-stats"), ignore = "requires the counting allocator")]
+    fn counts_allocations_of_other_threads() {
+        let (_, allocations) = measure(|| std::thread::spawn(|| vec![0_u8; 1 << 20]).join().unwrap());
+        assert!(allocations.unwrap().bytes >= 1 << 20);
+    }
+
+    #[test]
+    fn formats_sizes_in_readable_units() {
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(1536), "1.5KiB");
+        assert_eq!(format_bytes(3 * 1_048_576), "3.0MiB");
+    }
+}
@@ -0,0 +1,184 @@
+use std::str::FromStr;
+
+/// Hand-rolled parser consuming a line of input from the left, its errors being ready for
+/// [`parse_line`](crate::error::parse_line) to report.
+#[derive(Debug, Clone, Copy)]
+pub struct Parser<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(input: &'a str) -> Parser<'a> {
+        Parser { rest: input }
+    }
+
+    /// Input left to parse.
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.rest.is_empty()
+    }
+
+    pub fn skip_spaces(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    /// Consumes the exact `tag`.
+    pub fn tag(&mut self, tag: &str) -> Result<(), String> {
+        self.rest = self.rest.strip_prefix(tag).ok_or_else(|| format!("expected \"{}\"", tag))?;
+        Ok(())
+    }
+
+    /// Consumes a run of letters, digits or underscores.
+    pub fn word(&mut self) -> Result<&'a str, String> {
+        match self.take_while(|c| c.is_alphanumeric() || c == '_') {
+            "" => Err("expected a word".to_owned()),
+            word => Ok(word),
+        }
+    }
+
+    /// Consumes a decimal integer, with an optional sign.
+    pub fn integer<T: FromStr>(&mut self) -> Result<T, String> {
+        let sign = self.rest.strip_prefix(['-', '+']).map_or(0, |_| 1);
+        let length = sign + self.rest[sign..].find(|c: char| !c.is_ascii_digit()).unwrap_or(self.rest.len() - sign);
+        if length == sign {
+            return Err("expected a number".to_owned());
+        }
+        let (number, rest) = self.rest.split_at(length);
+        let number = number.parse().map_err(|_| format!("invalid number {}", number))?;
+        self.rest = rest;
+        Ok(number)
+    }
+
+    /// Consumes one or more whitespace separated integers.
+    pub fn integers<T: FromStr>(&mut self) -> Result<Vec<T>, String> {
+        self.separated(" ", Parser::integer)
+    }
+
+    /// Consumes one or more items, each followed by the next one by `separator`, with optional whitespace around it.
+    /// Stops before a separator not followed by an item.
+    pub fn separated<T>(&mut self, separator: &str, mut item: impl FnMut(&mut Parser<'a>) -> Result<T, String>) -> Result<Vec<T>, String> {
+        let mut items = vec![item(self)?];
+        loop {
+            let checkpoint = *self;
+            self.skip_spaces();
+            if self.tag(separator.trim()).is_err() {
+                *self = checkpoint;
+                return Ok(items);
+            }
+            self.skip_spaces();
+            match item(self) {
+                Ok(next) => items.push(next),
+                Err(_) => {
+                    *self = checkpoint;
+                    return Ok(items);
+                }
+            }
+        }
+    }
+
+    /// Consumes the content of a section introduced by `name` followed by a colon.
+    pub fn section(&mut self, name: &str) -> Result<(), String> {
+        self.tag(name)?;
+        self.tag(":")?;
+        self.skip_spaces();
+        Ok(())
+    }
+
+    pub fn end(&self) -> Result<(), String> {
+        if self.is_done() { Ok(()) } else { Err(format!("unexpected \"{}\"", self.rest)) }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let (taken, rest) = self.rest.split_at(self.rest.find(|c| !predicate(c)).unwrap_or(self.rest.len()));
+        self.rest = rest;
+        taken
+    }
+}
+
+/// Every integer in `s`, whatever separates them, a minus sign right before digits making them negative: `x=-3, y=12`
+/// gives `[-3, 12]` and `1-3` gives `[1, -3]`. Numbers out of range are skipped.
+pub fn ints(s: &str) -> Vec<i64> {
+    numbers(s, true)
+}
+
+/// Every unsigned integer in `s`, whatever separates them, minus signs included: `1-3` gives `[1, 3]`. Numbers out of
+/// range are skipped.
+pub fn uints(s: &str) -> Vec<u64> {
+    numbers(s, false)
+}
+
+fn numbers<T: FromStr>(s: &str, signed: bool) -> Vec<T> {
+    let bytes = s.as_bytes();
+    let mut numbers = Vec::new();
+    let mut index = 0;
+    while index < bytes.len() {
+        if !bytes[index].is_ascii_digit() {
+            index += 1;
+            continue;
+        }
+        let start = if signed && index > 0 && bytes[index - 1] == b'-' { index - 1 } else { index };
+        while index < bytes.len() && bytes[index].is_ascii_digit() {
+            index += 1;
+        }
+        numbers.extend(s[start..index].parse().ok());
+    }
+    numbers
+}
+
+/// Parses the whole of `input` with `parse`, failing on anything left over.
+pub fn parse_all<'a, T>(input: &'a str, parse: impl FnOnce(&mut Parser<'a>) -> Result<T, String>) -> Result<T, String> {
+    let mut parser = Parser::new(input);
+    let parsed = parse(&mut parser)?;
+    parser.end()?;
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tags_words_and_integers() {
+        let parsed = parse_all("AAA = (BBB, -12)", |p| {
+            let from = p.word()?;
+            p.tag(" = (")?;
+            let to = p.word()?;
+            p.tag(", ")?;
+            let offset = p.integer::<i32>()?;
+            p.tag(")")?;
+            Ok((from, to, offset))
+        });
+        assert_eq!(parsed, Ok(("AAA", "BBB", -12)));
+        assert_eq!(parse_all("12", |p| p.integer::<u8>()), Ok(12));
+        assert_eq!(parse_all("300", |p| p.integer::<u8>()), Err("invalid number 300".to_owned()));
+        assert_eq!(parse_all("-", |p| p.integer::<i8>()), Err("expected a number".to_owned()));
+        assert_eq!(parse_all("12 ", |p| p.integer::<i8>()), Err("unexpected \" \"".to_owned()));
+    }
+
+    #[test]
+    fn parses_separated_lists() {
+        let mut parser = Parser::new("41 48  6 | 83");
+        assert_eq!(parser.integers::<u32>(), Ok(vec![41, 48, 6]));
+        assert_eq!(parser.rest(), " | 83");
+        assert_eq!(parse_all("a, b,c", |p| p.separated(",", Parser::word)), Ok(vec!["a", "b", "c"]));
+        assert!(parse_all("", |p| p.integers::<u32>()).is_err());
+    }
+
+    #[test]
+    fn extracts_every_integer() {
+        assert_eq!(ints("Time:      7  15   30"), vec![7, 15, 30]);
+        assert_eq!(ints("x=-3, y=12 and 1-3"), vec![-3, 12, 1, -3]);
+        assert_eq!(uints("x=-3, y=12 and 1-3"), vec![3, 12, 1, 3]);
+        assert_eq!(uints("99999999999999999999 7"), vec![7]);
+        assert!(ints("no numbers - here").is_empty());
+    }
+
+    #[test]
+    fn parses_named_sections() {
+        assert_eq!(parse_all("seeds: 79 14", |p| { p.section("seeds")?; p.integers::<u64>() }), Ok(vec![79, 14]));
+        assert_eq!(parse_all("seed: 79", |p| { p.section("seeds")?; p.integers::<u64>() }), Err("expected \"seeds\"".to_owned()));
+    }
+}
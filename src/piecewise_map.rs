@@ -0,0 +1,160 @@
+use std::ops::Range;
+
+use crate::range_set::RangeSet;
+
+/// Maps integers by adding an offset to those in each of a set of disjoint ranges, leaving the others unchanged.
+#[derive(PartialEq, Eq, Debug, Clone, Default, Hash)]
+pub struct PiecewiseMap {
+    /// Sorted, disjoint and non-empty ranges, each with a non-zero offset.
+    pieces: Vec<(Range<isize>, isize)>,
+}
+
+impl PiecewiseMap {
+    /// The identity map.
+    pub fn new() -> PiecewiseMap {
+        PiecewiseMap::default()
+    }
+
+    fn normalized(mut pieces: Vec<(Range<isize>, isize)>) -> PiecewiseMap {
+        pieces.retain(|(range, offset)| !range.is_empty() && *offset != 0);
+        pieces.sort_by_key(|(range, _)| range.start);
+        let mut merged: Vec<(Range<isize>, isize)> = Vec::with_capacity(pieces.len());
+        for (range, offset) in pieces {
+            match merged.last_mut() {
+                Some((last, last_offset)) if last.end == range.start && *last_offset == offset => last.end = range.end,
+                _ => merged.push((range, offset)),
+            }
+        }
+        PiecewiseMap { pieces: merged }
+    }
+
+    /// Ranges with their offsets, the integers outside them being left unchanged.
+    pub fn pieces(&self) -> &[(Range<isize>, isize)] {
+        &self.pieces
+    }
+
+    pub fn get(&self, number: isize) -> isize {
+        let index = self.pieces.partition_point(|(range, _)| range.end <= number);
+        match self.pieces.get(index) {
+            Some((range, offset)) if range.contains(&number) => number + offset,
+            _ => number,
+        }
+    }
+
+    /// Every integer, split into the pieces and the unchanged ranges around them, with their offsets.
+    fn segments(&self) -> Vec<(Range<isize>, isize)> {
+        let mut segments = Vec::with_capacity(2 * self.pieces.len() + 1);
+        let mut start = isize::MIN;
+        for (range, offset) in &self.pieces {
+            segments.push((start..range.start, 0));
+            segments.push((range.clone(), *offset));
+            start = range.end;
+        }
+        segments.push((start..isize::MAX, 0));
+        segments.retain(|(range, _)| !range.is_empty());
+        segments
+    }
+
+    /// Integers that the integers of `numbers` map to.
+    pub fn image(&self, numbers: &RangeSet) -> RangeSet {
+        self.segments().into_iter()
+            .map(|(range, offset)| numbers.intersection(&RangeSet::from(range)).shift(offset))
+            .fold(RangeSet::new(), |image, mapped| image.union(&mapped))
+    }
+
+    /// Map applying this map, then `next`.
+    pub fn then(&self, next: &PiecewiseMap) -> PiecewiseMap {
+        let next_segments = next.segments();
+        let pieces = self.segments().into_iter().flat_map(|(range, offset)| {
+            let image = RangeSet::from(range).shift(offset);
+            next_segments.iter()
+                .flat_map(move |(next_range, next_offset)| {
+                    image.intersection(&RangeSet::from(next_range.clone())).shift(-offset).ranges().iter()
+                        .map(|range| (range.clone(), offset + next_offset))
+                        .collect::<Vec<_>>()
+                })
+        }).collect();
+        PiecewiseMap::normalized(pieces)
+    }
+
+    /// Map undoing this map, unless two integers map to the same one or some integer is mapped to by none.
+    pub fn inverse(&self) -> Option<PiecewiseMap> {
+        let mut images: Vec<(Range<isize>, isize)> = self.segments().into_iter()
+            .map(|(range, offset)| Some((range.start.checked_add(offset)?..range.end.checked_add(offset)?, -offset)))
+            .collect::<Option<_>>()?;
+        images.sort_by_key(|(range, _)| range.start);
+        let tiles = images.windows(2).all(|pair| pair[0].0.end == pair[1].0.start);
+        let covers = images.first().is_some_and(|(range, _)| range.start == isize::MIN)
+            && images.last().is_some_and(|(range, _)| range.end == isize::MAX);
+        (tiles && covers).then(|| PiecewiseMap::normalized(images))
+    }
+}
+
+impl FromIterator<(Range<isize>, isize)> for PiecewiseMap {
+    /// Map of the given pieces, earlier ones taking precedence where they overlap, even those leaving integers unchanged.
+    fn from_iter<I: IntoIterator<Item=(Range<isize>, isize)>>(pieces: I) -> PiecewiseMap {
+        let mut covered = RangeSet::new();
+        let mut kept = Vec::new();
+        for (range, offset) in pieces {
+            let range = RangeSet::from(range);
+            kept.extend(range.difference(&covered).ranges().iter().map(|uncovered| (uncovered.clone(), offset)));
+            covered = covered.union(&range);
+        }
+        PiecewiseMap::normalized(kept)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn seed_to_soil() -> PiecewiseMap {
+        [(98..100, -48), (50..98, 2)].into_iter().collect()
+    }
+
+    fn soil_to_fertilizer() -> PiecewiseMap {
+        [(15..52, -15), (52..54, -15), (0..15, 39)].into_iter().collect()
+    }
+
+    #[test]
+    fn maps_numbers_by_piece() {
+        let map = seed_to_soil();
+        assert_eq!([79, 14, 55, 13, 98, 99].map(|n| map.get(n)), [81, 14, 57, 13, 50, 51]);
+        assert_eq!(soil_to_fertilizer().pieces(), &[(0..15, 39), (15..54, -15)]);
+        let overlapping: PiecewiseMap = [(0..10, 5), (5..20, 1)].into_iter().collect();
+        assert_eq!(overlapping.pieces(), &[(0..10, 5), (10..20, 1)]);
+        let unchanged_first: PiecewiseMap = [(647..698, 0), (607..698, -607)].into_iter().collect();
+        assert_eq!((unchanged_first.get(647), unchanged_first.get(646)), (647, 39));
+    }
+
+    #[test]
+    fn maps_ranges_of_numbers() {
+        let image = seed_to_soil().image(&RangeSet::from_iter([79..93, 55..68]));
+        assert_eq!(image.ranges(), &[57..70, 81..95]);
+        assert_eq!(seed_to_soil().image(&RangeSet::from(96..101)).ranges(), &[50..52, 98..101]);
+    }
+
+    #[test]
+    fn composes_and_inverts_maps() {
+        let seed_to_fertilizer = seed_to_soil().then(&soil_to_fertilizer());
+        assert_eq!([79, 14, 55, 13].map(|n| seed_to_fertilizer.get(n)), [81, 53, 57, 52]);
+        let fertilizer_to_seed = seed_to_fertilizer.inverse().unwrap();
+        assert_eq!([81, 53, 57, 52].map(|n| fertilizer_to_seed.get(n)), [79, 14, 55, 13]);
+        assert_eq!(PiecewiseMap::from_iter([(0..10, 5)]).inverse(), None);
+    }
+
+    fn map() -> impl Strategy<Value=PiecewiseMap> {
+        prop::collection::vec((-50..50_isize, 0..20_isize, -30..30_isize), 0..5)
+            .prop_map(|pieces| pieces.into_iter().map(|(start, length, offset)| (start..start + length, offset)).collect())
+    }
+
+    proptest! {
+        #[test]
+        fn composition_agrees_with_successive_mapping(first in map(), second in map(), n in -100..100_isize) {
+            prop_assert_eq!(first.then(&second).get(n), second.get(first.get(n)));
+            prop_assert!(first.image(&RangeSet::from(n..n + 1)).contains(first.get(n)));
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+use serde::Serialize;
+
+use crate::parse::{parse_all, Parser};
+use crate::vec2::Vec2;
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis {
+    pub const ALL: [Axis; 3] = [Axis::X, Axis::Y, Axis::Z];
+}
+
+/// A position or a displacement in space.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default, Hash, PartialOrd, Ord, Serialize)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub const ZERO: Point3 = Point3::new(0, 0, 0);
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Point3 {
+        Point3 { x, y, z }
+    }
+
+    /// Parses comma separated coordinates, such as `1,0,1` or `19, 13, 30`.
+    pub fn parse(s: &str) -> Result<Point3, String> {
+        match parse_all(s, |p| p.separated(",", Parser::integer))?.as_slice() {
+            &[x, y, z] => Ok(Point3::new(x, y, z)),
+            _ => Err("expected 3 coordinates".to_owned()),
+        }
+    }
+
+    pub fn coordinate(self, axis: Axis) -> i64 {
+        match axis {
+            Axis::X => self.x,
+            Axis::Y => self.y,
+            Axis::Z => self.z,
+        }
+    }
+
+    /// Point seen along `axis`, keeping the two other coordinates in order.
+    pub fn project(self, axis: Axis) -> Vec2 {
+        let (x, y) = match axis {
+            Axis::X => (self.y, self.z),
+            Axis::Y => (self.x, self.z),
+            Axis::Z => (self.x, self.y),
+        };
+        Vec2::new(x as isize, y as isize)
+    }
+
+    pub fn manhattan_length(self) -> u64 {
+        self.x.unsigned_abs() + self.y.unsigned_abs() + self.z.unsigned_abs()
+    }
+
+    pub fn min(self, other: Point3) -> Point3 {
+        Point3::new(self.x.min(other.x), self.y.min(other.y), self.z.min(other.z))
+    }
+
+    pub fn max(self, other: Point3) -> Point3 {
+        Point3::new(self.x.max(other.x), self.y.max(other.y), self.z.max(other.z))
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, other: Point3) -> Point3 {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl AddAssign for Point3 {
+    fn add_assign(&mut self, other: Point3) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, other: Point3) -> Point3 {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Mul<i64> for Point3 {
+    type Output = Point3;
+
+    fn mul(self, factor: i64) -> Point3 {
+        Point3::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Point3;
+
+    fn neg(self) -> Point3 {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+/// An axis-aligned box of space, both corners included.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash, Serialize)]
+pub struct Cuboid {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+impl Cuboid {
+    /// Box between two opposite corners, in any order.
+    pub fn new(a: Point3, b: Point3) -> Cuboid {
+        Cuboid { min: a.min(b), max: a.max(b) }
+    }
+
+    /// Number of unit cubes in the box.
+    pub fn volume(&self) -> u64 {
+        let size = self.max - self.min + Point3::new(1, 1, 1);
+        size.x as u64 * size.y as u64 * size.z as u64
+    }
+
+    pub fn contains(&self, point: Point3) -> bool {
+        Axis::ALL.iter().all(|&axis| (self.min.coordinate(axis)..=self.max.coordinate(axis)).contains(&point.coordinate(axis)))
+    }
+
+    pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        let (min, max) = (self.min.max(other.min), self.max.min(other.max));
+        Axis::ALL.iter().all(|&axis| min.coordinate(axis) <= max.coordinate(axis)).then_some(Cuboid { min, max })
+    }
+
+    pub fn overlaps(&self, other: &Cuboid) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Whether both boxes overlap once seen along `axis`, such as bricks stacked along the z axis.
+    pub fn overlaps_along(&self, other: &Cuboid, axis: Axis) -> bool {
+        let (min, max) = (self.min.max(other.min), self.max.min(other.max));
+        Axis::ALL.iter().filter(|&&other_axis| other_axis != axis).all(|&axis| min.coordinate(axis) <= max.coordinate(axis))
+    }
+
+    /// Corners of the rectangle covered by the box seen along `axis`.
+    pub fn project(&self, axis: Axis) -> (Vec2, Vec2) {
+        (self.min.project(axis), self.max.project(axis))
+    }
+
+    pub fn shift(&self, offset: Point3) -> Cuboid {
+        Cuboid { min: self.min + offset, max: self.max + offset }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brick(a: (i64, i64, i64), b: (i64, i64, i64)) -> Cuboid {
+        Cuboid::new(Point3::new(a.0, a.1, a.2), Point3::new(b.0, b.1, b.2))
+    }
+
+    #[test]
+    fn parses_coordinates() {
+        assert_eq!(Point3::parse("1,0,1"), Ok(Point3::new(1, 0, 1)));
+        assert_eq!(Point3::parse("19, 13, -30"), Ok(Point3::new(19, 13, -30)));
+        assert_eq!(Point3::parse("1,2"), Err("expected 3 coordinates".to_owned()));
+    }
+
+    #[test]
+    fn combines_points() {
+        let a = Point3::new(1, -2, 3);
+        assert_eq!(a + Point3::new(1, 1, 1) * 2, Point3::new(3, 0, 5));
+        assert_eq!((-a).manhattan_length(), 6);
+        assert_eq!(a.project(Axis::Y), Vec2::new(1, 3));
+    }
+
+    #[test]
+    fn intersects_boxes() {
+        let a = brick((1, 0, 1), (1, 2, 1));
+        let b = brick((0, 0, 2), (2, 0, 2));
+        assert_eq!((a.volume(), b.volume()), (3, 3));
+        assert!(!a.overlaps(&b));
+        assert!(a.overlaps_along(&b, Axis::Z));
+        assert!(!a.overlaps_along(&b.shift(Point3::new(0, 3, 0)), Axis::Z));
+        assert_eq!(a.intersection(&b.shift(Point3::new(0, 0, -1))), Some(brick((1, 0, 1), (1, 0, 1))));
+        assert!(a.contains(Point3::new(1, 1, 1)));
+        assert_eq!(a.project(Axis::Z), (Vec2::new(1, 0), Vec2::new(1, 2)));
+    }
+}
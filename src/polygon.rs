@@ -0,0 +1,50 @@
+use num::Integer;
+
+use crate::vec2::Vec2;
+
+/// Twice the area enclosed by a polygon with the given vertices, in either winding order, with the Shoelace formula.
+/// Doubling keeps the area of a lattice polygon whole.
+pub fn double_area(vertices: &[Vec2]) -> usize {
+    let edges = vertices.iter().zip(vertices.iter().cycle().skip(1));
+    edges.map(|(a, b)| a.x * b.y - b.x * a.y).sum::<isize>().unsigned_abs()
+}
+
+/// Number of lattice points lying on the edges of a polygon with the given vertices.
+pub fn boundary_points(vertices: &[Vec2]) -> usize {
+    let edges = vertices.iter().zip(vertices.iter().cycle().skip(1));
+    edges.map(|(&a, &b)| b - a).map(|edge| edge.x.unsigned_abs().gcd(&edge.y.unsigned_abs())).sum()
+}
+
+/// Number of lattice points strictly inside a lattice polygon with the given vertices, with Pick's theorem.
+pub fn interior_points(vertices: &[Vec2]) -> usize {
+    if vertices.len() < 3 {
+        return 0;
+    }
+    (double_area(vertices) + 2 - boundary_points(vertices)) / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(vertices: &[(isize, isize)]) -> Vec<Vec2> {
+        vertices.iter().map(|&(x, y)| Vec2::new(x, y)).collect()
+    }
+
+    #[test]
+    fn measures_area_in_either_winding_order() {
+        let square = polygon(&[(0, 0), (4, 0), (4, 4), (0, 4)]);
+        assert_eq!(double_area(&square), 32);
+        assert_eq!(double_area(&square.iter().rev().copied().collect::<Vec<_>>()), 32);
+        assert_eq!(double_area(&polygon(&[(0, 0), (3, 0), (0, 1)])), 3);
+    }
+
+    #[test]
+    fn counts_lattice_points_with_picks_theorem() {
+        let square = polygon(&[(0, 0), (4, 0), (4, 4), (0, 4)]);
+        assert_eq!((boundary_points(&square), interior_points(&square)), (16, 9));
+        let triangle = polygon(&[(0, 0), (6, 0), (0, 4)]);
+        assert_eq!((boundary_points(&triangle), interior_points(&triangle)), (12, 7));
+        assert_eq!(interior_points(&polygon(&[(0, 0), (5, 5)])), 0);
+    }
+}
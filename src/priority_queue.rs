@@ -0,0 +1,105 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+/// Keys popped by lowest priority first, queuing a key again lowering its priority instead of adding a duplicate.
+/// Entries made stale by a lower priority stay in the underlying heap and are skipped when they come out.
+#[derive(Debug, Clone)]
+pub struct PriorityQueue<K, P> {
+    heap: BinaryHeap<Reverse<(P, usize)>>,
+    slots: HashMap<K, usize>,
+    keys: Vec<K>,
+    priorities: Vec<Option<P>>,
+    len: usize,
+}
+
+impl<K: Eq + Hash + Clone, P: Ord + Copy> PriorityQueue<K, P> {
+    pub fn new() -> PriorityQueue<K, P> {
+        PriorityQueue { heap: BinaryHeap::new(), slots: HashMap::new(), keys: Vec::new(), priorities: Vec::new(), len: 0 }
+    }
+
+    /// Queues `key` with `priority`, or lowers its priority when already queued with a higher one. Tells whether the
+    /// queue changed. A key can be queued again once popped. Keys of equal priority pop in the order first queued.
+    pub fn push(&mut self, key: K, priority: P) -> bool {
+        let slot = *self.slots.entry(key.clone()).or_insert_with(|| {
+            self.keys.push(key);
+            self.priorities.push(None);
+            self.keys.len() - 1
+        });
+        match self.priorities[slot] {
+            Some(queued) if queued <= priority => return false,
+            Some(_) => {}
+            None => self.len += 1,
+        }
+        self.priorities[slot] = Some(priority);
+        self.heap.push(Reverse((priority, slot)));
+        true
+    }
+
+    /// Key of lowest priority, with its priority.
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        while let Some(Reverse((priority, slot))) = self.heap.pop() {
+            if self.priorities[slot] == Some(priority) {
+                self.priorities[slot] = None;
+                self.len -= 1;
+                return Some((self.keys[slot].clone(), priority));
+            }
+        }
+        None
+    }
+
+    /// Priority of `key` if it is queued.
+    pub fn priority(&self, key: &K) -> Option<P> {
+        self.slots.get(key).and_then(|&slot| self.priorities[slot])
+    }
+
+    /// Number of keys queued.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<K: Eq + Hash + Clone, P: Ord + Copy> Default for PriorityQueue<K, P> {
+    fn default() -> PriorityQueue<K, P> {
+        PriorityQueue::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pops_lowest_priorities_first() {
+        let mut queue = PriorityQueue::new();
+        assert!(queue.push("b", 5));
+        assert!(queue.push("a", 3));
+        assert!(queue.push("c", 5));
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.pop(), Some(("a", 3)));
+        assert_eq!(queue.pop(), Some(("b", 5)));
+        assert_eq!(queue.pop(), Some(("c", 5)));
+        assert_eq!(queue.pop(), None);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn lowers_priority_of_queued_keys() {
+        let mut queue = PriorityQueue::new();
+        queue.push("a", 4);
+        queue.push("b", 6);
+        assert!(queue.push("b", 2));
+        assert!(!queue.push("a", 7));
+        assert_eq!((queue.len(), queue.priority(&"b")), (2, Some(2)));
+        assert_eq!(queue.pop(), Some(("b", 2)));
+        assert_eq!(queue.priority(&"b"), None);
+        assert_eq!(queue.pop(), Some(("a", 4)));
+        assert_eq!(queue.pop(), None);
+        assert!(queue.push("a", 9));
+        assert_eq!(queue.pop(), Some(("a", 9)));
+    }
+}
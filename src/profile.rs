@@ -0,0 +1,35 @@
+use std::fs::File;
+use std::io;
+
+use pprof::ProfilerGuardBuilder;
+
+use crate::error::{AocError, Result};
+use crate::input::daily_input;
+use crate::puzzle::{Part, Solver};
+
+const SAMPLING_FREQUENCY: i32 = 1000;
+
+pub fn flamegraph_path(year: u32, day: u32) -> String {
+    format!("flamegraph-{}-day{:0>2}.svg", year, day)
+}
+
+fn profiler_error(error: pprof::Error) -> AocError {
+    AocError::Io(io::Error::other(error))
+}
+
+pub fn profile(solver: &Solver, parts: &[Part], iterations: usize, output: &str) -> Result<isize> {
+    let input = daily_input(solver.year, solver.day)?;
+    let guard = ProfilerGuardBuilder::default()
+        .frequency(SAMPLING_FREQUENCY)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()
+        .map_err(profiler_error)?;
+    for _ in 0..iterations {
+        for &part in parts {
+            (solver.solve)(part, input.clone())?;
+        }
+    }
+    let report = guard.report().build().map_err(profiler_error)?;
+    report.flamegraph(File::create(output)?).map_err(profiler_error)?;
+    Ok(report.data.values().sum())
+}
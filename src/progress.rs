@@ -0,0 +1,89 @@
+use std::cell::Cell;
+use std::sync::OnceLock;
+
+pub trait Reporter: Send + Sync {
+    fn start(&self, label: String, total: u64) -> Box<dyn Task>;
+}
+
+pub trait Task {
+    fn advance(&self, amount: u64);
+
+    fn finish(&self);
+}
+
+static REPORTER: OnceLock<Box<dyn Reporter>> = OnceLock::new();
+
+pub fn set_reporter(reporter: impl Reporter + 'static) {
+    let _ = REPORTER.set(Box::new(reporter));
+}
+
+const BATCH: u64 = 1 << 16;
+
+pub struct Tracker {
+    task: Option<Box<dyn Task>>,
+    pending: Cell<u64>,
+}
+
+impl Tracker {
+    pub fn tick(&self) {
+        let Some(task) = &self.task else { return };
+        let pending = self.pending.get() + 1;
+        if pending == BATCH {
+            task.advance(pending);
+            self.pending.set(0);
+        } else {
+            self.pending.set(pending);
+        }
+    }
+}
+
+impl Drop for Tracker {
+    fn drop(&mut self) {
+        if let Some(task) = &self.task {
+            task.advance(self.pending.get());
+            task.finish();
+        }
+    }
+}
+
+pub fn track(label: impl FnOnce() -> String, total: u64) -> Tracker {
+    let task = REPORTER.get().map(|reporter| reporter.start(label(), total));
+    Tracker { task, pending: Cell::new(0) }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    struct Recorder(Rc<Cell<(u64, bool)>>);
+
+    impl Task for Recorder {
+        fn advance(&self, amount: u64) {
+            let (done, finished) = self.0.get();
+            self.0.set((done + amount, finished));
+        }
+
+        fn finish(&self) {
+            self.0.set((self.0.get().0, true));
+        }
+    }
+
+    #[test]
+    fn reports_ticks_in_batches_and_the_rest_when_dropped() {
+        let progress = Rc::new(Cell::new((0, false)));
+        let tracker = Tracker { task: Some(Box::new(Recorder(progress.clone()))), pending: Cell::new(0) };
+        (0..BATCH + 3).for_each(|_| tracker.tick());
+        assert_eq!(progress.get(), (BATCH, false));
+        drop(tracker);
+        assert_eq!(progress.get(), (BATCH + 3, true));
+    }
+
+    #[test]
+    fn ignores_ticks_without_reporter() {
+        let tracker = track(|| "seeds".to_owned(), 10);
+        (0..10).for_each(|_| tracker.tick());
+        assert_eq!(tracker.pending.get(), 0);
+    }
+}
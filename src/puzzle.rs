@@ -0,0 +1,211 @@
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+use tracing::debug;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+use crate::compare::{Comparison, Measurement};
+use crate::error::{AocError, Result};
+use crate::validate::{validate, Rule, DEFAULT_RULES};
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash, Ord, PartialOrd)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn both() -> [Part; 2] {
+        [Part::One, Part::Two]
+    }
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    pub fn from_number(number: u8) -> Option<Part> {
+        match number {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+pub const DEFAULT_YEAR: u32 = 2023;
+
+pub struct Variant<M> {
+    pub name: &'static str,
+    pub part: Part,
+    pub solve: fn(&M) -> Result<String>,
+}
+
+pub trait Puzzle {
+    const YEAR: u32 = DEFAULT_YEAR;
+
+    const DAY: u32;
+
+    const RULES: &'static [Rule] = DEFAULT_RULES;
+
+    const QUERIES: &'static [&'static str] = &[];
+
+    type Model;
+
+    fn parse(input: Vec<String>) -> Result<Self::Model>;
+
+    fn part_one(model: &Self::Model) -> Result<impl Display>;
+
+    fn part_two(model: &Self::Model) -> Result<impl Display>;
+
+    fn query(_model: &Self::Model, query: &[&str]) -> Result<String> {
+        Err(AocError::unknown_query(query))
+    }
+
+    fn variants() -> Vec<Variant<Self::Model>> {
+        Vec::new()
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Solution {
+    pub answer: String,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+fn solve<P: Puzzle>(part: Part, input: Vec<String>) -> Result<Solution> {
+    debug!(year = P::YEAR, day = P::DAY, lines = input.len(), "parsing input");
+    validate(&input, P::RULES)?;
+    let start = Instant::now();
+    let model = P::parse(input)?;
+    let parse_time = start.elapsed();
+    debug!(day = P::DAY, ?parse_time, "parsed input");
+
+    let start = Instant::now();
+    let answer = match part {
+        Part::One => P::part_one(&model)?.to_string(),
+        Part::Two => P::part_two(&model)?.to_string(),
+    };
+    let solve_time = start.elapsed();
+    debug!(day = P::DAY, %part, ?solve_time, "solved");
+    Ok(Solution { answer, parse_time, solve_time })
+}
+
+fn parse<P: Puzzle>(input: Vec<String>) -> Result<()> {
+    validate(&input, P::RULES)?;
+    P::parse(input).map(|_| ())
+}
+
+fn timed(solve: impl FnOnce() -> Result<String>) -> Result<(String, Duration)> {
+    let start = Instant::now();
+    let answer = solve()?;
+    Ok((answer, start.elapsed()))
+}
+
+fn compare<P: Puzzle>(input: Vec<String>) -> Result<Vec<Comparison>> {
+    validate(&input, P::RULES)?;
+    let model = P::parse(input)?;
+    let variants = P::variants();
+    Part::both().into_iter()
+        .filter(|&part| variants.iter().any(|variant| variant.part == part))
+        .map(|part| {
+            let mut measurements = vec![match part {
+                Part::One => Measurement::of("default", timed(|| Ok(P::part_one(&model)?.to_string()))?),
+                Part::Two => Measurement::of("default", timed(|| Ok(P::part_two(&model)?.to_string()))?),
+            }];
+            for variant in variants.iter().filter(|variant| variant.part == part) {
+                debug!(day = P::DAY, %part, variant = variant.name, "solving with variant");
+                measurements.push(Measurement::of(variant.name, timed(|| (variant.solve)(&model))?));
+            }
+            Ok(Comparison { day: P::DAY, part, measurements })
+        })
+        .collect()
+}
+
+fn incomparable(_: Vec<String>) -> Result<Vec<Comparison>> {
+    Ok(Vec::new())
+}
+
+pub type Explorer = Box<dyn Fn(&[&str]) -> Result<String>>;
+
+fn explore<P: Puzzle>(input: Vec<String>) -> Result<Explorer> where P::Model: 'static {
+    validate(&input, P::RULES)?;
+    let model = P::parse(input)?;
+    Ok(Box::new(move |query| P::query(&model, query)))
+}
+
+fn unexplorable(_: Vec<String>) -> Result<Explorer> {
+    Err(AocError::unsolvable("nothing to explore"))
+}
+
+#[derive(Copy, Clone)]
+pub struct Solver {
+    pub year: u32,
+    pub day: u32,
+    pub parse: fn(Vec<String>) -> Result<()>,
+    pub solve: fn(Part, Vec<String>) -> Result<Solution>,
+    pub explore: fn(Vec<String>) -> Result<Explorer>,
+    pub queries: &'static [&'static str],
+    pub compare: fn(Vec<String>) -> Result<Vec<Comparison>>,
+}
+
+impl Solver {
+    pub(crate) const fn of<P: Puzzle>() -> Solver where P::Model: 'static {
+        Solver { year: P::YEAR, day: P::DAY, parse: parse::<P>, solve: solve::<P>, explore: explore::<P>, queries: P::QUERIES, compare: compare::<P> }
+    }
+
+    pub fn new(year: u32, day: u32, parse: fn(Vec<String>) -> Result<()>, solve: fn(Part, Vec<String>) -> Result<Solution>) -> Solver {
+        Solver { year, day, parse, solve, explore: unexplorable, queries: &[], compare: incomparable }
+    }
+}
+
+inventory::collect!(Solver);
+
+/// Solvers registered by `#[aoc(day = N)]`, ordered by year and day.
+pub fn solvers() -> Vec<Solver> {
+    let mut solvers: Vec<Solver> = inventory::iter::<Solver>.into_iter().copied().collect();
+    solvers.sort_by_key(|solver| (solver.year, solver.day));
+    solvers
+}
+
+pub fn split_lines(input: &str) -> Vec<String> {
+    input.lines().map(String::from).collect()
+}
+
+/// Blocks of lines separated by blank lines, consecutive blank lines leaving empty blocks in between.
+pub fn split_blocks(lines: &[String]) -> Vec<Vec<String>> {
+    lines.split(|line| line.is_empty()).map(<[String]>::to_vec).collect()
+}
+
+pub fn solver(year: u32, day: u32) -> Option<Solver> {
+    solvers().into_iter().find(|solver| solver.year == year && solver.day == day)
+}
+
+pub fn solvers_of(year: u32) -> Vec<Solver> {
+    solvers().into_iter().filter(|solver| solver.year == year).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_blocks_on_blank_lines() {
+        let lines = split_lines("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50 48\n\n\nhumidity");
+        let blocks = split_blocks(&lines);
+        assert_eq!(blocks.iter().map(Vec::len).collect::<Vec<_>>(), vec![1, 3, 0, 1]);
+        assert_eq!(blocks[1][0], "seed-to-soil map:");
+    }
+}
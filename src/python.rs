@@ -0,0 +1,64 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::puzzle::{solver, split_lines, Explorer, Part, Solver, DEFAULT_YEAR};
+
+fn solver_of(day: u32) -> PyResult<Solver> {
+    solver(DEFAULT_YEAR, day).ok_or_else(|| PyValueError::new_err(format!("Day {} is not solved yet", day)))
+}
+
+#[pyfunction]
+pub fn solve(day: u32, part: u8, input: &str) -> PyResult<String> {
+    let solver = solver_of(day)?;
+    let part = Part::from_number(part).ok_or_else(|| PyValueError::new_err(format!("Part {} does not exist", part)))?;
+    let solution = (solver.solve)(part, split_lines(input)).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(solution.answer)
+}
+
+#[pyclass(unsendable)]
+pub struct Model {
+    #[pyo3(get)]
+    day: u32,
+    #[pyo3(get)]
+    queries: Vec<&'static str>,
+    explore: Explorer,
+}
+
+#[pymethods]
+impl Model {
+    pub fn query(&self, query: &str) -> PyResult<String> {
+        let query: Vec<&str> = query.split_whitespace().collect();
+        (self.explore)(&query).map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+}
+
+#[pyfunction]
+pub fn parse(day: u32, input: &str) -> PyResult<Model> {
+    let solver = solver_of(day)?;
+    let explore = (solver.explore)(split_lines(input)).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(Model { day, queries: solver.queries.to_vec(), explore })
+}
+
+#[pymodule]
+fn aoc2023(_py: Python<'_>, module: &PyModule) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(solve, module)?)?;
+    module.add_function(wrap_pyfunction!(parse, module)?)?;
+    module.add_class::<Model>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_puzzle_given_as_text() {
+        assert_eq!(solve(7, 2, include_str!("examples/2023/day07.txt")).ok(), Some("5905".to_owned()));
+    }
+
+    #[test]
+    fn answers_queries_on_parsed_puzzle() {
+        let model = parse(8, include_str!("examples/2023/day08.txt")).unwrap();
+        assert_eq!(model.query("take_step AAA L").ok(), Some("BBB".to_owned()));
+    }
+}
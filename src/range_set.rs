@@ -0,0 +1,156 @@
+use std::ops::Range;
+
+/// A set of integers stored as sorted, disjoint and non-adjacent ranges.
+#[derive(PartialEq, Eq, Debug, Clone, Default, Hash)]
+pub struct RangeSet {
+    ranges: Vec<Range<isize>>,
+}
+
+impl RangeSet {
+    pub fn new() -> RangeSet {
+        RangeSet::default()
+    }
+
+    fn normalized(mut ranges: Vec<Range<isize>>) -> RangeSet {
+        ranges.retain(|range| !range.is_empty());
+        ranges.sort_by_key(|range| range.start);
+        let mut merged: Vec<Range<isize>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        RangeSet { ranges: merged }
+    }
+
+    pub fn ranges(&self) -> &[Range<isize>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of integers in the set.
+    pub fn len(&self) -> usize {
+        self.ranges.iter().map(|range| range.len()).sum()
+    }
+
+    pub fn min(&self) -> Option<isize> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    pub fn max(&self) -> Option<isize> {
+        self.ranges.last().map(|range| range.end - 1)
+    }
+
+    pub fn contains(&self, number: isize) -> bool {
+        let index = self.ranges.partition_point(|range| range.end <= number);
+        self.ranges.get(index).is_some_and(|range| range.contains(&number))
+    }
+
+    pub fn insert(&mut self, range: Range<isize>) {
+        *self = self.union(&RangeSet::from(range));
+    }
+
+    pub fn union(&self, other: &RangeSet) -> RangeSet {
+        RangeSet::normalized(self.ranges.iter().chain(&other.ranges).cloned().collect())
+    }
+
+    pub fn intersection(&self, other: &RangeSet) -> RangeSet {
+        let (mut mine, mut theirs) = (self.ranges.iter().peekable(), other.ranges.iter().peekable());
+        let mut ranges = Vec::new();
+        while let (Some(a), Some(b)) = (mine.peek(), theirs.peek()) {
+            let overlap = a.start.max(b.start)..a.end.min(b.end);
+            if !overlap.is_empty() {
+                ranges.push(overlap);
+            }
+            if a.end < b.end { mine.next(); } else { theirs.next(); }
+        }
+        RangeSet { ranges }
+    }
+
+    /// Every integer not in the set, up to `isize::MAX` excluded.
+    pub fn complement(&self) -> RangeSet {
+        let bounds = self.ranges.iter().flat_map(|range| [range.start, range.end]);
+        let edges: Vec<isize> = [isize::MIN].into_iter().chain(bounds).chain([isize::MAX]).collect();
+        RangeSet::normalized(edges.chunks(2).map(|gap| gap[0]..gap[1]).collect())
+    }
+
+    pub fn difference(&self, other: &RangeSet) -> RangeSet {
+        self.intersection(&other.complement())
+    }
+
+    /// Splits the set into the integers below `at` and the ones from `at` upwards.
+    pub fn split_at(&self, at: isize) -> (RangeSet, RangeSet) {
+        (self.intersection(&RangeSet::from(isize::MIN..at)), self.intersection(&RangeSet::from(at..isize::MAX)))
+    }
+
+    pub fn shift(&self, offset: isize) -> RangeSet {
+        RangeSet { ranges: self.ranges.iter().map(|range| range.start + offset..range.end + offset).collect() }
+    }
+}
+
+impl From<Range<isize>> for RangeSet {
+    fn from(range: Range<isize>) -> RangeSet {
+        RangeSet::normalized(vec![range])
+    }
+}
+
+impl FromIterator<Range<isize>> for RangeSet {
+    fn from_iter<I: IntoIterator<Item=Range<isize>>>(ranges: I) -> RangeSet {
+        RangeSet::normalized(ranges.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn set(ranges: &[Range<isize>]) -> RangeSet {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn merges_overlapping_and_adjacent_ranges() {
+        assert_eq!(set(&[5..8, 1..3, 3..4, 7..10, 12..12]).ranges(), &[1..4, 5..10]);
+        assert_eq!(set(&[1..4, 5..10]).len(), 8);
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a = set(&[0..10, 20..30]);
+        let b = RangeSet::from(5..25);
+        assert_eq!(a.union(&b), RangeSet::from(0..30));
+        assert_eq!(a.intersection(&b).ranges(), &[5..10, 20..25]);
+        assert_eq!(a.difference(&b).ranges(), &[0..5, 25..30]);
+        assert_eq!(b.difference(&a), RangeSet::from(10..20));
+    }
+
+    #[test]
+    fn splits_and_shifts_sets() {
+        let (below, above) = set(&[0..10, 20..30]).split_at(25);
+        assert_eq!((&below, &above), (&set(&[0..10, 20..25]), &RangeSet::from(25..30)));
+        assert_eq!(above.shift(-20), RangeSet::from(5..10));
+        assert_eq!((below.min(), below.max()), (Some(0), Some(24)));
+    }
+
+    fn ranges() -> impl Strategy<Value=Vec<Range<isize>>> {
+        prop::collection::vec((-50..50_isize, 0..20_isize).prop_map(|(start, length)| start..start + length), 0..6)
+    }
+
+    proptest! {
+        #[test]
+        fn agrees_with_membership_of_each_integer(a in ranges(), b in ranges(), n in -60..80_isize) {
+            let (in_a, in_b) = (a.iter().any(|r| r.contains(&n)), b.iter().any(|r| r.contains(&n)));
+            let (a, b) = (set(&a), set(&b));
+            prop_assert_eq!(a.contains(n), in_a);
+            prop_assert_eq!(a.union(&b).contains(n), in_a || in_b);
+            prop_assert_eq!(a.intersection(&b).contains(n), in_a && in_b);
+            prop_assert_eq!(a.difference(&b).contains(n), in_a && !in_b);
+        }
+    }
+}
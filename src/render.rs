@@ -0,0 +1,119 @@
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    fn code(&self) -> u8 {
+        match self {
+            Color::Black => 0,
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::Magenta => 5,
+            Color::Cyan => 6,
+            Color::White => 7,
+        }
+    }
+
+    /// Name of the color in CSS and SVG.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Color::Black => "black",
+            Color::Red => "red",
+            Color::Green => "green",
+            Color::Yellow => "yellow",
+            Color::Blue => "blue",
+            Color::Magenta => "magenta",
+            Color::Cyan => "cyan",
+            Color::White => "white",
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Copy, Clone, Default)]
+pub struct Style {
+    pub foreground: Option<Color>,
+    pub background: Option<Color>,
+    pub bold: bool,
+}
+
+impl Style {
+    pub const PLAIN: Style = Style { foreground: None, background: None, bold: false };
+
+    pub fn fg(color: Color) -> Style {
+        Style { foreground: Some(color), ..Style::PLAIN }
+    }
+
+    pub fn on(self, color: Color) -> Style {
+        Style { background: Some(color), ..self }
+    }
+
+    pub fn bold(self) -> Style {
+        Style { bold: true, ..self }
+    }
+
+    fn escape(&self) -> String {
+        let codes: Vec<String> = [self.bold.then_some(1), self.foreground.map(|c| 30 + c.code()), self.background.map(|c| 40 + c.code())]
+            .into_iter()
+            .flatten()
+            .map(|code| code.to_string())
+            .collect();
+        format!("\x1b[{}m", codes.join(";"))
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+
+pub fn render_grid<R: AsRef<str>>(rows: &[R], style: impl Fn(usize, usize, char) -> Style) -> String {
+    let mut text = String::new();
+    for (y, row) in rows.iter().enumerate() {
+        let mut current = Style::PLAIN;
+        for (x, c) in row.as_ref().chars().enumerate() {
+            let cell = style(x, y, c);
+            if cell != current {
+                if current != Style::PLAIN {
+                    text += RESET;
+                }
+                if cell != Style::PLAIN {
+                    text += &cell.escape();
+                }
+                current = cell;
+            }
+            text.push(c);
+        }
+        if current != Style::PLAIN {
+            text += RESET;
+        }
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaves_plain_cells_untouched() {
+        assert_eq!(render_grid(&["..#", "#.."], |_, _, _| Style::PLAIN), "..#\n#..\n");
+    }
+
+    #[test]
+    fn colors_runs_of_cells_sharing_a_style() {
+        let rendered = render_grid(&["467..*"], |x, _, c| match c {
+            '*' => Style::fg(Color::Yellow).bold(),
+            _ if x < 3 => Style::fg(Color::Green).on(Color::Black),
+            _ => Style::PLAIN,
+        });
+        assert_eq!(rendered, "\x1b[32;40m467\x1b[0m..\x1b[1;33m*\x1b[0m\n");
+    }
+}
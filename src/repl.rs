@@ -0,0 +1,54 @@
+use std::io;
+use std::io::{BufRead, Write};
+
+use crate::error::Result;
+use crate::puzzle::Solver;
+
+const PROMPT: &str = "> ";
+
+pub fn repl(solver: &Solver, input: Vec<String>, reader: impl BufRead, mut writer: impl Write) -> Result<()> {
+    let explore = (solver.explore)(input)?;
+    writeln!(writer, "Day {:0>2} parsed, type help for the available queries", solver.day)?;
+    write!(writer, "{}", PROMPT)?;
+    writer.flush()?;
+    for line in reader.lines() {
+        let line = line?;
+        let query: Vec<&str> = line.split_whitespace().collect();
+        match query.as_slice() {
+            [] => {}
+            ["quit"] | ["exit"] => break,
+            ["help"] => solver.queries.iter().try_for_each(|query| writeln!(writer, "  {}", query))?,
+            _ => match explore(&query) {
+                Ok(answer) => writeln!(writer, "{}", answer)?,
+                Err(error) => writeln!(writer, "{}", error)?,
+            },
+        }
+        write!(writer, "{}", PROMPT)?;
+        writer.flush()?;
+    }
+    Ok(())
+}
+
+pub fn repl_on_terminal(solver: &Solver, input: Vec<String>) -> Result<()> {
+    repl(solver, input, io::stdin().lock(), io::stdout())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::daily_example;
+    use crate::puzzle::{solver, DEFAULT_YEAR};
+
+    use super::*;
+
+    #[test]
+    fn answers_queries_on_parsed_model() {
+        let solver = solver(DEFAULT_YEAR, 8).unwrap();
+        let mut output = Vec::new();
+        repl(&solver, daily_example(DEFAULT_YEAR, 8).unwrap(), "take_step AAA L\n\nlook around\nquit\n".as_bytes(), &mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "\
+Day 08 parsed, type help for the available queries
+> BBB
+> > cannot answer: unknown query \"look around\"
+> ");
+    }
+}
@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+use crate::bench::format_duration;
+use crate::error::AocError;
+use crate::input::InputSource;
+use crate::puzzle::{Part, Solver};
+use crate::runner::{run_all, PartRun, Repetitions, DEFAULT_BUDGET};
+
+pub fn answer_of(runs: &[PartRun], part: Part) -> String {
+    match runs.iter().find(|run| run.part == part).map(|run| &run.solution) {
+        Some(Ok(solution)) => solution.answer.clone(),
+        Some(Err(AocError::OverBudget(_))) => "over budget".to_owned(),
+        Some(Err(_)) => "failed".to_owned(),
+        None => String::new(),
+    }
+}
+
+pub fn runtime_of(runs: &[PartRun]) -> Duration {
+    runs.iter().map(PartRun::runtime).sum()
+}
+
+pub fn results_table(runs: &[PartRun]) -> String {
+    let mut table = String::from("| Day | Part 1 | Part 2 | Runtime |\n|----:|-------:|-------:|--------:|\n");
+    for runs in runs.chunk_by(|a, b| a.day == b.day) {
+        table += &format!("| {} | {} | {} | {} |\n", runs[0].day, answer_of(runs, Part::One), answer_of(runs, Part::Two),
+                          format_duration(runtime_of(runs)));
+    }
+    table
+}
+
+pub fn generate_results(solvers: &[Solver]) -> String {
+    results_table(&run_all(solvers, &InputSource::Daily, false, DEFAULT_BUDGET, Repetitions::ONCE))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::puzzle::Solution;
+
+    use super::*;
+
+    fn part_run(day: u32, part: Part, answer: &str, millis: u64) -> PartRun {
+        let solution = Solution { answer: answer.to_owned(), parse_time: Duration::ZERO, solve_time: Duration::from_millis(millis) };
+        PartRun { day, part, solution: Ok(solution), allocations: None, timings: None }
+    }
+
+    #[test]
+    fn tabulates_answers_and_runtime_per_day() {
+        let runs = vec![
+            part_run(7, Part::One, "6440", 2),
+            part_run(7, Part::Two, "5905", 3),
+            part_run(8, Part::One, "6", 1),
+        ];
+        assert_eq!(results_table(&runs), "\
+| Day | Part 1 | Part 2 | Runtime |
+|----:|-------:|-------:|--------:|
+| 7 | 6440 | 5905 | 5.0ms |
+| 8 | 6 |  | 1.0ms |
+");
+    }
+
+    #[test]
+    fn tabulates_failed_parts() {
+        let runs = vec![
+            part_run(5, Part::One, "35", 2),
+            PartRun { day: 5, part: Part::Two, solution: Err(AocError::OverBudget(DEFAULT_BUDGET)), allocations: None, timings: None },
+        ];
+        assert!(results_table(&runs).ends_with("| 5 | 35 | over budget | 2.0ms |\n"));
+    }
+}
@@ -0,0 +1,46 @@
+use itertools::Itertools;
+
+/// Runs of equal consecutive items, each with its length.
+pub fn encode<T: PartialEq>(items: impl IntoIterator<Item=T>) -> Vec<(T, usize)> {
+    items.into_iter().dedup_with_count().map(|(length, item)| (item, length)).collect()
+}
+
+/// Items repeated as many times as their runs are long.
+pub fn decode<T: Clone>(runs: impl IntoIterator<Item=(T, usize)>) -> impl Iterator<Item=T> {
+    runs.into_iter().flat_map(|(item, length)| std::iter::repeat_n(item, length))
+}
+
+/// Lengths of the runs of `item`, such as the sizes of groups of damaged springs in a row.
+pub fn run_lengths_of<T: PartialEq>(items: impl IntoIterator<Item=T>, item: &T) -> Vec<usize> {
+    encode(items).into_iter().filter(|(run, _)| run == item).map(|(_, length)| length).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn encodes_runs_of_equal_items() {
+        assert_eq!(encode("#.#.###".chars()), vec![('#', 1), ('.', 1), ('#', 1), ('.', 1), ('#', 3)]);
+        assert_eq!(encode(Vec::<u8>::new()), vec![]);
+        assert_eq!(decode([('O', 2), ('.', 3)]).collect::<String>(), "OO...");
+    }
+
+    #[test]
+    fn measures_runs_of_an_item() {
+        assert_eq!(run_lengths_of(".###.##.#...".chars(), &'#'), vec![3, 2, 1]);
+        assert_eq!(run_lengths_of("....".chars(), &'#'), Vec::<usize>::new());
+    }
+
+    proptest! {
+        #[test]
+        fn decodes_what_it_encodes(items in prop::collection::vec(0..3_u8, 0..50)) {
+            let runs = encode(items.iter().copied());
+            prop_assert!(runs.windows(2).all(|pair| pair[0].0 != pair[1].0));
+            prop_assert!(runs.iter().all(|&(_, length)| length > 0));
+            prop_assert_eq!(decode(runs).collect::<Vec<_>>(), items);
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use std::io;
+use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::thread;
+use std::time::Duration;
+
+use rayon::prelude::*;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::bench::{format_duration, Timings};
+use crate::error::{AocError, Result};
+use crate::input::InputSource;
+use crate::memory::{measure, Allocations};
+use crate::puzzle::{Part, Solution, Solver};
+
+pub const DEFAULT_BUDGET: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Copy, Clone)]
+pub struct Repetitions {
+    pub warmup: usize,
+    pub runs: usize,
+}
+
+impl Repetitions {
+    pub const ONCE: Repetitions = Repetitions { warmup: 0, runs: 1 };
+
+    pub fn new(warmup: usize, runs: usize) -> Repetitions {
+        Repetitions { warmup, runs: runs.max(1) }
+    }
+}
+
+pub struct PartRun {
+    pub day: u32,
+    pub part: Part,
+    pub solution: Result<Solution>,
+    pub allocations: Option<Allocations>,
+    pub timings: Option<Timings>,
+}
+
+#[derive(Serialize)]
+struct Record<'a> {
+    day: u32,
+    part: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parse_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    solve_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    solve_std_dev_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    runs: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_micros() as f64 / 1e3
+}
+
+impl PartRun {
+    fn record(&self) -> Record<'_> {
+        let solution = self.solution.as_ref().ok();
+        Record {
+            day: self.day,
+            part: self.part.number(),
+            answer: solution.map(|s| s.answer.as_str()),
+            parse_ms: solution.map(|s| millis(s.parse_time)),
+            solve_ms: solution.map(|s| self.timings.as_ref().map_or(millis(s.solve_time), |t| millis(t.median()))),
+            solve_std_dev_ms: self.timings.as_ref().map(|t| millis(t.std_dev())),
+            runs: self.timings.as_ref().map(Timings::count),
+            error: self.solution.as_ref().err().map(AocError::to_string),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.record()).unwrap()
+    }
+
+    pub fn to_text(&self) -> String {
+        match (&self.solution, &self.timings) {
+            (Ok(solution), Some(timings)) => format!("Day {:0>2} part {}: {} (parsed in {}, solved in {} ± {} over {} runs)", self.day, self.part,
+                                                     solution.answer, format_duration(solution.parse_time), format_duration(timings.median()),
+                                                     format_duration(timings.std_dev()), timings.count()),
+            (Ok(solution), None) => format!("Day {:0>2} part {}: {} (parsed in {}, solved in {})", self.day, self.part, solution.answer,
+                                            format_duration(solution.parse_time), format_duration(solution.solve_time)),
+            (Err(error), _) => format!("Day {:0>2} part {}: {}", self.day, self.part, error),
+        }
+    }
+
+    pub fn runtime(&self) -> Duration {
+        self.solution.as_ref().map_or(Duration::ZERO, |s| s.parse_time + s.solve_time)
+    }
+}
+
+/// Solves `part` on a worker thread, giving up on it after `budget`. Solvers cannot be interrupted, so the worker of a
+/// part over budget keeps running in the background until it finishes, its result being dropped.
+fn solve_within(solver: &Solver, part: Part, input: Vec<String>, budget: Duration) -> (Result<Solution>, Option<Allocations>) {
+    let (sender, receiver) = mpsc::channel();
+    let solve = solver.solve;
+    thread::spawn(move || sender.send(measure(|| solve(part, input))));
+    match receiver.recv_timeout(budget) {
+        Ok(measured) => measured,
+        Err(RecvTimeoutError::Timeout) => (Err(AocError::OverBudget(budget)), None),
+        Err(RecvTimeoutError::Disconnected) => (Err(AocError::unsolvable("solver crashed")), None),
+    }
+}
+
+fn run_part(solver: &Solver, part: Part, input: &[String], budget: Duration, repetitions: Repetitions) -> PartRun {
+    let mut solve_times = Vec::new();
+    let mut last = None;
+    for iteration in 0..repetitions.warmup + repetitions.runs {
+        let (solution, allocations) = solve_within(solver, part, input.to_vec(), budget);
+        // A part over budget is not run again, which would leave yet another worker running in the background.
+        let solution = match solution {
+            Ok(solution) => solution,
+            Err(error) => return PartRun { day: solver.day, part, solution: Err(error), allocations, timings: None },
+        };
+        if iteration >= repetitions.warmup {
+            solve_times.push(solution.solve_time);
+        }
+        last = Some((solution, allocations));
+    }
+    let (solution, allocations) = last.expect("parts are run at least once");
+    let timings = (solve_times.len() > 1).then(|| Timings::new(solve_times));
+    PartRun { day: solver.day, part, solution: Ok(solution), allocations, timings }
+}
+
+pub fn run_repeatedly(solver: &Solver, input: &[String], budget: Duration, repetitions: Repetitions) -> Vec<PartRun> {
+    Part::both().into_iter()
+        .map(|part| run_part(solver, part, input, budget, repetitions))
+        .collect()
+}
+
+pub fn run(solver: &Solver, input: &[String], budget: Duration) -> Vec<PartRun> {
+    run_repeatedly(solver, input, budget, Repetitions::ONCE)
+}
+
+/// Both parts of a day failing because its input could not be read.
+fn unreadable_input(solver: &Solver, error: AocError) -> Vec<PartRun> {
+    let reason = error.to_string();
+    Part::both().into_iter()
+        .map(|part| PartRun { day: solver.day, part, solution: Err(AocError::Io(io::Error::other(reason.clone()))), allocations: None, timings: None })
+        .collect()
+}
+
+/// Runs every day, a day whose input cannot be read failing on its own while the others still run.
+pub fn run_all(solvers: &[Solver], source: &InputSource, parallel: bool, budget: Duration, repetitions: Repetitions) -> Vec<PartRun> {
+    debug!(days = solvers.len(), parallel, ?source, ?budget, ?repetitions, "running solvers");
+    let run_day = |solver: &Solver| match source.read(solver.year, solver.day) {
+        Ok(input) => run_repeatedly(solver, &input, budget, repetitions),
+        Err(error) => unreadable_input(solver, error),
+    };
+    let days: Vec<Vec<PartRun>> = if parallel {
+        solvers.par_iter().map(run_day).collect()
+    } else {
+        solvers.iter().map(run_day).collect()
+    };
+    days.into_iter().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use crate::puzzle::{solver, DEFAULT_YEAR};
+    use crate::summary::Summary;
+
+    use super::*;
+
+    #[test]
+    fn serializes_run_as_json_record() {
+        let solution = Solution { answer: "6440".to_owned(), parse_time: Duration::from_micros(1500), solve_time: Duration::from_millis(2) };
+        let run = PartRun { day: 7, part: Part::One, solution: Ok(solution), allocations: None, timings: None };
+        assert_eq!(run.to_json(), r#"{"day":7,"part":1,"answer":"6440","parse_ms":1.5,"solve_ms":2.0}"#);
+    }
+
+    #[test]
+    fn reports_median_and_deviation_of_repeated_runs() {
+        let solution = Solution { answer: "6440".to_owned(), parse_time: Duration::from_micros(1500), solve_time: Duration::from_millis(2) };
+        let timings = Timings::new(vec![Duration::from_millis(3), Duration::from_millis(1), Duration::from_millis(2)]);
+        let run = PartRun { day: 7, part: Part::One, solution: Ok(solution), allocations: None, timings: Some(timings) };
+        assert_eq!(run.to_text(), "Day 07 part 1: 6440 (parsed in 1.5ms, solved in 2.0ms ± 816.5µs over 3 runs)");
+        assert_eq!(run.to_json(), r#"{"day":7,"part":1,"answer":"6440","parse_ms":1.5,"solve_ms":2.0,"solve_std_dev_ms":0.816,"runs":3}"#);
+    }
+
+    #[test]
+    fn serializes_failed_run_with_its_error() {
+        let run = PartRun { day: 5, part: Part::Two, solution: Err(AocError::OverBudget(Duration::from_secs(15))), allocations: None, timings: None };
+        assert_eq!(run.to_json(), r#"{"day":5,"part":2,"error":"over time budget of 15s"}"#);
+    }
+
+    fn parse_nothing(_: Vec<String>) -> Result<()> {
+        Ok(())
+    }
+
+    fn solve_slowly(_: Part, _: Vec<String>) -> Result<Solution> {
+        thread::sleep(Duration::from_secs(1));
+        Ok(Solution { answer: "42".to_owned(), parse_time: Duration::ZERO, solve_time: Duration::from_secs(1) })
+    }
+
+    #[test]
+    fn reports_parts_over_budget() {
+        let solver = Solver::new(DEFAULT_YEAR, 25, parse_nothing, solve_slowly);
+        let runs = run(&solver, &[], Duration::from_millis(10));
+        assert_eq!(runs.iter().map(|r| r.to_text()).collect::<Vec<_>>(), vec![
+            "Day 25 part 1: over time budget of 10ms",
+            "Day 25 part 2: over time budget of 10ms",
+        ]);
+    }
+
+    static SLOW_SOLVES: AtomicUsize = AtomicUsize::new(0);
+
+    fn solve_slowly_counting(part: Part, input: Vec<String>) -> Result<Solution> {
+        SLOW_SOLVES.fetch_add(1, Ordering::SeqCst);
+        solve_slowly(part, input)
+    }
+
+    #[test]
+    fn does_not_repeat_parts_over_budget() {
+        let solver = Solver::new(DEFAULT_YEAR, 25, parse_nothing, solve_slowly_counting);
+        let runs = run_repeatedly(&solver, &[], Duration::from_millis(10), Repetitions::new(2, 5));
+        assert!(runs.iter().all(|run| matches!(run.solution, Err(AocError::OverBudget(_)))));
+        assert_eq!(SLOW_SOLVES.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn fails_days_whose_input_cannot_be_read_without_stopping_the_others() {
+        let solvers = [Solver::new(DEFAULT_YEAR, 24, parse_nothing, solve_slowly), Solver::new(DEFAULT_YEAR, 25, parse_nothing, solve_slowly)];
+        let source = InputSource::File("missing/input.txt".to_owned());
+        let runs = run_all(&solvers, &source, true, DEFAULT_BUDGET, Repetitions::ONCE);
+        assert_eq!(runs.iter().map(|run| (run.day, run.part)).collect::<Vec<_>>(), vec![(24, Part::One), (24, Part::Two), (25, Part::One), (25, Part::Two)]);
+        assert!(runs.iter().all(|run| run.solution.is_err()));
+        assert_eq!(Summary::of(&runs, Duration::ZERO).failures.len(), 4);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "personal-inputs"), ignore = "requires personal puzzle inputs")]
+    fn runs_days_in_parallel_keeping_registry_order() {
+        let solvers = [solver(DEFAULT_YEAR, 1).unwrap(), solver(DEFAULT_YEAR, 7).unwrap()];
+        let answers = |runs: Vec<PartRun>| runs.into_iter().map(|r| (r.day, r.part, r.solution.unwrap().answer)).collect::<Vec<_>>();
+        let source = InputSource::Daily;
+        assert_eq!(answers(run_all(&solvers, &source, true, DEFAULT_BUDGET, Repetitions::ONCE)), answers(run_all(&solvers, &source, false, DEFAULT_BUDGET, Repetitions::ONCE)));
+    }
+}
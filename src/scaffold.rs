@@ -0,0 +1,122 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::input::{example_path, input_path};
+use crate::puzzle::DEFAULT_YEAR;
+
+const LIB: &str = "src/lib.rs";
+
+fn module_name(day: u32) -> String {
+    format!("day{:0>2}", day)
+}
+
+fn puzzle_name(day: u32) -> String {
+    format!("Day{:0>2}", day)
+}
+
+fn registration(year: u32, day: u32) -> String {
+    match year {
+        DEFAULT_YEAR => format!("day = {}", day),
+        _ => format!("year = {}, day = {}", year, day),
+    }
+}
+
+fn skeleton(year: u32, day: u32) -> String {
+    let name = puzzle_name(day);
+    let registration = registration(year, day);
+    format!(r#"use std::fmt::Display;
+
+use aoc2023_macros::aoc;
+
+use crate::error::Result;
+use crate::puzzle::Puzzle;
+
+pub struct {name};
+
+#[aoc({registration})]
+impl Puzzle for {name} {{
+    type Model = Vec<String>;
+
+    fn parse(input: Vec<String>) -> Result<Self::Model> {{
+        Ok(input)
+    }}
+
+    fn part_one(_model: &Self::Model) -> Result<impl Display> {{
+        Ok(0)
+    }}
+
+    fn part_two(_model: &Self::Model) -> Result<impl Display> {{
+        Ok(0)
+    }}
+}}
+
+#[cfg(test)]
+mod test {{
+    use crate::input::daily_example;
+
+    use super::*;
+
+    #[test]
+    fn solves_example_part_one() {{
+        let model = {name}::parse(daily_example({year}, {day}).unwrap()).unwrap();
+        assert_eq!({name}::part_one(&model).unwrap().to_string(), "0")
+    }}
+}}
+"#)
+}
+
+fn declare_module(lib: &str, day: u32) -> String {
+    let declaration = format!("mod {};", module_name(day));
+    let mut lines: Vec<&str> = lib.lines().collect();
+    let position = lines.iter().rposition(|line| line.starts_with("mod day")).map_or(lines.len(), |i| i + 1);
+    lines.insert(position, &declaration);
+    lines.join("\n") + "\n"
+}
+
+pub fn scaffold(year: u32, day: u32) -> io::Result<Vec<String>> {
+    let source = format!("src/{}.rs", module_name(day));
+    if Path::new(&source).exists() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} already exists", source)));
+    }
+    let example = example_path(year, day);
+    let input = input_path(year, day);
+
+    fs::write(&source, skeleton(year, day))?;
+    fs::write(LIB, declare_module(&fs::read_to_string(LIB)?, day))?;
+    for file in [&example, &input] {
+        if !Path::new(file).exists() {
+            if let Some(directory) = Path::new(file).parent() {
+                fs::create_dir_all(directory)?;
+            }
+            fs::write(file, "")?;
+        }
+    }
+    Ok(vec![source, example, input])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn declares_module_after_last_day() {
+        let lib = "pub mod input;\nmod day01;\nmod day08;\n";
+        assert_eq!(declare_module(lib, 9), "pub mod input;\nmod day01;\nmod day08;\nmod day09;\n");
+    }
+
+    #[test]
+    fn generates_puzzle_skeleton() {
+        let skeleton = skeleton(DEFAULT_YEAR, 9);
+        assert!(skeleton.contains("pub struct Day09;"));
+        assert!(skeleton.contains("#[aoc(day = 9)]"));
+        assert!(skeleton.contains("daily_example(2023, 9)"));
+    }
+
+    #[test]
+    fn registers_skeleton_of_another_year() {
+        let skeleton = skeleton(2024, 9);
+        assert!(skeleton.contains("#[aoc(year = 2024, day = 9)]"));
+        assert!(skeleton.contains("daily_example(2024, 9)"));
+    }
+}
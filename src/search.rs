@@ -0,0 +1,120 @@
+use std::hash::Hash;
+
+use crate::graph;
+
+/// A search problem, which gets the searches of the [`graph`] module by describing its states.
+pub trait Problem {
+    type State: Eq + Hash + Clone;
+
+    fn start(&self) -> Self::State;
+
+    fn neighbors(&self, state: &Self::State) -> Vec<Self::State>;
+
+    fn is_goal(&self, state: &Self::State) -> bool;
+
+    /// Cost of moving from a state to one of its neighbors.
+    fn cost(&self, _from: &Self::State, _to: &Self::State) -> usize {
+        1
+    }
+
+    /// Estimate of the cost left to reach a goal from `state`, which must never exceed the actual cost.
+    fn heuristic(&self, _state: &Self::State) -> usize {
+        0
+    }
+
+    /// Path with the fewest moves from the start to a goal.
+    fn bfs(&self) -> Option<Vec<Self::State>> {
+        graph::bfs(self.start(), |state| self.neighbors(state), |state| self.is_goal(state))
+    }
+
+    /// First goal met exploring states depth-first from the start.
+    fn dfs(&self) -> Option<Self::State> {
+        graph::dfs_find(self.start(), |state| self.neighbors(state), |state| self.is_goal(state))
+    }
+
+    /// Cheapest path from the start to a goal, with its cost.
+    fn dijkstra(&self) -> Option<(Vec<Self::State>, usize)> {
+        graph::dijkstra(self.start(), |state| self.weighted_neighbors(state), |state| self.is_goal(state))
+    }
+
+    /// Cheapest path from the start to a goal, with its cost, guided by the heuristic.
+    fn astar(&self) -> Option<(Vec<Self::State>, usize)> {
+        graph::astar(self.start(), |state| self.weighted_neighbors(state), |state| self.heuristic(state), |state| self.is_goal(state))
+    }
+
+    /// Neighbors of `state` with the cost of moving to each.
+    fn weighted_neighbors(&self, state: &Self::State) -> Vec<(Self::State, usize)> {
+        self.neighbors(state).into_iter().map(|next| {
+            let cost = self.cost(state, &next);
+            (next, cost)
+        }).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::vec2::Vec2;
+
+    use super::*;
+
+    /// Walking from the top left to the bottom right of a city, each block costing its digit to enter.
+    struct City(Vec<Vec<usize>>);
+
+    impl Problem for City {
+        type State = Vec2;
+
+        fn start(&self) -> Vec2 {
+            Vec2::ZERO
+        }
+
+        fn neighbors(&self, state: &Vec2) -> Vec<Vec2> {
+            state.neighbors().filter(|next| (0..3).contains(&next.x) && (0..3).contains(&next.y)).collect()
+        }
+
+        fn is_goal(&self, state: &Vec2) -> bool {
+            *state == Vec2::new(2, 2)
+        }
+
+        fn cost(&self, _from: &Vec2, to: &Vec2) -> usize {
+            self.0[to.y as usize][to.x as usize]
+        }
+
+        fn heuristic(&self, state: &Vec2) -> usize {
+            (*state - Vec2::new(2, 2)).manhattan_length()
+        }
+    }
+
+    #[test]
+    fn searches_with_provided_algorithms() {
+        let city = City(vec![vec![1, 9, 9], vec![1, 1, 9], vec![9, 1, 1]]);
+        assert_eq!(city.bfs().map(|path| path.len()), Some(5));
+        assert_eq!(city.dfs(), Some(Vec2::new(2, 2)));
+        let cheapest = Some((vec![Vec2::new(0, 0), Vec2::new(0, 1), Vec2::new(1, 1), Vec2::new(1, 2), Vec2::new(2, 2)], 4));
+        assert_eq!(city.dijkstra(), cheapest);
+        assert_eq!(city.astar(), cheapest);
+    }
+
+    /// Counting up from zero without end.
+    struct Count(u64);
+
+    impl Problem for Count {
+        type State = u64;
+
+        fn start(&self) -> u64 {
+            0
+        }
+
+        fn neighbors(&self, state: &u64) -> Vec<u64> {
+            vec![state + 1]
+        }
+
+        fn is_goal(&self, state: &u64) -> bool {
+            *state == self.0
+        }
+    }
+
+    #[test]
+    fn stops_depth_first_search_at_the_first_goal() {
+        assert_eq!(Count(3).dfs(), Some(3));
+    }
+}
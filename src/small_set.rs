@@ -0,0 +1,124 @@
+/// A set of small non-negative integers, stored as one bit per integer up to the largest one.
+#[derive(PartialEq, Eq, Debug, Clone, Default, Hash)]
+pub struct SmallSet {
+    /// Bits of the integers, without trailing empty words so that equal sets compare equal.
+    words: Vec<u64>,
+}
+
+impl SmallSet {
+    pub fn new() -> SmallSet {
+        SmallSet::default()
+    }
+
+    fn trimmed(mut words: Vec<u64>) -> SmallSet {
+        while words.last() == Some(&0) {
+            words.pop();
+        }
+        SmallSet { words }
+    }
+
+    /// Adds `n`, telling whether it was missing.
+    pub fn insert(&mut self, n: usize) -> bool {
+        let (word, bit) = (n / 64, 1 << (n % 64));
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        let missing = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        missing
+    }
+
+    /// Removes `n`, telling whether it was there.
+    pub fn remove(&mut self, n: usize) -> bool {
+        let present = self.contains(n);
+        if present {
+            self.words[n / 64] &= !(1 << (n % 64));
+            *self = SmallSet::trimmed(std::mem::take(&mut self.words));
+        }
+        present
+    }
+
+    pub fn contains(&self, n: usize) -> bool {
+        self.words.get(n / 64).is_some_and(|word| word & (1 << (n % 64)) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn union(&self, other: &SmallSet) -> SmallSet {
+        let (longer, shorter) = if self.words.len() >= other.words.len() { (self, other) } else { (other, self) };
+        let mut words = longer.words.clone();
+        words.iter_mut().zip(&shorter.words).for_each(|(word, other)| *word |= other);
+        SmallSet { words }
+    }
+
+    pub fn intersection(&self, other: &SmallSet) -> SmallSet {
+        SmallSet::trimmed(self.words.iter().zip(&other.words).map(|(word, other)| word & other).collect())
+    }
+
+    pub fn difference(&self, other: &SmallSet) -> SmallSet {
+        let others = other.words.iter().chain(std::iter::repeat(&0));
+        SmallSet::trimmed(self.words.iter().zip(others).map(|(word, other)| word & !other).collect())
+    }
+
+    /// Integers of the set in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item=usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(index, &word)| {
+            (0..64).filter(move |bit| word & (1 << bit) != 0).map(move |bit| index * 64 + bit)
+        })
+    }
+}
+
+impl FromIterator<usize> for SmallSet {
+    fn from_iter<I: IntoIterator<Item=usize>>(numbers: I) -> SmallSet {
+        let mut set = SmallSet::new();
+        numbers.into_iter().for_each(|n| { set.insert(n); });
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn tracks_membership() {
+        let mut set = SmallSet::new();
+        assert!(set.insert(41));
+        assert!(!set.insert(41));
+        assert!(set.insert(130));
+        assert!(set.contains(130) && !set.contains(42) && !set.contains(10_000));
+        assert_eq!(set.len(), 2);
+        assert!(set.remove(130));
+        assert_eq!(set, SmallSet::from_iter([41]));
+    }
+
+    #[test]
+    fn combines_sets() {
+        let winning = SmallSet::from_iter([41, 48, 83, 86, 17]);
+        let own = SmallSet::from_iter([83, 86, 6, 31, 17, 9, 48, 53]);
+        assert_eq!(winning.intersection(&own).iter().collect::<Vec<_>>(), vec![17, 48, 83, 86]);
+        assert_eq!(winning.difference(&own).iter().collect::<Vec<_>>(), vec![41]);
+        assert_eq!(winning.union(&own).len(), 9);
+        assert!(SmallSet::from_iter([200]).intersection(&SmallSet::from_iter([1])).is_empty());
+    }
+
+    proptest! {
+        #[test]
+        fn agrees_with_membership_of_each_integer(a in prop::collection::vec(0..300_usize, 0..20), b in prop::collection::vec(0..300_usize, 0..20), n in 0..300_usize) {
+            let (in_a, in_b) = (a.contains(&n), b.contains(&n));
+            let (a, b) = (SmallSet::from_iter(a), SmallSet::from_iter(b));
+            prop_assert_eq!(a.union(&b).contains(n), in_a || in_b);
+            prop_assert_eq!(a.intersection(&b).contains(n), in_a && in_b);
+            prop_assert_eq!(a.difference(&b).contains(n), in_a && !in_b);
+            prop_assert_eq!(a.union(&b), b.union(&a));
+        }
+    }
+}
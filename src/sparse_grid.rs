@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use crate::grid::Grid;
+use crate::vec2::Vec2;
+
+/// Cells at arbitrary positions of an unbounded plane, only occupied ones being stored.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Vec2, T>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid { cells: HashMap::new() }
+    }
+
+    /// Cells of `grid` that satisfy `keep`, at the same positions.
+    pub fn from_grid(grid: &Grid<T>, keep: impl Fn(&T) -> bool) -> SparseGrid<T> where T: Clone {
+        grid.cells()
+            .filter(|(_, cell)| keep(cell))
+            .map(|((x, y), cell)| (Vec2::new(x as isize, y as isize), cell.clone()))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, position: Vec2) -> bool {
+        self.cells.contains_key(&position)
+    }
+
+    pub fn get(&self, position: Vec2) -> Option<&T> {
+        self.cells.get(&position)
+    }
+
+    pub fn get_mut(&mut self, position: Vec2) -> Option<&mut T> {
+        self.cells.get_mut(&position)
+    }
+
+    /// Occupies `position` with `value`, returning the value it replaces.
+    pub fn insert(&mut self, position: Vec2, value: T) -> Option<T> {
+        self.cells.insert(position, value)
+    }
+
+    pub fn remove(&mut self, position: Vec2) -> Option<T> {
+        self.cells.remove(&position)
+    }
+
+    /// Occupied cells with their position, in no particular order.
+    pub fn cells(&self) -> impl Iterator<Item=(Vec2, &T)> {
+        self.cells.iter().map(|(&position, cell)| (position, cell))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item=Vec2> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// Smallest and largest corners of the box enclosing every occupied cell, if any.
+    pub fn bounds(&self) -> Option<(Vec2, Vec2)> {
+        self.cells.keys().fold(None, |bounds, &p| match bounds {
+            None => Some((p, p)),
+            Some((min, max)) => Some((Vec2::new(min.x.min(p.x), min.y.min(p.y)), Vec2::new(max.x.max(p.x), max.y.max(p.y)))),
+        })
+    }
+
+    /// Occupied cells above, right, below and left of `position`.
+    pub fn neighbors(&self, position: Vec2) -> impl Iterator<Item=(Vec2, &T)> {
+        position.neighbors().filter_map(|next| self.get(next).map(|cell| (next, cell)))
+    }
+
+    /// Occupied cells among the eight around `position`, diagonals included.
+    pub fn surroundings(&self, position: Vec2) -> impl Iterator<Item=(Vec2, &T)> {
+        position.surroundings().filter_map(|next| self.get(next).map(|cell| (next, cell)))
+    }
+
+    /// Dense grid covering the bounds of the occupied cells, with `empty` in place of missing ones, along with the
+    /// position of its top left corner.
+    pub fn to_grid(&self, empty: T) -> Option<(Vec2, Grid<T>)> where T: Clone {
+        let (min, max) = self.bounds()?;
+        let width = (max.x - min.x + 1) as usize;
+        let cells = (min.y..=max.y)
+            .flat_map(|y| (min.x..=max.x).map(move |x| Vec2::new(x, y)))
+            .map(|position| self.get(position).unwrap_or(&empty).clone())
+            .collect();
+        Some((min, Grid::new(width, cells)))
+    }
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> SparseGrid<T> {
+        SparseGrid::new()
+    }
+}
+
+impl<T> FromIterator<(Vec2, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item=(Vec2, T)>>(cells: I) -> SparseGrid<T> {
+        SparseGrid { cells: cells.into_iter().collect() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn galaxies() -> SparseGrid<char> {
+        let grid = Grid::of_chars(&["#..".to_owned(), "..#".to_owned()]).unwrap();
+        SparseGrid::from_grid(&grid, |&c| c == '#')
+    }
+
+    #[test]
+    fn keeps_only_occupied_cells() {
+        let mut galaxies = galaxies();
+        assert_eq!(galaxies.len(), 2);
+        assert!(galaxies.contains(Vec2::new(2, 1)));
+        galaxies.insert(Vec2::new(-1_000_000, 5), '#');
+        assert_eq!(galaxies.bounds(), Some((Vec2::new(-1_000_000, 0), Vec2::new(2, 5))));
+        assert_eq!(galaxies.remove(Vec2::new(0, 0)), Some('#'));
+        assert_eq!(SparseGrid::<char>::new().bounds(), None);
+    }
+
+    #[test]
+    fn finds_occupied_neighbors() {
+        let mut galaxies = galaxies();
+        galaxies.insert(Vec2::new(1, 0), '#');
+        assert_eq!(galaxies.neighbors(Vec2::new(0, 0)).collect::<Vec<_>>(), vec![(Vec2::new(1, 0), &'#')]);
+        assert_eq!(galaxies.surroundings(Vec2::new(1, 1)).count(), 3);
+    }
+
+    #[test]
+    fn converts_back_to_a_dense_grid() {
+        let (corner, grid) = galaxies().to_grid('.').unwrap();
+        assert_eq!((corner, grid.to_string()), (Vec2::ZERO, "#..\n..#\n".to_owned()));
+    }
+}
@@ -0,0 +1,123 @@
+use std::fs::{OpenOptions, read_to_string};
+use std::io;
+use std::io::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use regex::Regex;
+
+use crate::fetch::post_answer;
+
+const SUBMISSIONS: &str = "submissions.tsv";
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    RateLimited(String),
+    AlreadySolved,
+    Unknown,
+}
+
+impl Outcome {
+    fn parse(response: &str) -> Outcome {
+        if response.contains("That's the right answer") {
+            Outcome::Correct
+        } else if response.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if response.contains("your answer is too low") {
+            Outcome::TooLow
+        } else if response.contains("That's not the right answer") {
+            Outcome::Incorrect
+        } else if response.contains("You gave an answer too recently") {
+            let re = Regex::new(r"You have (?<wait>[^.]+) left to wait").unwrap();
+            let wait = re.captures(response).map(|c| c["wait"].to_owned()).unwrap_or_default();
+            Outcome::RateLimited(wait)
+        } else if response.contains("You don't seem to be solving the right level") {
+            Outcome::AlreadySolved
+        } else {
+            Outcome::Unknown
+        }
+    }
+
+    fn label(&self) -> &str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too high",
+            Outcome::TooLow => "too low",
+            Outcome::Incorrect => "incorrect",
+            Outcome::RateLimited(_) => "rate limited",
+            Outcome::AlreadySolved => "already solved",
+            Outcome::Unknown => "unknown",
+        }
+    }
+
+    fn from_label(label: &str) -> Option<Outcome> {
+        match label {
+            "correct" => Some(Outcome::Correct),
+            "too high" => Some(Outcome::TooHigh),
+            "too low" => Some(Outcome::TooLow),
+            "incorrect" => Some(Outcome::Incorrect),
+            _ => None,
+        }
+    }
+
+    fn is_final(&self) -> bool {
+        matches!(self, Outcome::Correct | Outcome::TooHigh | Outcome::TooLow | Outcome::Incorrect)
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Outcome::RateLimited(wait) if !wait.is_empty() => format!("rate limited, {} left to wait", wait),
+            _ => self.label().to_owned(),
+        }
+    }
+}
+
+fn previous_outcome(year: u32, day: u32, part: u8, answer: &str) -> Option<Outcome> {
+    let submissions = read_to_string(SUBMISSIONS).unwrap_or_default();
+    let puzzle = [year.to_string(), day.to_string(), part.to_string()];
+    submissions.lines().rev()
+        .find_map(|line| match line.split('\t').collect::<Vec<&str>>().as_slice() {
+            &[_, y, d, p, a, outcome] if [y, d, p] == puzzle && a == answer => Outcome::from_label(outcome),
+            _ => None,
+        })
+}
+
+fn record(year: u32, day: u32, part: u8, answer: &str, outcome: &Outcome) -> io::Result<()> {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let mut submissions = OpenOptions::new().create(true).append(true).open(SUBMISSIONS)?;
+    writeln!(submissions, "{}\t{}\t{}\t{}\t{}\t{}", timestamp, year, day, part, answer, outcome.label())
+}
+
+pub fn submit(year: u32, day: u32, part: u8, answer: &str) -> io::Result<Outcome> {
+    if let Some(outcome) = previous_outcome(year, day, part, answer) {
+        return Ok(outcome);
+    }
+    let outcome = Outcome::parse(&post_answer(year, day, part, answer)?);
+    if outcome.is_final() {
+        record(year, day, part, answer, &outcome)?;
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn understands_submission_responses() {
+        assert_eq!(Outcome::parse("<p>That's the right answer! You are one gold star closer.</p>"), Outcome::Correct);
+        assert_eq!(Outcome::parse("<p>That's not the right answer; your answer is too high.</p>"), Outcome::TooHigh);
+        assert_eq!(Outcome::parse("<p>That's not the right answer; your answer is too low.</p>"), Outcome::TooLow);
+        assert_eq!(Outcome::parse("<p>That's not the right answer. If you're stuck...</p>"), Outcome::Incorrect);
+        assert_eq!(Outcome::parse("<p>You don't seem to be solving the right level.</p>"), Outcome::AlreadySolved);
+    }
+
+    #[test]
+    fn knows_how_long_to_wait_when_rate_limited() {
+        let response = "<p>You gave an answer too recently. You have 41s left to wait.</p>";
+        assert_eq!(Outcome::parse(response), Outcome::RateLimited("41s".to_owned()));
+    }
+}
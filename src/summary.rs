@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use crate::bench::format_duration;
+use crate::memory::{format_bytes, Allocations};
+use crate::puzzle::Part;
+use crate::runner::PartRun;
+
+const SLOWEST: usize = 3;
+
+pub struct Summary {
+    pub wall_time: Duration,
+    pub stars: usize,
+    pub slowest: Vec<(u32, Part, Duration)>,
+    pub failures: Vec<(u32, Part, String)>,
+    pub allocations: Vec<(u32, Part, Allocations)>,
+}
+
+impl Summary {
+    pub fn of(runs: &[PartRun], wall_time: Duration) -> Summary {
+        let mut slowest: Vec<_> = runs.iter()
+            .filter(|run| run.solution.is_ok())
+            .map(|run| (run.day, run.part, run.runtime()))
+            .collect();
+        slowest.sort_by_key(|&(_, _, runtime)| std::cmp::Reverse(runtime));
+        slowest.truncate(SLOWEST);
+        let failures = runs.iter()
+            .filter_map(|run| run.solution.as_ref().err().map(|error| (run.day, run.part, error.to_string())))
+            .collect();
+        let allocations = runs.iter()
+            .filter_map(|run| run.allocations.map(|allocations| (run.day, run.part, allocations)))
+            .collect();
+        Summary { wall_time, stars: runs.iter().filter(|run| run.solution.is_ok()).count(), slowest, failures, allocations }
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{} stars in {}\n", self.stars, format_duration(self.wall_time));
+        if !self.slowest.is_empty() {
+            text += "Slowest parts:\n";
+        }
+        for (day, part, runtime) in &self.slowest {
+            text += &format!("  day {:0>2} part {}: {}\n", day, part, format_duration(*runtime));
+        }
+        if !self.failures.is_empty() {
+            text += &format!("{} failure(s):\n", self.failures.len());
+        }
+        for (day, part, error) in &self.failures {
+            text += &format!("  day {:0>2} part {}: {}\n", day, part, error);
+        }
+        if !self.allocations.is_empty() {
+            text += "Allocations:\n";
+        }
+        for (day, part, allocations) in &self.allocations {
+            text += &format!("  day {:0>2} part {}: {} allocations, {} in total, {} at peak\n", day, part, allocations.count,
+                             format_bytes(allocations.bytes), format_bytes(allocations.peak));
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AocError;
+    use crate::puzzle::Solution;
+
+    use super::*;
+
+    fn part_run(day: u32, part: Part, millis: u64) -> PartRun {
+        let solution = Solution { answer: "42".to_owned(), parse_time: Duration::ZERO, solve_time: Duration::from_millis(millis) };
+        PartRun { day, part, solution: Ok(solution), allocations: None, timings: None }
+    }
+
+    #[test]
+    fn summarizes_stars_slowest_parts_and_failures() {
+        let runs = vec![
+            part_run(1, Part::One, 1),
+            part_run(1, Part::Two, 4),
+            part_run(2, Part::One, 2),
+            part_run(2, Part::Two, 3),
+            PartRun { day: 5, part: Part::Two, solution: Err(AocError::OverBudget(Duration::from_secs(15))), allocations: None, timings: None },
+        ];
+        assert_eq!(Summary::of(&runs, Duration::from_millis(12)).to_text(), "\
+4 stars in 12.0ms
+Slowest parts:
+  day 01 part 2: 4.0ms
+  day 02 part 2: 3.0ms
+  day 02 part 1: 2.0ms
+1 failure(s):
+  day 05 part 2: over time budget of 15s
+");
+    }
+
+    #[test]
+    fn reports_allocations_of_measured_parts() {
+        let mut run = part_run(3, Part::One, 5);
+        run.allocations = Some(Allocations { count: 1200, bytes: 3 * 1_048_576, peak: 1536 });
+        assert!(Summary::of(&[run], Duration::from_millis(5)).to_text().ends_with("\
+Allocations:
+  day 03 part 1: 1200 allocations, 3.0MiB in total, 1.5KiB at peak
+"));
+    }
+}
@@ -0,0 +1,128 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::grid::Grid;
+use crate::vec2::Vec2;
+
+/// How a shape is filled and outlined, colors being any SVG color such as `"green"` or `"#1e90ff"`.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct Paint {
+    pub fill: Option<String>,
+    pub stroke: Option<String>,
+    pub stroke_width: f64,
+}
+
+impl Paint {
+    pub fn fill(color: &str) -> Paint {
+        Paint { fill: Some(color.to_owned()), ..Paint::default() }
+    }
+
+    pub fn stroke(color: &str, width: f64) -> Paint {
+        Paint { stroke: Some(color.to_owned()), stroke_width: width, ..Paint::default() }
+    }
+
+    pub fn outlined(self, color: &str, width: f64) -> Paint {
+        Paint { stroke: Some(color.to_owned()), stroke_width: width, ..self }
+    }
+
+    fn attributes(&self) -> String {
+        let mut attributes = format!("fill=\"{}\"", self.fill.as_deref().unwrap_or("none"));
+        if let Some(stroke) = &self.stroke {
+            attributes += &format!(" stroke=\"{}\" stroke-width=\"{}\"", stroke, self.stroke_width);
+        }
+        attributes
+    }
+}
+
+/// A picture of cells of a 2D puzzle, each cell being a unit square drawn `scale` pixels wide, which adjusts its
+/// bounds to the shapes drawn.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Svg {
+    scale: usize,
+    bounds: Option<(Vec2, Vec2)>,
+    elements: Vec<String>,
+}
+
+impl Svg {
+    pub fn new(scale: usize) -> Svg {
+        Svg { scale, bounds: None, elements: Vec::new() }
+    }
+
+    fn include(&mut self, cell: Vec2) {
+        self.bounds = Some(match self.bounds {
+            None => (cell, cell),
+            Some((min, max)) => (Vec2::new(min.x.min(cell.x), min.y.min(cell.y)), Vec2::new(max.x.max(cell.x), max.y.max(cell.y))),
+        });
+    }
+
+    /// Draws the cells of `grid` that `paint` styles, leaving the others blank.
+    pub fn grid<T>(&mut self, grid: &Grid<T>, paint: impl Fn((usize, usize), &T) -> Option<Paint>) -> &mut Svg {
+        for (position, cell) in grid.cells() {
+            self.include(Vec2::new(position.0 as isize, position.1 as isize));
+            if let Some(paint) = paint(position, cell) {
+                self.elements.push(format!("<rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" {}/>", position.0, position.1, paint.attributes()));
+            }
+        }
+        self
+    }
+
+    /// Draws the closed polygon joining the centers of the cells at `vertices`.
+    pub fn polygon(&mut self, vertices: &[Vec2], paint: &Paint) -> &mut Svg {
+        vertices.iter().for_each(|&vertex| self.include(vertex));
+        self.elements.push(format!("<polygon points=\"{}\" {}/>", centers(vertices), paint.attributes()));
+        self
+    }
+
+    /// Draws the open path going through the centers of the cells at `points`.
+    pub fn path(&mut self, points: &[Vec2], paint: &Paint) -> &mut Svg {
+        points.iter().for_each(|&point| self.include(point));
+        self.elements.push(format!("<polyline points=\"{}\" {}/>", centers(points), paint.attributes()));
+        self
+    }
+
+    /// Writes the picture to `path`, to be opened in a browser.
+    pub fn write(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+}
+
+fn centers(cells: &[Vec2]) -> String {
+    cells.iter().map(|cell| format!("{},{}", cell.x as f64 + 0.5, cell.y as f64 + 0.5)).collect::<Vec<_>>().join(" ")
+}
+
+impl Display for Svg {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (min, max) = self.bounds.unwrap_or_default();
+        let (width, height) = (max.x - min.x + 1, max.y - min.y + 1);
+        writeln!(f, "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\" width=\"{}\" height=\"{}\">",
+                 min.x, min.y, width, height, width as usize * self.scale, height as usize * self.scale)?;
+        for element in &self.elements {
+            writeln!(f, "  {}", element)?;
+        }
+        writeln!(f, "</svg>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draws_grids_polygons_and_paths() {
+        let grid = Grid::new(2, vec!['#', '.', '.', '#']);
+        let mut svg = Svg::new(10);
+        svg.grid(&grid, |_, &c| (c == '#').then(|| Paint::fill("black")))
+            .polygon(&[Vec2::new(0, 0), Vec2::new(2, 0), Vec2::new(2, 2)], &Paint::fill("green").outlined("red", 0.1))
+            .path(&[Vec2::new(-1, 0), Vec2::new(0, 0)], &Paint::stroke("blue", 0.2));
+        assert_eq!(svg.to_string(), concat!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-1 0 4 3\" width=\"40\" height=\"30\">\n",
+            "  <rect x=\"0\" y=\"0\" width=\"1\" height=\"1\" fill=\"black\"/>\n",
+            "  <rect x=\"1\" y=\"1\" width=\"1\" height=\"1\" fill=\"black\"/>\n",
+            "  <polygon points=\"0.5,0.5 2.5,0.5 2.5,2.5\" fill=\"green\" stroke=\"red\" stroke-width=\"0.1\"/>\n",
+            "  <polyline points=\"-0.5,0.5 0.5,0.5\" fill=\"none\" stroke=\"blue\" stroke-width=\"0.2\"/>\n",
+            "</svg>\n",
+        ));
+    }
+}
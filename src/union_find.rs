@@ -0,0 +1,100 @@
+/// Disjoint sets of the elements `0..n`, with path compression and union by rank.
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+    sizes: Vec<usize>,
+    count: usize,
+}
+
+impl UnionFind {
+    pub fn new(elements: usize) -> UnionFind {
+        UnionFind { parents: (0..elements).collect(), ranks: vec![0; elements], sizes: vec![1; elements], count: elements }
+    }
+
+    /// Representative of the set containing `element`.
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        let mut current = element;
+        while self.parents[current] != root {
+            current = std::mem::replace(&mut self.parents[current], root);
+        }
+        root
+    }
+
+    /// Merges the sets of `a` and `b`, telling whether they were distinct.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (root, child) = if self.ranks[a] < self.ranks[b] { (b, a) } else { (a, b) };
+        self.parents[child] = root;
+        self.sizes[root] += self.sizes[child];
+        if self.ranks[root] == self.ranks[child] {
+            self.ranks[root] += 1;
+        }
+        self.count -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Number of elements in the set containing `element`.
+    pub fn size_of(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    /// Number of disjoint sets.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Elements of every set, sets ordered by their smallest element.
+    pub fn sets(&mut self) -> Vec<Vec<usize>> {
+        let mut sets: Vec<Vec<usize>> = Vec::with_capacity(self.count);
+        let mut set_of_root = vec![usize::MAX; self.parents.len()];
+        for element in 0..self.parents.len() {
+            let root = self.find(element);
+            if set_of_root[root] == usize::MAX {
+                set_of_root[root] = sets.len();
+                sets.push(Vec::new());
+            }
+            sets[set_of_root[root]].push(element);
+        }
+        sets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_sets() {
+        let mut sets = UnionFind::new(6);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(4, 3));
+        assert!(sets.union(1, 4));
+        assert!(!sets.union(0, 3));
+        assert!(sets.connected(3, 1));
+        assert!(!sets.connected(2, 5));
+        assert_eq!((sets.count(), sets.size_of(4), sets.size_of(5)), (3, 4, 1));
+        assert_eq!(sets.sets(), vec![vec![0, 1, 3, 4], vec![2], vec![5]]);
+    }
+
+    #[test]
+    fn keeps_long_chains_shallow() {
+        let mut sets = UnionFind::new(1000);
+        (1..1000).for_each(|element| { sets.union(element - 1, element); });
+        let root = sets.find(999);
+        assert!((0..1000).all(|element| sets.find(element) == root));
+        assert!(sets.parents.iter().all(|&parent| parent == root));
+    }
+}
@@ -0,0 +1,64 @@
+use crate::error::{AocError, Result};
+
+#[derive(Debug, Copy, Clone)]
+pub enum Rule {
+    MinLines(usize),
+    Rectangular,
+    Charset(&'static str),
+    NoTrailingBlankLines,
+}
+
+pub const DEFAULT_RULES: &[Rule] = &[Rule::MinLines(1), Rule::NoTrailingBlankLines];
+
+impl Rule {
+    fn check(&self, input: &[String]) -> Result<()> {
+        match *self {
+            Rule::MinLines(count) if input.len() < count => Err(AocError::parse(input.len(), "", format!("expected at least {} lines, input may be truncated", count))),
+            Rule::Rectangular => match input.iter().position(|line| line.len() != input[0].len()) {
+                Some(index) => Err(AocError::parse(index + 1, &input[index], format!("expected {} columns", input[0].len()))),
+                None => Ok(()),
+            },
+            Rule::Charset(allowed) => match input.iter().position(|line| !line.chars().all(|c| allowed.contains(c))) {
+                Some(index) => Err(AocError::parse(index + 1, &input[index], "unexpected character")),
+                None => Ok(()),
+            },
+            Rule::NoTrailingBlankLines if input.last().is_some_and(|line| line.trim().is_empty()) => Err(AocError::parse(input.len(), "", "trailing blank line")),
+            _ => Ok(()),
+        }
+    }
+}
+
+pub fn validate(input: &[String], rules: &[Rule]) -> Result<()> {
+    rules.iter().try_for_each(|rule| rule.check(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(input: &[&str]) -> Vec<String> {
+        input.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn accepts_input_following_every_rule() {
+        let rules = [Rule::MinLines(2), Rule::Rectangular, Rule::Charset(".#"), Rule::NoTrailingBlankLines];
+        assert!(validate(&lines(&["#..", ".#."]), &rules).is_ok());
+    }
+
+    #[test]
+    fn reports_truncated_input() {
+        assert_eq!(validate(&lines(&["LR"]), &[Rule::MinLines(3)]).unwrap_err().to_string(),
+                   r#"line 1: expected at least 3 lines, input may be truncated in """#);
+        assert_eq!(validate(&lines(&["#..", ".#"]), &[Rule::Rectangular]).unwrap_err().to_string(),
+                   r#"line 2: expected 3 columns in ".#""#);
+    }
+
+    #[test]
+    fn reports_unexpected_characters_and_blank_lines() {
+        assert_eq!(validate(&lines(&["#..", ".x."]), &[Rule::Charset(".#")]).unwrap_err().to_string(),
+                   r#"line 2: unexpected character in ".x.""#);
+        assert_eq!(validate(&lines(&["1", ""]), DEFAULT_RULES).unwrap_err().to_string(),
+                   r#"line 2: trailing blank line in """#);
+    }
+}
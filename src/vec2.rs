@@ -0,0 +1,153 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+use serde::Serialize;
+
+/// A position or a displacement on a grid, `y` growing downwards.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default, Hash, PartialOrd, Ord, Serialize)]
+pub struct Vec2 {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2::new(0, 0);
+
+    /// Offsets of the eight cells around a cell, row by row.
+    pub const AROUND: [Vec2; 8] = [
+        Vec2::new(-1, -1), Vec2::new(0, -1), Vec2::new(1, -1),
+        Vec2::new(-1, 0), Vec2::new(1, 0),
+        Vec2::new(-1, 1), Vec2::new(0, 1), Vec2::new(1, 1),
+    ];
+
+    pub const fn new(x: isize, y: isize) -> Vec2 {
+        Vec2 { x, y }
+    }
+
+    pub fn rotate_left(self) -> Vec2 {
+        Vec2::new(self.y, -self.x)
+    }
+
+    pub fn rotate_right(self) -> Vec2 {
+        Vec2::new(-self.y, self.x)
+    }
+
+    pub fn manhattan_length(self) -> usize {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    pub fn neighbors(self) -> impl Iterator<Item=Vec2> {
+        Direction::ALL.into_iter().map(move |direction| self + direction.offset())
+    }
+
+    pub fn surroundings(self) -> impl Iterator<Item=Vec2> {
+        Vec2::AROUND.into_iter().map(move |offset| self + offset)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, other: Vec2) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<isize> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, factor: isize) -> Vec2 {
+        Vec2::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash, PartialOrd, Ord, Serialize)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Directions clockwise, starting upwards.
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+    pub fn offset(self) -> Vec2 {
+        match self {
+            Direction::Up => Vec2::new(0, -1),
+            Direction::Right => Vec2::new(1, 0),
+            Direction::Down => Vec2::new(0, 1),
+            Direction::Left => Vec2::new(-1, 0),
+        }
+    }
+
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn opposite(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combines_vectors() {
+        let position = Vec2::new(3, 1);
+        assert_eq!(position + Vec2::new(-1, 2), Vec2::new(2, 3));
+        assert_eq!(position - Vec2::new(-1, 2), Vec2::new(4, -1));
+        assert_eq!(position * 3, Vec2::new(9, 3));
+        assert_eq!(-position, Vec2::new(-3, -1));
+        assert_eq!((position - Vec2::new(5, 8)).manhattan_length(), 9);
+    }
+
+    #[test]
+    fn rotates_quarter_turns_with_y_growing_downwards() {
+        assert_eq!(Direction::Up.offset().rotate_right(), Direction::Right.offset());
+        assert_eq!(Direction::Up.offset().rotate_left(), Direction::Left.offset());
+        assert_eq!(Vec2::new(2, 1).rotate_right().rotate_right(), Vec2::new(-2, -1));
+    }
+
+    #[test]
+    fn turns_directions() {
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Down.opposite(), Direction::Up);
+        assert!(Direction::ALL.iter().all(|d| d.turn_right().offset() == d.offset().rotate_right()));
+    }
+
+    #[test]
+    fn lists_surrounding_positions() {
+        assert_eq!(Vec2::ZERO.neighbors().collect::<Vec<_>>(), vec![Vec2::new(0, -1), Vec2::new(1, 0), Vec2::new(0, 1), Vec2::new(-1, 0)]);
+        assert_eq!(Vec2::new(5, 5).surroundings().filter(|p| (p.x - 5).abs() + (p.y - 5).abs() == 2).count(), 4);
+    }
+}
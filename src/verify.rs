@@ -0,0 +1,79 @@
+use std::path::Path;
+
+use crate::answers::Answers;
+use crate::error::Result;
+use crate::input::{daily_example, daily_input, example_path, read_lines, user_answers_path, user_input_path};
+use crate::puzzle::{Part, Solver};
+
+#[derive(PartialEq, Debug)]
+pub enum Verdict {
+    Correct,
+    Wrong { expected: String },
+    Unknown,
+}
+
+impl Verdict {
+    fn of(answer: &str, expected: Option<&str>) -> Verdict {
+        match expected {
+            Some(expected) if expected == answer => Verdict::Correct,
+            Some(expected) => Verdict::Wrong { expected: expected.to_owned() },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+pub struct Verification {
+    pub day: u32,
+    pub part: Part,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+impl Verification {
+    pub fn is_wrong(&self) -> bool {
+        matches!(self.verdict, Verdict::Wrong { .. })
+    }
+}
+
+fn check(solver: &Solver, input: Vec<String>, answers: &Answers) -> Result<Vec<Verification>> {
+    Part::both().into_iter()
+        .map(|part| {
+            let answer = (solver.solve)(part, input.clone())?.answer;
+            let verdict = Verdict::of(&answer, answers.get(solver.year, solver.day, part));
+            Ok(Verification { day: solver.day, part, answer, verdict })
+        })
+        .collect()
+}
+
+pub fn verify(solver: &Solver, answers: &Answers) -> Result<Vec<Verification>> {
+    check(solver, daily_input(solver.year, solver.day)?, answers)
+}
+
+pub fn verify_user(solver: &Solver, user: &str) -> Result<Option<Vec<Verification>>> {
+    let input = user_input_path(solver.year, user, solver.day);
+    if !Path::new(&input).exists() {
+        return Ok(None);
+    }
+    let answers = Answers::load_from(&user_answers_path(solver.year, user))?;
+    check(solver, read_lines(input)?, &answers).map(Some)
+}
+
+pub fn verify_example(solver: &Solver) -> Result<Option<Vec<Verification>>> {
+    if !Path::new(&example_path(solver.year, solver.day)).exists() {
+        return Ok(None);
+    }
+    let answers = Answers::load_example(solver.year, solver.day)?;
+    check(solver, daily_example(solver.year, solver.day)?, &answers).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_answer_with_expected_one() {
+        assert_eq!(Verdict::of("6440", Some("6440")), Verdict::Correct);
+        assert_eq!(Verdict::of("6440", Some("5905")), Verdict::Wrong { expected: "5905".to_owned() });
+        assert_eq!(Verdict::of("6440", None), Verdict::Unknown);
+    }
+}
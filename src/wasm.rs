@@ -0,0 +1,21 @@
+use wasm_bindgen::prelude::*;
+
+use crate::puzzle::{solver, split_lines, Part, DEFAULT_YEAR};
+
+#[wasm_bindgen]
+pub fn solve(day: u32, part: u8, input: &str) -> Result<String, JsError> {
+    let solver = solver(DEFAULT_YEAR, day).ok_or_else(|| JsError::new(&format!("Day {} is not solved yet", day)))?;
+    let part = Part::from_number(part).ok_or_else(|| JsError::new(&format!("Part {} does not exist", part)))?;
+    let solution = (solver.solve)(part, split_lines(input)).map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(solution.answer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_puzzle_given_as_text() {
+        assert_eq!(solve(7, 2, include_str!("examples/2023/day07.txt")).ok(), Some("5905".to_owned()));
+    }
+}
@@ -0,0 +1,90 @@
+use crate::grid::Grid;
+use crate::vec2::Vec2;
+
+/// SplitMix64 step, turning any 64 bits into well mixed ones.
+fn mix(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Random keys for every value of every cell of a grid, hashing a grid state to the exclusive or of the keys of its
+/// cells. Changing a cell updates the hash in constant time, so large boards can be remembered by their hash alone.
+/// Keys derive from a fixed seed, making hashes stable from run to run.
+#[derive(Debug, Clone)]
+pub struct Zobrist {
+    values: usize,
+    keys: Vec<u64>,
+}
+
+impl Zobrist {
+    /// Keys for `cells` cells each holding one of `values` values.
+    pub fn new(cells: usize, values: usize) -> Zobrist {
+        Zobrist { values, keys: (0..cells * values).map(|index| mix(index as u64)).collect() }
+    }
+
+    /// Keys fitting the cells of `grid`.
+    pub fn for_grid<T>(grid: &Grid<T>, values: usize) -> Zobrist {
+        Zobrist::new(grid.width() * grid.height(), values)
+    }
+
+    pub fn key(&self, cell: usize, value: usize) -> u64 {
+        self.keys[cell * self.values + value]
+    }
+
+    /// Hash of `grid`, `value_of` telling which value each cell holds, if any worth hashing.
+    pub fn hash<T>(&self, grid: &Grid<T>, value_of: impl Fn(&T) -> Option<usize>) -> u64 {
+        grid.cells()
+            .filter_map(|((x, y), cell)| value_of(cell).map(|value| self.key(y * grid.width() + x, value)))
+            .fold(0, |hash, key| hash ^ key)
+    }
+
+    /// Hash of the state of a grid once the `cell` of a state hashed to `hash` changes from `from` to `to`, cells being
+    /// numbered row by row.
+    pub fn update(&self, hash: u64, cell: usize, from: Option<usize>, to: Option<usize>) -> u64 {
+        let key = |value: Option<usize>| value.map_or(0, |value| self.key(cell, value));
+        hash ^ key(from) ^ key(to)
+    }
+}
+
+/// Hash of a set of points, whatever the order they come in. Points must be distinct, as repeated ones cancel out.
+pub fn hash_points(points: impl IntoIterator<Item=Vec2>) -> u64 {
+    points.into_iter().fold(0, |hash, point| hash ^ mix(mix(point.x as u64) ^ point.y as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rocks(rows: &[&str]) -> Grid<char> {
+        Grid::of_chars(&rows.iter().map(|row| row.to_string()).collect::<Vec<_>>()).unwrap()
+    }
+
+    fn value_of(cell: &char) -> Option<usize> {
+        match cell {
+            'O' => Some(0),
+            '#' => Some(1),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn hashes_grid_states_incrementally() {
+        let before = rocks(&["O.#", ".O."]);
+        let after = rocks(&["O.#", "O.."]);
+        let zobrist = Zobrist::for_grid(&before, 2);
+        let hash = zobrist.hash(&before, value_of);
+        assert_ne!(hash, zobrist.hash(&after, value_of));
+        let moved = zobrist.update(zobrist.update(hash, 4, Some(0), None), 3, None, Some(0));
+        assert_eq!(moved, zobrist.hash(&after, value_of));
+        assert_eq!(Zobrist::new(6, 2).hash(&before, value_of), hash);
+    }
+
+    #[test]
+    fn hashes_sets_of_points_regardless_of_order() {
+        let points = [Vec2::new(1, 2), Vec2::new(-3, 4), Vec2::new(2, 1)];
+        assert_eq!(hash_points(points), hash_points(points.into_iter().rev()));
+        assert_ne!(hash_points(points), hash_points([Vec2::new(1, 2), Vec2::new(-3, 4), Vec2::new(1, 2)]));
+    }
+}